@@ -1,4 +1,4 @@
-use clap::{Args, Parser, Subcommand, ValueHint};
+use clap::{Args, Parser, Subcommand, ValueEnum, ValueHint};
 
 /// 1. First interaction
 /// drand get --url https://drand.cloudflare.com # latest beacon
@@ -20,6 +20,15 @@ use clap::{Args, Parser, Subcommand, ValueHint};
 pub struct Cli {
     #[clap(flatten)]
     pub verbose: clap_verbosity_flag::Verbosity,
+    /// Seconds to wait for a remote to respond before giving up.
+    #[arg(
+        long,
+        global = true,
+        env = "DEE_TIMEOUT",
+        default_value_t = 10,
+        value_name = "SECONDS"
+    )]
+    pub timeout: u64,
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -29,16 +38,21 @@ pub enum Commands {
     /// Interact with timelock encryption
     ///
     /// INPUT defaults to standard input, and OUTPUT defaults to standard output.
+    /// When INPUT is a file and stderr a terminal, progress is reported on stderr, unless --quiet is set.
     ///
     /// ROUND can be:
     /// * a specific round (123),
-    /// * a duration (30s),
-    /// * an RFC3339 date (2023-06-28 21:30:22)
+    /// * a duration, negative to look back in time (30s, 1h30m, -10m),
+    /// * an RFC3339 date (2023-06-28 21:30:22),
+    /// * a unix timestamp prefixed with @ (@1700000000)
     ///
     /// UPSTREAM is an existing remote, and defaults to the lastest used.
     ///
+    /// RECIPIENT is an additional remote to encrypt to, at the same time. A beacon of any of these remotes decrypts the input.
+    ///
     /// Example:
     ///     $ tar cvz ~/data | dee crypt -u myremote -r 30s > data.tar.gz.age
+    ///     $ tar cvz ~/data | dee crypt -u myremote --recipient otherremote -r 30s > data.tar.gz.age
     ///     $ dee crypt --decrypt -o data.tar.gz data.tar.gz.age
     #[command(verbatim_doc_comment)]
     Crypt {
@@ -58,10 +72,14 @@ pub enum Commands {
         /// Encrypt to the specified ROUND.
         /// ROUND can be:
         /// * a specific round. e.g. 123,
-        /// * a duration. e.g. 30s,
-        /// * an RFC3339 date. e.g. 2023-06-28 21:30:22
-        #[arg(short, long, verbatim_doc_comment)]
+        /// * a duration, negative to look back in time. e.g. 30s, 1h30m, -10m,
+        /// * an RFC3339 date. e.g. 2023-06-28 21:30:22,
+        /// * a unix timestamp prefixed with @. e.g. @1700000000
+        #[arg(short, long, allow_hyphen_values = true, verbatim_doc_comment)]
         round: Option<String>,
+        /// Also encrypt to the remote RECIPIENT. Can be repeated.
+        #[arg(long = "recipient", value_name = "RECIPIENT")]
+        recipients: Vec<String>,
         /// Encrypt to a PEM encoded format.
         #[arg(short, long)]
         armor: bool,
@@ -80,6 +98,8 @@ pub enum Commands {
     /// Example:
     ///     $ dee rand -u myremote 1000
     ///     $ dee rand -l
+    ///     $ dee rand -10m
+    ///     $ dee rand --watch --json --count 10
     #[command(verbatim_doc_comment)]
     Rand {
         /// Set default upstream. If empty, use the lastest upstream.
@@ -97,10 +117,63 @@ pub enum Commands {
         /// Round number to retrieve.
         /// ROUND can be:
         /// * a specific round. e.g. 123,
-        /// * a duration. e.g. 30s,
+        /// * a duration, negative to look back in time. e.g. 30s, 1h30m, -10m,
         /// * an RFC3339 date. e.g. 2023-06-28 21:30:22,
+        /// * a unix timestamp prefixed with @. e.g. @1700000000,
         /// * empty to retrieve the latest round
+        #[arg(allow_hyphen_values = true)]
         beacon: Option<String>,
+        /// Keep printing beacons as they are published, until interrupted.
+        #[arg(short, long, default_value_t = false, conflicts_with = "beacon")]
+        watch: bool,
+        /// Stop watching after COUNT beacons.
+        #[arg(long, requires = "watch")]
+        count: Option<u64>,
+        /// Encoding of the randomness in the default output.
+        #[arg(long = "format", value_enum, default_value_t = RandomnessFormat::Hex, conflicts_with_all = ["long", "json", "raw"])]
+        randomness_format: RandomnessFormat,
+        /// Write the randomness as raw bytes, without a trailing newline.
+        #[arg(
+            long,
+            default_value_t = false,
+            group = "format",
+            conflicts_with = "watch"
+        )]
+        raw: bool,
+        /// Write raw randomness to the file at path OUTPUT.
+        #[arg(short, long, requires = "raw")]
+        output: Option<String>,
+        /// Write raw randomness to standard output, even when it is a terminal.
+        #[arg(long, default_value_t = false, requires = "raw")]
+        force: bool,
+    },
+    /// Verify a beacon offline, against the chain info stored for a remote.
+    ///
+    /// INPUT is a beacon in drand API JSON format, and defaults to standard input.
+    ///
+    /// UPSTREAM is an existing remote, and defaults to the lastest used.
+    ///
+    /// Exits with a non-zero status and the reason if the beacon is not valid.
+    ///
+    /// Example:
+    ///     $ dee rand --json 1000 | dee verify --round 1000
+    ///     $ dee verify -u myremote --json beacon.json
+    #[command(verbatim_doc_comment)]
+    Verify {
+        /// Set default upstream. If empty, use the lastest upstream.
+        #[arg(short = 'u', long)]
+        set_upstream: Option<String>,
+        /// Expected round of the beacon.
+        #[arg(short, long)]
+        round: Option<u64>,
+        /// Enable detailed output
+        #[arg(short, long, default_value_t = false, group = "format")]
+        long: bool,
+        /// Enable json output
+        #[arg(long, default_value_t = false, group = "format")]
+        json: bool,
+        /// Path to a file to read from.
+        input: Option<String>,
     },
     /// Manage set of tracked chains.
     ///
@@ -111,6 +184,55 @@ pub enum Commands {
         #[command(subcommand)]
         command: Option<RemoteCommand>,
     },
+    /// Manage dee configuration.
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+    /// Generate shell completions.
+    ///
+    /// Completions are written to standard output.
+    ///
+    /// Example:
+    ///     $ dee completion bash > /usr/share/bash-completion/completions/dee
+    ///     $ dee completion zsh > "${fpath[1]}/_dee"
+    #[command(verbatim_doc_comment)]
+    Completion {
+        /// Shell to generate completions for.
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum RandomnessFormat {
+    /// Hexadecimal
+    #[default]
+    Hex,
+    /// Standard base64, with padding
+    Base64,
+    /// Decimal integer of the first 8 bytes, read as big-endian
+    Uint64,
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    /// Print the path of the configuration file.
+    Path,
+    /// Print the configuration.
+    ///
+    /// The json output can be imported with dee remote import.
+    Show {
+        /// Enable json output
+        #[arg(long, default_value_t = false)]
+        json: bool,
+    },
+    /// Reset the configuration, removing all remotes.
+    Reset {
+        /// Reset without asking for confirmation.
+        #[arg(short, long, default_value_t = false)]
+        yes: bool,
+    },
 }
 
 #[derive(Args)]
@@ -178,9 +300,120 @@ pub enum RemoteCommand {
         json: bool,
         name: Option<String>,
     },
+    /// Export all remotes, with their chain information, as a JSON document.
+    Export {
+        /// Write the result to the file at path OUTPUT.
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Import remotes from a JSON document created with dee remote export, or dee config show --json.
+    ///
+    /// Remotes which already exist with another URL or chain information are refused, unless --overwrite or --skip-existing is set.
+    Import {
+        /// Replace existing remotes with imported ones.
+        #[arg(long, default_value_t = false, conflicts_with = "skip_existing")]
+        overwrite: bool,
+        /// Keep existing remotes, and only import new ones.
+        #[arg(long, default_value_t = false)]
+        skip_existing: bool,
+        /// Path to a file to read from. Defaults to standard input.
+        input: Option<String>,
+    },
+    /// Refresh the chain information stored for the remote <name>, and print the fields which changed.
+    ///
+    /// Chain information with another chain hash or public key is refused, unless --force is set.
+    Update {
+        /// Update all remotes.
+        #[arg(long, default_value_t = false, conflicts_with = "name")]
+        all: bool,
+        /// Update even if the chain hash or public key changed.
+        #[arg(long, default_value_t = false)]
+        force: bool,
+        name: Option<String>,
+    },
+    /// Check that the remote <name> is reachable, serves the chain it has been added with, and is up to date.
+    ///
+    /// Exits with a non-zero status if any checked remote fails.
+    Check {
+        /// Enable detailed output
+        #[arg(short, long, default_value_t = false, group = "format")]
+        long: bool,
+        /// Enable json output
+        #[arg(long, default_value_t = false, group = "format")]
+        json: bool,
+        /// Check all remotes.
+        #[arg(long, default_value_t = false, conflicts_with = "name")]
+        all: bool,
+        name: Option<String>,
+    },
 }
 
 #[allow(dead_code)]
 pub fn build() -> Cli {
     Cli::parse()
 }
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    fn rand_beacon(args: &[&str]) -> Option<String> {
+        let cli =
+            Cli::try_parse_from(["dee", "rand"].into_iter().chain(args.iter().copied())).unwrap();
+        match cli.command {
+            Commands::Rand { beacon, .. } => beacon,
+            _ => panic!("expected rand command"),
+        }
+    }
+
+    #[test]
+    fn cli_works() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn cli_rand_beacon_works() {
+        assert_eq!(rand_beacon(&[]), None);
+        for beacon in [
+            "1000",
+            "30s",
+            "1h30m",
+            "2024-06-28T21:30:22Z",
+            "2024-06-28 21:30:22",
+            "2024-06-28",
+            "@1700000000",
+        ] {
+            assert_eq!(rand_beacon(&[beacon]), Some(beacon.to_owned()));
+        }
+        // negative durations are accepted as they are by crypt --round, and flags are still recognised
+        assert_eq!(rand_beacon(&["-10m"]), Some("-10m".to_owned()));
+        assert_eq!(
+            rand_beacon(&["--json", "-1h30m"]),
+            Some("-1h30m".to_owned())
+        );
+        assert_eq!(rand_beacon(&["-l"]), None);
+
+        // a beacon cannot be watched
+        assert!(Cli::try_parse_from(["dee", "rand", "--watch", "1000"]).is_err());
+        assert!(Cli::try_parse_from(["dee", "rand", "--count", "3"]).is_err());
+
+        // raw output composes with a round, but not with other formats
+        assert_eq!(
+            rand_beacon(&["--raw", "--verify", "1000"]),
+            Some("1000".to_owned())
+        );
+        assert!(Cli::try_parse_from(["dee", "rand", "--raw", "--json"]).is_err());
+        assert!(Cli::try_parse_from(["dee", "rand", "--force"]).is_err());
+        assert!(Cli::try_parse_from(["dee", "rand", "-o", "seed.bin"]).is_err());
+
+        // randomness encoding only applies to the default output
+        assert_eq!(
+            rand_beacon(&["--format", "uint64", "1000"]),
+            Some("1000".to_owned())
+        );
+        assert!(Cli::try_parse_from(["dee", "rand", "--format", "base64", "--json"]).is_err());
+        assert!(Cli::try_parse_from(["dee", "rand", "--format", "base32"]).is_err());
+    }
+}
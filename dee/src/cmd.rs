@@ -1,4 +1,9 @@
+pub mod completion;
+pub use completion::completion;
+pub mod config;
 pub mod crypt;
 pub mod rand;
 pub use rand::rand;
 pub mod remote;
+pub mod verify;
+pub use verify::verify;
@@ -0,0 +1,30 @@
+use anyhow::Result;
+use clap::CommandFactory;
+use clap_complete::Shell;
+
+use crate::cli::Cli;
+
+/// Completion script of `shell` for dee, generated from the same command definition as the one parsing arguments.
+pub fn completion(shell: Shell) -> Result<String> {
+    let mut cmd = Cli::command();
+    let name = cmd.get_name().to_owned();
+    let mut buffer = vec![];
+    clap_complete::generate(shell, &mut cmd, name, &mut buffer);
+    Ok(String::from_utf8(buffer)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::ValueEnum;
+
+    use super::*;
+
+    #[test]
+    fn completion_works() {
+        for shell in Shell::value_variants() {
+            let script = completion(*shell).unwrap();
+            assert!(!script.is_empty(), "{shell} completion is empty");
+            assert!(script.contains("dee"), "{shell} completion misses dee");
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, BufRead, Write},
+};
+
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::config::{self, ConfigChain};
+
+/// Configuration as it is dumped, with remotes sorted by name so that the output is stable.
+/// This is the format `dee remote import` accepts on top of exported remotes.
+#[derive(Serialize)]
+struct ConfigDump {
+    upstream: Option<String>,
+    chains: BTreeMap<String, ConfigChain>,
+}
+
+impl From<&config::Local> for ConfigDump {
+    fn from(cfg: &config::Local) -> Self {
+        Self {
+            upstream: cfg.upstream(),
+            chains: cfg.chains().into_iter().collect(),
+        }
+    }
+}
+
+pub fn path() -> Result<String> {
+    config::Local::path()
+}
+
+pub fn show(cfg: &config::Local, json: bool) -> Result<String> {
+    let dump = ConfigDump::from(cfg);
+    if json {
+        return serde_json::to_string_pretty(&dump).map_err(|e| anyhow!(e));
+    }
+
+    let mut output = vec![
+        format!("{: <10}: {}", "Path".bold(), config::Local::path()?),
+        format!(
+            "{: <10}: {}",
+            "Upstream".bold(),
+            dump.upstream.unwrap_or_default()
+        ),
+    ];
+    for (name, chain) in dump.chains {
+        output.push(format!(
+            "{: <10}: {name: <20}\t{url}",
+            "Remote".bold(),
+            url = chain.url()
+        ));
+    }
+    Ok(output.join("\n"))
+}
+
+pub fn reset(cfg: &mut config::Local, yes: bool) -> Result<String> {
+    if !yes {
+        eprint!(
+            "Reset configuration at {}, removing all remotes? [y/N] ",
+            config::Local::path()?
+        );
+        io::stderr().flush()?;
+        let mut answer = String::new();
+        io::stdin().lock().read_line(&mut answer)?;
+        if !matches!(answer.trim(), "y" | "Y" | "yes") {
+            return Err(anyhow!("configuration has not been reset."));
+        }
+    }
+    *cfg = config::Local::default();
+    Ok(String::new())
+}
@@ -1,14 +1,20 @@
-use std::{cmp::Ordering, fs, io};
+use std::{
+    cmp::Ordering,
+    fs,
+    io::{self, BufRead},
+    iter,
+};
 
+use age_core::format::{FileKey, Stanza};
 use anyhow::{anyhow, Result};
 use colored::Colorize;
 use drand_core::{
-    beacon::{BeaconError, RandomnessBeaconTime},
+    beacon::{RandomnessBeacon, RandomnessBeaconTime},
     chain::ChainInfo,
-    ChainOptions, DrandError, HttpClient,
+    ChainOptions,
 };
+use indicatif::{ProgressBar, ProgressStyle};
 use serde::Serialize;
-use tlock_age::Header;
 
 use crate::{
     config::{self, ConfigChain},
@@ -25,6 +31,33 @@ pub fn file_or_stdin(input: Option<String>) -> Result<Box<dyn io::Read>> {
     Ok(reader)
 }
 
+/// Input reader reporting its progress on stderr when `progress` is set, and the input is a file of known size.
+/// The progress bar is cleared once the returned [`Progress`] is dropped.
+fn file_or_stdin_with_progress(
+    input: Option<String>,
+    progress: bool,
+) -> Result<(Box<dyn io::Read>, Progress)> {
+    let len = match (progress, input.as_ref()) {
+        (true, Some(path)) => fs::metadata(path).ok().map(|metadata| metadata.len()),
+        _ => None,
+    };
+    let src = file_or_stdin(input)?;
+
+    let Some(len) = len else {
+        return Ok((src, Progress(ProgressBar::hidden())));
+    };
+    // progress is drawn on stderr, which leaves the output written to stdout untouched
+    let bar = ProgressBar::new(len).with_style(
+        ProgressStyle::with_template("{bytes}/{total_bytes} [{wide_bar}] {bytes_per_sec}, {eta}")?
+            .progress_chars("=> "),
+    );
+    let src = ProgressReader::new(src, {
+        let bar = bar.clone();
+        move |n| bar.inc(n)
+    });
+    Ok((Box::new(src), Progress(bar)))
+}
+
 pub fn file_or_stdout(output: Option<String>) -> Result<Box<dyn io::Write>> {
     let writer: Box<dyn io::Write> = match output {
         Some(path) => Box::new(io::BufWriter::new(
@@ -36,11 +69,12 @@ pub fn file_or_stdout(output: Option<String>) -> Result<Box<dyn io::Write>> {
 }
 
 pub fn encrypt(
-    _cfg: &config::Local,
+    cfg: &config::Local,
     output: Option<String>,
     input: Option<String>,
     armor: bool,
     chain: ConfigChain,
+    recipients: Vec<(String, ConfigChain)>,
     round: Option<String>,
 ) -> Result<String> {
     let info = chain.info();
@@ -48,9 +82,13 @@ pub fn encrypt(
         return Err(anyhow!("remote must use unchained signatures"));
     }
 
-    let beacon_time = crate::time::round_from_option(&chain, round)?;
+    let beacon_time = crate::time::round_from_option(cfg, &chain, round)?;
 
-    let src = file_or_stdin(input)?;
+    if !recipients.is_empty() {
+        return encrypt_to_recipients(cfg, output, input, armor, chain, recipients, beacon_time);
+    }
+
+    let (src, _progress) = file_or_stdin_with_progress(input, cfg.progress())?;
     let dst = file_or_stdout(output)?;
     if armor {
         let mut dst = tlock_age::armor::ArmoredWriter::wrap_output(dst)?;
@@ -96,23 +134,177 @@ pub fn encrypt(
     .map_err(|err| anyhow!(err))
 }
 
-pub fn inspect(
+/// Encrypt to the upstream and every recipient, so that a beacon of any of their chains decrypts the input.
+/// Each chain is encrypted to its first round produced at or after the upstream round.
+fn encrypt_to_recipients(
     cfg: &config::Local,
-    format: Format,
+    output: Option<String>,
     input: Option<String>,
+    armor: bool,
     chain: ConfigChain,
+    recipients: Vec<(String, ConfigChain)>,
+    beacon_time: RandomnessBeaconTime,
 ) -> Result<String> {
-    let src = file_or_stdin(input)?;
-    let header = tlock_age::decrypt_header(src)?;
+    let info = chain.info();
+    let upstream = cfg
+        .chain_by_hash(&info.hash())
+        .map(|(name, _)| name)
+        .unwrap_or_else(|| chain.url());
+    let unix_time = info.time_info().time_of_round(beacon_time.round());
+
+    let mut chains = vec![(upstream, info)];
+    for (name, recipient) in recipients {
+        let info = recipient.info();
+        if chains.iter().any(|(_, chain)| chain.hash() == info.hash()) {
+            continue;
+        }
+        chains.push((name, info));
+    }
+
+    let mut age_recipients: Vec<Box<dyn age::Recipient + Send>> = vec![];
+    for (name, info) in chains {
+        if !info.is_unchained() {
+            return Err(anyhow!("remote '{name}' must use unchained signatures"));
+        }
+        if !info.is_rfc9380() {
+            return Err(anyhow!(
+                "remote '{name}' does not support encryption to multiple remotes"
+            ));
+        }
+        let time_info = info.time_info();
+        let round = match time_info.round_at(unix_time) {
+            round if time_info.time_of_round(round) < unix_time => round + 1,
+            round => round,
+        };
+        age_recipients.push(Box::new(tlock_age::internal::Recipient::new(
+            &info.hash(),
+            &info.public_key(),
+            round,
+        )));
+    }
 
-    let result = if let Some((name, chain_config)) = cfg.chain_by_hash(&header.hash()) {
-        let is_upstream = chain.info().hash() == chain_config.info().hash();
-        InspectResult::new(header, Some(name), is_upstream, Some(chain_config.info()))
+    let (src, _progress) = file_or_stdin_with_progress(input, cfg.progress())?;
+    let dst = file_or_stdout(output)?;
+    if armor {
+        let mut dst = tlock_age::armor::ArmoredWriter::wrap_output(dst)?;
+        encrypt_age(&mut dst, src, age_recipients)?;
+        dst.finish()?;
     } else {
-        InspectResult::new(header, None, false, None)
+        encrypt_age(dst, src, age_recipients)?;
+    }
+    Ok(String::from(""))
+}
+
+fn encrypt_age<W: io::Write, R: io::Read>(
+    dst: W,
+    mut src: R,
+    recipients: Vec<Box<dyn age::Recipient + Send>>,
+) -> Result<()> {
+    let encryptor =
+        age::Encryptor::with_recipients(recipients).ok_or(anyhow!("no recipient provided"))?;
+    let mut writer = encryptor.wrap_output(dst)?;
+    io::copy(&mut src, &mut writer)?;
+    writer.finish()?;
+    Ok(())
+}
+
+/// Timelock recipients of an age encrypted input, as `(round, chain hash)` in header order.
+/// Armored inputs are supported.
+fn read_recipients<R: io::Read>(src: R) -> Result<Vec<(u64, Vec<u8>)>> {
+    let invalid = || anyhow!("input is not timelock encrypted");
+    let reader = io::BufReader::new(age::armor::ArmoredReader::new(src));
+
+    let mut recipients = vec![];
+    for line in reader.lines() {
+        let line = line.map_err(|_e| invalid())?;
+        // the header ends with its MAC, which is followed by the payload
+        if line.starts_with("---") {
+            return match recipients.is_empty() {
+                true => Err(invalid()),
+                false => Ok(recipients),
+            };
+        }
+        let args: Vec<&str> = line.split(' ').collect();
+        if let ["->", "tlock", round, hash] = args[..] {
+            let round = round.parse().map_err(|_e| invalid())?;
+            let hash = hex::decode(hash).map_err(|_e| invalid())?;
+            recipients.push((round, hash));
+        }
+    }
+    Err(invalid())
+}
+
+/// Timelock identity only attempting the stanzas of its chain.
+/// A header can hold stanzas of several chains, which identities of another chain should leave alone.
+struct ChainIdentity {
+    hash: String,
+    inner: tlock_age::internal::Identity,
+}
+
+impl ChainIdentity {
+    fn new(info: &ChainInfo, beacon: &RandomnessBeacon) -> Self {
+        Self {
+            hash: hex::encode(info.hash()),
+            inner: tlock_age::internal::Identity::new(&info.hash(), &beacon.signature()),
+        }
+    }
+}
+
+impl age::Identity for ChainIdentity {
+    fn unwrap_stanza(&self, stanza: &Stanza) -> Option<Result<FileKey, age::DecryptError>> {
+        match stanza.args.get(1) {
+            Some(hash) if stanza.tag == "tlock" && *hash == self.hash => {
+                age::Identity::unwrap_stanza(&self.inner, stanza)
+            }
+            _ => None,
+        }
+    }
+}
+
+fn decrypt_age<W: io::Write, R: io::Read>(
+    mut dst: W,
+    src: R,
+    info: &ChainInfo,
+    beacon: &RandomnessBeacon,
+) -> Result<()> {
+    let identity = ChainIdentity::new(info, beacon);
+    let decryptor = match age::Decryptor::new(age::armor::ArmoredReader::new(src))? {
+        age::Decryptor::Recipients(decryptor) => decryptor,
+        _ => return Err(anyhow!("input is not timelock encrypted")),
     };
+    let mut reader = decryptor.decrypt(iter::once(&identity as &dyn age::Identity))?;
+    io::copy(&mut reader, &mut dst)?;
+    Ok(())
+}
 
-    print_with_format(result, format)
+pub fn inspect(
+    cfg: &config::Local,
+    format: Format,
+    input: Option<String>,
+    chain: ConfigChain,
+) -> Result<String> {
+    let src = file_or_stdin(input)?;
+    let recipients = read_recipients(src)?;
+
+    let results = recipients
+        .into_iter()
+        .map(|(round, hash)| {
+            if let Some((name, chain_config)) = cfg.chain_by_hash(&hash) {
+                let is_upstream = chain.info().hash() == chain_config.info().hash();
+                InspectResult::new(
+                    round,
+                    hash,
+                    Some(name),
+                    is_upstream,
+                    Some(chain_config.info()),
+                )
+            } else {
+                InspectResult::new(round, hash, None, false, None)
+            }
+        })
+        .collect();
+
+    print_with_format(InspectResults(results), format)
 }
 
 pub fn decrypt(
@@ -121,45 +313,92 @@ pub fn decrypt(
     input: Option<String>,
     chain: ConfigChain,
 ) -> Result<String> {
-    let mut src = ResetReader::new(file_or_stdin(input)?);
-    let header = tlock_age::decrypt_header(&mut src)?;
+    let (src, _progress) = file_or_stdin_with_progress(input, cfg.progress())?;
+    let mut src = ResetReader::new(src);
+    let recipients = read_recipients(&mut src)?;
     // Once headers have been read, reset the reader to pass it as if unmodified to tlock_age::decrypt
     // This allows the same reader to be used twice.
     src.reset();
 
-    let info = chain.info();
+    // Recipients are attempted against known remotes, starting with the upstream
+    let upstream = chain.info().hash();
+    let mut candidates: Vec<(u64, String, ConfigChain)> = recipients
+        .iter()
+        .filter_map(|(round, hash)| {
+            cfg.chain_by_hash(hash)
+                .map(|(name, chain)| (*round, name, chain))
+        })
+        .collect();
+    candidates.sort_by_key(|(_, _, chain)| chain.info().hash() != upstream);
+
+    if candidates.is_empty() {
+        let hashes: Vec<String> = recipients
+            .iter()
+            .map(|(_, hash)| hex::encode(hash))
+            .collect();
+        return Err(anyhow!(
+            "decryption failed.\nNo remote for chain {}. Did you forget `dee remote add`?",
+            hashes.join(", ")
+        ));
+    }
 
-    if header.hash() != info.hash() {
-        if let Some((name, _chain_config)) = cfg.chain_by_hash(&header.hash()) {
-            return Err(anyhow!("decryption failed.\nDid you forget `-u {name}`?"));
+    let mut too_early = None;
+    let mut error = None;
+    for (round, name, chain) in candidates {
+        let info = chain.info();
+        if recipients.len() > 1 && !info.is_rfc9380() {
+            error = Some(anyhow!(
+                "remote '{name}' does not support decryption with multiple remotes"
+            ));
+            continue;
         }
-    };
-
-    let client = HttpClient::new(
-        &chain.url(),
-        Some(ChainOptions::new(true, true, Some(info.clone().into()))),
-    )?;
 
-    let time = RandomnessBeaconTime::from_round(&info.clone().into(), header.round());
+        let client = cfg.client(
+            &chain.url(),
+            ChainOptions::builder()
+                .chain_verification(info.clone().into())
+                .build(),
+        )?;
+
+        let beacon = match client.get(round) {
+            Ok(beacon) => beacon,
+            Err(e) if e.is_not_found() => {
+                let time = RandomnessBeaconTime::from_round(&info.clone().into(), round)?;
+                // the earliest round is reported, as it is the first decryption can succeed with
+                if too_early
+                    .as_ref()
+                    .map_or(true, |early: &RandomnessBeaconTime| {
+                        time.absolute() < early.absolute()
+                    })
+                {
+                    too_early = Some(time);
+                }
+                continue;
+            }
+            Err(e) => {
+                log::warn!("remote '{name}': {e}");
+                error = Some(cfg.remote_error(&name, e.into()));
+                continue;
+            }
+        };
 
-    let beacon = match client.get(header.round()) {
-        Ok(beacon) => beacon,
-        Err(DrandError::Beacon(e)) => match *e {
-            BeaconError::NotFound => return crate::cmd::rand::RandResult::new(None, time).short(),
-            err => return Err(err.into()),
-        },
-        Err(e) => return Err(e.into()),
-    };
+        let dst = file_or_stdout(output)?;
+        return if recipients.len() > 1 {
+            decrypt_age(dst, src, &info, &beacon)
+        } else if info.is_rfc9380() {
+            tlock_age::decrypt(dst, src, &info.hash(), &beacon.signature())
+                .map_err(|err| anyhow!(err))
+        } else {
+            tlock_age_non_rfc9380::decrypt(dst, src, &info.hash(), &beacon.signature())
+                .map_err(|err| anyhow!(err))
+        }
+        .map(|()| String::from(""));
+    }
 
-    let dst = file_or_stdout(output)?;
-    if info.is_rfc9380() {
-        tlock_age::decrypt(dst, src, &header.hash(), &beacon.signature())
-            .map(|()| String::from(""))
-            .map_err(|err| anyhow!(err))
-    } else {
-        tlock_age_non_rfc9380::decrypt(dst, src, &header.hash(), &beacon.signature())
-            .map(|()| String::from(""))
-            .map_err(|err| anyhow!(err))
+    match (too_early, error) {
+        (Some(time), _) => crate::cmd::rand::RandResult::new(None, time).short(),
+        (None, Some(error)) => Err(error),
+        (None, None) => unreachable!("at least one remote has been attempted"),
     }
 }
 
@@ -175,14 +414,15 @@ struct InspectResult {
 
 impl InspectResult {
     pub fn new(
-        header: Header,
+        round: u64,
+        hash: Vec<u8>,
         chain_name: Option<String>,
         is_upstream: bool,
         chain_info: Option<ChainInfo>,
     ) -> Self {
         Self {
-            round: header.round(),
-            hash: header.hash(),
+            round,
+            hash,
             chain_name,
             is_upstream,
             chain_info,
@@ -272,6 +512,58 @@ impl Print for InspectResult {
     }
 }
 
+/// Recipients of an encrypted input, one per chain it is encrypted to.
+/// A single recipient prints as it always has, several are printed in turn, or as a JSON array.
+struct InspectResults(Vec<InspectResult>);
+
+impl Print for InspectResults {
+    fn short(&self) -> Result<String> {
+        let output: Result<Vec<String>> = self.0.iter().map(InspectResult::short).collect();
+        Ok(output?.join("\n"))
+    }
+
+    fn long(&self) -> Result<String> {
+        let output: Result<Vec<String>> = self.0.iter().map(InspectResult::long).collect();
+        Ok(output?.join("\n\n"))
+    }
+
+    fn json(&self) -> Result<String> {
+        match &self.0[..] {
+            [result] => result.json(),
+            results => Ok(serde_json::to_string(results)?),
+        }
+    }
+}
+
+/// Progress bar, cleared from stderr once dropped.
+struct Progress(ProgressBar);
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        self.0.finish_and_clear();
+    }
+}
+
+/// Reader reporting the number of bytes read to `on_read`.
+struct ProgressReader<R, F> {
+    inner: R,
+    on_read: F,
+}
+
+impl<R, F> ProgressReader<R, F> {
+    fn new(inner: R, on_read: F) -> Self {
+        Self { inner, on_read }
+    }
+}
+
+impl<R: io::Read, F: FnMut(u64)> io::Read for ProgressReader<R, F> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        (self.on_read)(n as u64);
+        Ok(n)
+    }
+}
+
 // Reader buffering every read, and with the ability to re-read what's been read already.
 // This is useful when one need to use stdin reader twice.
 struct ResetReader<R> {
@@ -300,35 +592,57 @@ impl<R: std::io::Read> ResetReader<R> {
 
 impl<R: std::io::Read> std::io::Read for ResetReader<R> {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        // Buffer contains enough bytes for this read
-        if self.buf.len() < self.offset {
-            buf.copy_from_slice(&self.buf[..buf.len()]);
-            self.offset += buf.len();
-            return Ok(buf.len());
-        }
-
-        // Read is a mix of read from buffer and from the reader
-        let n_read_bytes = self.buf.len() - self.offset;
-        let (from_buf, from_read) = buf.split_at_mut(n_read_bytes);
-        // First read from the inner buffer
-        if n_read_bytes > 0 {
-            from_buf.copy_from_slice(self.buf.as_slice());
+        // Bytes left in the buffer are read first, as many as fit
+        if self.offset < self.buf.len() {
+            let n_read_bytes = buf.len().min(self.buf.len() - self.offset);
+            buf[..n_read_bytes].copy_from_slice(&self.buf[self.offset..self.offset + n_read_bytes]);
             self.offset += n_read_bytes;
-        }
-
-        if from_read.is_empty() {
             return Ok(n_read_bytes);
         }
 
         // Now read from the reader
-        let r = match self.inner.read(from_read) {
-            Ok(size) => size,
-            Err(e) => return Err(e),
-        };
+        let r = self.inner.read(buf)?;
         if self.is_buffer_enabled {
-            self.buf.append(from_read[..r].to_vec().as_mut());
+            self.buf.extend_from_slice(&buf[..r]);
             self.offset += r;
         }
-        Ok(n_read_bytes + r)
+        Ok(r)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+
+    #[test]
+    fn progress_reader_works() {
+        let data: Vec<u8> = (0..100_000).map(|i| i as u8).collect();
+
+        let mut count = 0;
+        let mut output = vec![];
+        io::copy(
+            &mut ProgressReader::new(&data[..], |n| count += n),
+            &mut output,
+        )
+        .unwrap();
+        assert_eq!(count, data.len() as u64);
+        assert_eq!(output, data);
+
+        // bytes read again from the reset buffer are only reported once
+        let bar = ProgressBar::hidden();
+        bar.set_length(data.len() as u64);
+        let mut src = ResetReader::new(ProgressReader::new(&data[..], {
+            let bar = bar.clone();
+            move |n| bar.inc(n)
+        }));
+        let mut header = [0; 1000];
+        src.read_exact(&mut header).unwrap();
+        src.reset();
+        let mut output = vec![];
+        io::copy(&mut src, &mut output).unwrap();
+        assert_eq!(bar.position(), data.len() as u64);
+        assert_eq!(output, data);
     }
 }
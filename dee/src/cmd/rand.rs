@@ -1,35 +1,76 @@
-use std::cmp::Ordering;
+use std::{
+    cmp::Ordering,
+    io::{self, IsTerminal, Write},
+    thread,
+    time::Duration,
+};
 
 use anyhow::{anyhow, Result};
 
+use base64::{engine::general_purpose::STANDARD, Engine};
 use colored::Colorize;
 use drand_core::{
-    beacon::{BeaconError, RandomnessBeacon, RandomnessBeaconTime},
-    ChainOptions, DrandError, HttpClient,
+    beacon::{RandomnessBeacon, RandomnessBeaconTime},
+    ChainOptions, DrandError,
 };
 use serde::Serialize;
 
 use crate::{
+    cli::RandomnessFormat,
+    cmd::crypt::file_or_stdout,
     config::{self, ConfigChain},
     print::{print_with_format, Format, Print},
 };
 
+/// Delay before retrying a failed retrieval while watching. It doubles on each consecutive failure.
+const WATCH_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+/// Upper bound on the delay between retrievals while watching.
+const WATCH_MAX_BACKOFF: Duration = Duration::from_secs(60);
+
 #[derive(Serialize)]
 pub(crate) struct RandResult {
     beacon: Option<RandomnessBeacon>,
     time: RandomnessBeaconTime,
+    #[serde(skip)]
+    randomness_format: RandomnessFormat,
 }
 
 impl RandResult {
     pub(crate) fn new(beacon: Option<RandomnessBeacon>, time: RandomnessBeaconTime) -> Self {
-        Self { beacon, time }
+        Self {
+            beacon,
+            time,
+            randomness_format: RandomnessFormat::default(),
+        }
+    }
+
+    /// Encoding of the randomness in the short output. Long and JSON outputs are not affected.
+    pub(crate) fn with_randomness_format(mut self, randomness_format: RandomnessFormat) -> Self {
+        self.randomness_format = randomness_format;
+        self
+    }
+}
+
+/// Encode `randomness` as per `format`. Uint64 is the first 8 bytes of randomness, read as a big-endian integer.
+fn encode_randomness(randomness: &[u8], format: RandomnessFormat) -> String {
+    match format {
+        RandomnessFormat::Hex => hex::encode(randomness),
+        RandomnessFormat::Base64 => STANDARD.encode(randomness),
+        RandomnessFormat::Uint64 => {
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(&randomness[..8]);
+            u64::from_be_bytes(bytes).to_string()
+        }
     }
 }
 
 impl Print for RandResult {
     fn short(&self) -> Result<String> {
         match self.beacon.as_ref() {
-            Some(beacon) => Ok(hex::encode(beacon.randomness())),
+            Some(beacon) => Ok(encode_randomness(
+                &beacon.randomness(),
+                self.randomness_format,
+            )),
             None => {
                 let format = time::format_description::parse(
                     "[year]-[month]-[day]T[hour]:[minute]:[second]Z",
@@ -87,17 +128,20 @@ impl Print for RandResult {
     }
 
     fn json(&self) -> Result<String> {
-        Ok(serde_json::to_string(&self.beacon)?)
+        match self.beacon.as_ref() {
+            Some(beacon) => Ok(beacon.to_json()),
+            None => Ok(serde_json::to_string(&self.beacon)?),
+        }
     }
 }
 
-pub fn rand(
-    _cfg: &config::Local,
-    format: Format,
+/// Time of round `beacon`, the latest one if `None`, and the result of its retrieval from `chain`.
+fn retrieve(
+    cfg: &config::Local,
     chain: ConfigChain,
     beacon: Option<String>,
     verify: bool,
-) -> Result<String> {
+) -> Result<(RandomnessBeaconTime, Result<RandomnessBeacon, DrandError>)> {
     let base_url = chain.url();
     let info = chain.info();
     let latest = beacon.is_none();
@@ -108,9 +152,12 @@ pub fn rand(
         Err(_) => return Err(anyhow!("Invalid beacon round \"{beacon}\"")),
     };
 
-    let client = HttpClient::new(
+    let client = cfg.client(
         &base_url,
-        Some(ChainOptions::new(verify, true, Some(info.into()))),
+        ChainOptions::builder()
+            .verify_beacons(verify)
+            .chain_verification(info.into())
+            .build(),
     )?;
 
     let beacon = if latest {
@@ -118,13 +165,246 @@ pub fn rand(
     } else {
         client.get(time.round())
     };
+    Ok((time, beacon))
+}
+
+pub fn rand(
+    cfg: &config::Local,
+    format: Format,
+    randomness_format: RandomnessFormat,
+    chain: ConfigChain,
+    beacon: Option<String>,
+    verify: bool,
+) -> Result<String> {
+    let (time, beacon) = retrieve(cfg, chain, beacon, verify)?;
 
     match beacon {
-        Ok(beacon) => print_with_format(RandResult::new(Some(beacon), time), format),
-        Err(DrandError::Beacon(e)) => match *e {
-            BeaconError::NotFound => print_with_format(RandResult::new(None, time), format),
-            _ => Ok(e.to_string()),
-        },
-        Err(e) => Err(e.into()),
+        Ok(beacon) => print_with_format(
+            RandResult::new(Some(beacon), time).with_randomness_format(randomness_format),
+            format,
+        ),
+        Err(e) if e.is_not_found() => print_with_format(RandResult::new(None, time), format),
+        Err(DrandError::Beacon(e)) => Ok(e.to_string()),
+        Err(e) => Err(cfg.upstream_error(e.into())),
+    }
+}
+
+/// Write the randomness of beacon `beacon` to `output`, as raw bytes without a trailing newline.
+/// Standard output is refused when it is a terminal, unless `force` is set.
+pub fn raw(
+    cfg: &config::Local,
+    chain: ConfigChain,
+    beacon: Option<String>,
+    verify: bool,
+    output: Option<String>,
+    force: bool,
+) -> Result<String> {
+    if output.is_none() && io::stdout().is_terminal() && !force {
+        return Err(anyhow!(
+            "refusing to write binary randomness to a terminal. Use --output, or --force to write it anyway."
+        ));
+    }
+
+    let (time, beacon) = retrieve(cfg, chain, beacon, verify)?;
+    let beacon = match beacon {
+        Ok(beacon) => beacon,
+        // reports the round is too early
+        Err(e) if e.is_not_found() => return RandResult::new(None, time).short(),
+        Err(e) => return Err(cfg.upstream_error(e.into())),
+    };
+
+    write_raw(file_or_stdout(output)?, &beacon)?;
+    Ok(String::new())
+}
+
+fn write_raw(mut dst: impl Write, beacon: &RandomnessBeacon) -> Result<()> {
+    dst.write_all(&beacon.randomness())?;
+    dst.flush()?;
+    Ok(())
+}
+
+/// Print beacons as they are published, until `count` have been printed, or forever if `None`.
+/// Network failures are logged and retried with backoff. A round the remote does not serve is skipped, resuming at the latest round.
+pub fn watch(
+    cfg: &config::Local,
+    format: Format,
+    randomness_format: RandomnessFormat,
+    chain: ConfigChain,
+    verify: bool,
+    count: Option<u64>,
+) -> Result<String> {
+    let info = chain.info();
+    let client = cfg.client(
+        &chain.url(),
+        ChainOptions::builder()
+            .verify_beacons(verify)
+            .chain_verification(info.clone().into())
+            .build(),
+    )?;
+
+    let mut watcher = client.watch();
+    let mut backoff = WATCH_INITIAL_BACKOFF;
+    let mut printed = 0;
+    while count.map_or(true, |count| printed < count) {
+        let Some(beacon) = watcher.next() else {
+            break;
+        };
+        match beacon {
+            Ok(beacon) => {
+                backoff = WATCH_INITIAL_BACKOFF;
+                let time = RandomnessBeaconTime::from_round(&info.clone().into(), beacon.round())?;
+                let output = print_with_format(
+                    RandResult::new(Some(beacon), time).with_randomness_format(randomness_format),
+                    format,
+                )?;
+                // detailed outputs span multiple lines, and are separated by an empty one
+                if format == Format::Long && printed > 0 {
+                    println!();
+                }
+                println!("{output}");
+                printed += 1;
+            }
+            Err(e) if e.is_not_found() => {
+                log::warn!("{e}, resuming at the latest round");
+                watcher = client.watch();
+            }
+            Err(e) if e.is_network() || e.is_retryable() => {
+                log::warn!("{e}, retrying in {}s", backoff.as_secs());
+                thread::sleep(backoff);
+                backoff = (backoff * 2).min(WATCH_MAX_BACKOFF);
+            }
+            Err(e) => return Err(cfg.upstream_error(e.into())),
+        }
+    }
+    Ok(String::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use drand_core::chain::ChainInfo;
+
+    use super::*;
+
+    /// drand mainnet (curl -sS https://drand.cloudflare.com/info)
+    const CHAIN_INFO: &str = r#"{
+        "public_key": "868f005eb8e6e4ca0a47c8a77ceaa5309a47978a7c71bc5cce96366b5d7a569937c529eeda66c7293784a9402801af31",
+        "period": 30,
+        "genesis_time": 1595431050,
+        "hash": "8990e7a9aaed2ffed73dbd7092123d6f289930540d7651336225dc172e51b2ce",
+        "groupHash": "176f93498eac9ca337150b46d21dd58673ea4e3581185f869672e59fa4cb390a",
+        "schemeID": "pedersen-bls-chained",
+        "metadata": {
+            "beaconID": "default"
+        }
+    }"#;
+
+    const RANDOMNESS: &str = "a26ba4d229c666f52a06f1a9be1278dcc7a80dbc1dd2004a1ae7b63cb79fd37e";
+
+    /// drand mainnet (curl -sS https://drand.cloudflare.com/public/1000000)
+    const BEACON: &str = r#"{
+        "round": 1000000,
+        "randomness": "a26ba4d229c666f52a06f1a9be1278dcc7a80dbc1dd2004a1ae7b63cb79fd37e",
+        "signature": "87e355169c4410a8ad6d3e7f5094b2122932c1062f603e6628aba2e4cb54f46c3bf1083c3537cd3b99e8296784f46fb40e090961cf9634f02c7dc2a96b69fc3c03735bc419962780a71245b72f81882cf6bb9c961bcf32da5624993bb747c9e5",
+        "previous_signature": "86bbc40c9d9347568967add4ddf6e351aff604352a7e1eec9b20dea4ca531ed6c7d38de9956ffc3bb5a7fabe28b3a36b069c8113bd9824135c3bff9b03359476f6b03beec179d4aeff456f4d34bbf702b9af78c3bb44e1892ace8e581bf4afa9"
+    }"#;
+
+    fn chain_info() -> ChainInfo {
+        serde_json::from_str(CHAIN_INFO).unwrap()
+    }
+
+    /// Remote serving mainnet chain info, and `beacon` as round 1000000.
+    fn remote(beacon: &str) -> (mockito::ServerGuard, [mockito::Mock; 2]) {
+        let mut server = mockito::Server::new();
+        let info_mock = server
+            .mock("GET", "/info")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(CHAIN_INFO)
+            .create();
+        let beacon_mock = server
+            .mock("GET", "/public/1000000")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(beacon)
+            .create();
+        (server, [info_mock, beacon_mock])
+    }
+
+    /// Raw randomness of round 1000000 served as `beacon`, written to a file.
+    fn rand_raw(name: &str, beacon: &str, verify: bool) -> Result<Vec<u8>> {
+        let (server, _mocks) = remote(beacon);
+        let info = chain_info();
+        let path = std::env::temp_dir().join(format!("dee-rand-{name}-{}", std::process::id()));
+        let result = raw(
+            &config::Local::default(),
+            ConfigChain::new(&server.url(), info),
+            Some("1000000".to_owned()),
+            verify,
+            Some(path.to_str().unwrap().to_owned()),
+            false,
+        );
+        let output = fs::read(&path);
+        let _ = fs::remove_file(&path);
+        result.map(|result| {
+            assert!(result.is_empty());
+            output.unwrap()
+        })
+    }
+
+    #[test]
+    fn rand_raw_works() {
+        let expected = hex::decode(RANDOMNESS).unwrap();
+        let output = rand_raw("raw", BEACON, true).unwrap();
+        assert_eq!(output.len(), 32);
+        assert_eq!(output, expected);
+
+        let output = rand_raw("raw-no-verify", BEACON, false).unwrap();
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn rand_randomness_format_works() {
+        let beacon =
+            RandomnessBeacon::from_api(serde_json::from_str(BEACON).unwrap(), &chain_info());
+        let result = |randomness_format| {
+            let time =
+                RandomnessBeaconTime::from_round(&chain_info().into(), beacon.round()).unwrap();
+            RandResult::new(Some(beacon.clone()), time).with_randomness_format(randomness_format)
+        };
+
+        assert_eq!(result(RandomnessFormat::Hex).short().unwrap(), RANDOMNESS);
+        assert_eq!(
+            result(RandomnessFormat::Base64).short().unwrap(),
+            "omuk0inGZvUqBvGpvhJ43MeoDbwd0gBKGue2PLef034="
+        );
+        assert_eq!(
+            result(RandomnessFormat::Uint64).short().unwrap(),
+            "11703629279203321589"
+        );
+
+        // long and json outputs do not depend on the randomness format
+        for randomness_format in [RandomnessFormat::Base64, RandomnessFormat::Uint64] {
+            assert!(result(randomness_format)
+                .long()
+                .unwrap()
+                .contains(RANDOMNESS));
+            assert_eq!(
+                result(randomness_format).json().unwrap(),
+                result(RandomnessFormat::Hex).json().unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn rand_raw_invalid_beacon_fails() {
+        let beacon = BEACON.replace(
+            RANDOMNESS,
+            "a26ba4d229c666f52a06f1a9be1278dcc7a80dbc1dd2004a1ae7b63cb79fd37f",
+        );
+        assert!(rand_raw("raw-invalid", &beacon, true).is_err());
     }
 }
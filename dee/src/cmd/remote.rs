@@ -1,11 +1,17 @@
+use std::{collections::BTreeMap, fs};
+
 use anyhow::{anyhow, Result};
 use colored::Colorize;
-use drand_core::HttpClient;
+use drand_core::{
+    chain::{ChainInfo, ChainVerification},
+    ChainOptions,
+};
 use log::{log_enabled, Level};
-use time::OffsetDateTime;
+use serde::Serialize;
 
 use crate::{
-    config::{self, ConfigChain},
+    cmd::crypt::file_or_stdin,
+    config::{self, Chains, ConfigChain},
     print::{self, print_with_format},
 };
 
@@ -13,9 +19,12 @@ pub fn add(cfg: &mut config::Local, name: String, url: &str) -> Result<String> {
     if cfg.chain(&name).is_some() {
         return Err(anyhow!("remote {name} already exists."));
     }
-    let client: HttpClient = url.try_into()?;
-    let info = client.chain_info().map_err(|err| {
-        anyhow!("failed to retrieve information from remote '{name}'. server response: {err}")
+    let client = cfg.client(url, ChainOptions::default())?;
+    let info = client.chain_info().map_err(|err| match err.is_timeout() {
+        true => cfg.remote_error(&name, err.into()),
+        false => {
+            anyhow!("failed to retrieve information from remote '{name}'. server response: {err}")
+        }
     })?;
 
     cfg.add_chain(name.clone(), ConfigChain::new(url, info))?;
@@ -60,6 +69,170 @@ pub fn set_url(cfg: &mut config::Local, name: String, url: &str) -> Result<Strin
     Ok(name)
 }
 
+/// Remotes of the configuration as a JSON document, sorted by name so that it can be shared and reviewed.
+/// Chain info is exported in full, so that imported remotes work offline, and keep verification pinned.
+fn export_chains(cfg: &config::Local) -> Result<String> {
+    let chains: BTreeMap<String, ConfigChain> = cfg.chains().into_iter().collect();
+    serde_json::to_string_pretty(&chains).map_err(|e| anyhow!(e))
+}
+
+pub fn export(cfg: &config::Local, output: Option<String>) -> Result<String> {
+    let chains = export_chains(cfg)?;
+    match output {
+        Some(path) => {
+            fs::write(path, chains).map_err(|_e| anyhow!("cannot create output file"))?;
+            Ok(String::new())
+        }
+        None => Ok(chains),
+    }
+}
+
+/// Add `chains` to the configuration, returning the names of remotes which have been added or replaced.
+/// Remotes which already exist with another URL or chain info are refused, unless `overwrite` replaces them, or `skip_existing` keeps them.
+fn import_chains(
+    cfg: &mut config::Local,
+    chains: Chains,
+    overwrite: bool,
+    skip_existing: bool,
+) -> Result<Vec<String>> {
+    let mut names: Vec<String> = chains.keys().cloned().collect();
+    names.sort();
+
+    let collisions: Vec<String> = names
+        .iter()
+        .filter(|name| matches!(cfg.chain(name), Some(chain) if chain != chains[*name]))
+        .cloned()
+        .collect();
+    if !collisions.is_empty() && !overwrite && !skip_existing {
+        return Err(anyhow!(
+            "remote(s) {} already exist. Use --overwrite or --skip-existing.",
+            collisions.join(", ")
+        ));
+    }
+
+    let mut imported = vec![];
+    for name in names {
+        match cfg.chain(&name) {
+            Some(chain) if chain == chains[&name] => continue,
+            Some(_) if skip_existing => continue,
+            _ => {}
+        }
+        cfg.add_chain(name.clone(), chains[&name].clone())?;
+        imported.push(name);
+    }
+    Ok(imported)
+}
+
+pub fn import(
+    cfg: &mut config::Local,
+    input: Option<String>,
+    overwrite: bool,
+    skip_existing: bool,
+) -> Result<String> {
+    let document: serde_json::Value = serde_json::from_reader(file_or_stdin(input)?)
+        .map_err(|e| anyhow!("invalid remotes file: {e}"))?;
+    // remotes exported with dee remote export, or a whole configuration dumped with dee config show --json
+    let chains: Chains = serde_json::from_value::<Chains>(document.clone())
+        .or_else(|_e| serde_json::from_value::<config::Local>(document).map(|cfg| cfg.chains()))
+        .map_err(|e| anyhow!("invalid remotes file: {e}"))?;
+    let imported = import_chains(cfg, chains, overwrite, skip_existing)?;
+    Ok(imported.join("\n"))
+}
+
+/// Fields of chain info which differ from `old` to `new`, as `(field, old, new)`.
+fn chain_info_diff(old: &ChainInfo, new: &ChainInfo) -> Vec<(&'static str, String, String)> {
+    let fields = |info: &ChainInfo| {
+        [
+            ("Public Key", hex::encode(info.public_key())),
+            ("Period", info.period().to_string()),
+            ("Genesis", info.genesis_time().to_string()),
+            ("Chain Hash", hex::encode(info.hash())),
+            ("Group Hash", hex::encode(info.group_hash())),
+            ("Scheme ID", info.scheme_id()),
+            ("Beacon ID", info.metadata().beacon_id()),
+        ]
+    };
+    fields(old)
+        .into_iter()
+        .zip(fields(new))
+        .filter(|((_, old), (_, new))| old != new)
+        .map(|((field, old), (_, new))| (field, old, new))
+        .collect()
+}
+
+/// Refresh the chain info stored for remote `name`.
+/// Chain info with another hash or public key is refused unless `force` is set, as it is another chain, or a relay tampering with it.
+fn update_chain(cfg: &mut config::Local, name: &str, force: bool) -> Result<String> {
+    let chain = match cfg.chain(name) {
+        Some(chain) => chain,
+        None => return Err(anyhow!("no such remote '{name}'.")),
+    };
+    let stored = chain.info();
+
+    // retrieved without verification, so that a change of key is reported rather than failing the request
+    let client = cfg.client(&chain.url(), ChainOptions::default())?;
+    let info = client.chain_info().map_err(|err| match err.is_timeout() {
+        true => cfg.remote_error(name, err.into()),
+        false => {
+            anyhow!("failed to retrieve information from remote '{name}'. server response: {err}")
+        }
+    })?;
+
+    let diff = chain_info_diff(&stored, &info);
+    if diff.is_empty() {
+        return Ok(format!("{name}: up to date"));
+    }
+    let diff: Vec<String> = diff
+        .into_iter()
+        .map(|(field, old, new)| format!("{: <10}: {old} -> {new}", field.bold()))
+        .collect();
+    let diff = diff.join("\n");
+
+    if !ChainVerification::from(stored).verify(&info) && !force {
+        return Err(anyhow!(
+            "remote '{name}' serves another chain hash or public key.\n{diff}\nUse --force to update it anyway."
+        ));
+    }
+    cfg.set_info_chain(name.to_owned(), info)?;
+
+    Ok(format!("{name}: updated\n{diff}"))
+}
+
+pub fn update(
+    cfg: &mut config::Local,
+    name: Option<String>,
+    all: bool,
+    force: bool,
+) -> Result<String> {
+    let names: Vec<String> = if all {
+        cfg.chains().keys().cloned().collect()
+    } else {
+        vec![name
+            .or(cfg.upstream())
+            .ok_or(anyhow!("No chain or upstream"))?]
+    };
+
+    let mut output = vec![];
+    let mut failed = 0;
+    for name in names {
+        match update_chain(cfg, &name, force) {
+            Ok(result) => output.push(result),
+            Err(err) => {
+                output.push(format!("error: {err}"));
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        // remotes which have been updated are kept, even though others failed
+        cfg.store()?;
+        println!("{}", output.join("\n"));
+        return Err(anyhow!("{failed} remote(s) failed to update."));
+    }
+    Ok(output.join("\n"))
+}
+
 impl print::Print for ConfigChain {
     fn short(&self) -> Result<String> {
         Ok(hex::encode(self.info().public_key()))
@@ -83,7 +256,7 @@ impl print::Print for ConfigChain {
             "Period".bold(),
             info.period(),
             "Genesis".bold(),
-            OffsetDateTime::from_unix_timestamp(info.genesis_time() as i64).unwrap(),
+            info.genesis_datetime()?,
             "Chain Hash".bold(),
             hex::encode(info.hash()),
             "Group Hash".bold(),
@@ -128,3 +301,412 @@ pub fn list(cfg: &config::Local) -> Result<String> {
         Ok(output.join("\n"))
     }
 }
+
+/// Rounds a remote can be behind the expected round, and still be considered healthy.
+/// A round might not have been published yet when it is checked right at its time.
+const MAX_LAG: u64 = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum CheckStatus {
+    Healthy,
+    Lagging,
+    WrongChain,
+    InvalidBeacon,
+    Unreachable,
+}
+
+impl std::fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let status = match self {
+            Self::Healthy => "healthy",
+            Self::Lagging => "lagging",
+            Self::WrongChain => "wrong chain",
+            Self::InvalidBeacon => "invalid beacon",
+            Self::Unreachable => "unreachable",
+        };
+        write!(f, "{status}")
+    }
+}
+
+#[derive(Serialize)]
+struct CheckResult {
+    name: String,
+    url: String,
+    status: CheckStatus,
+    latest_round: Option<u64>,
+    expected_round: u64,
+    lag: Option<u64>,
+    error: Option<String>,
+}
+
+impl CheckResult {
+    /// Check that the remote is reachable, serves the chain stored in the configuration, and how far its latest round is from the one expected.
+    fn new(cfg: &config::Local, name: String, chain: &ConfigChain) -> Self {
+        let info = chain.info();
+        let mut result = Self {
+            name,
+            url: chain.url(),
+            status: CheckStatus::Healthy,
+            latest_round: None,
+            expected_round: info.current_round(),
+            lag: None,
+            error: None,
+        };
+
+        // the latest beacon the remote serves is verified here, as a verifying client requests the round expected from the local clock instead
+        let client = match cfg.client(
+            &chain.url(),
+            ChainOptions::builder()
+                .verify_beacons(false)
+                .chain_verification(info.clone().into())
+                .build(),
+        ) {
+            Ok(client) => client,
+            Err(e) => return result.failed(CheckStatus::Unreachable, e),
+        };
+
+        if let Err(e) = client.chain_info() {
+            return match e.is_validation_failure() {
+                true => result.failed(CheckStatus::WrongChain, e),
+                false => result.failed(CheckStatus::Unreachable, e),
+            };
+        }
+
+        match client.latest() {
+            Ok(beacon) if !matches!(beacon.verify(info), Ok(true)) => result.failed(
+                CheckStatus::InvalidBeacon,
+                format!("beacon {} failed verification", beacon.round()),
+            ),
+            Ok(beacon) => {
+                let lag = result.expected_round.saturating_sub(beacon.round());
+                result.latest_round = Some(beacon.round());
+                result.lag = Some(lag);
+                if lag > MAX_LAG {
+                    result.status = CheckStatus::Lagging;
+                }
+                result
+            }
+            Err(e) if e.is_network() || e.is_retryable() => {
+                result.failed(CheckStatus::Unreachable, e)
+            }
+            Err(e) => result.failed(CheckStatus::InvalidBeacon, e),
+        }
+    }
+
+    fn failed(mut self, status: CheckStatus, error: impl std::fmt::Display) -> Self {
+        self.status = status;
+        self.error = Some(error.to_string());
+        self
+    }
+
+    fn is_healthy(&self) -> bool {
+        self.status == CheckStatus::Healthy
+    }
+}
+
+impl print::Print for CheckResult {
+    fn short(&self) -> Result<String> {
+        Ok(format!(
+            "{name: <20}\t{status}",
+            name = self.name,
+            status = self.status
+        ))
+    }
+
+    fn long(&self) -> Result<String> {
+        let optional =
+            |value: Option<u64>| value.map(|value| value.to_string()).unwrap_or_default();
+        let mut output = format!(
+            r"{: <10}: {}
+{: <10}: {}
+{: <10}: {}
+{: <10}: {}
+{: <10}: {}
+{: <10}: {}",
+            "Name".bold(),
+            self.name,
+            "URL".bold(),
+            self.url,
+            "Status".bold(),
+            self.status,
+            "Latest".bold(),
+            optional(self.latest_round),
+            "Expected".bold(),
+            self.expected_round,
+            "Lag".bold(),
+            optional(self.lag),
+        );
+        if let Some(error) = self.error.as_ref() {
+            output = format!("{output}\n{: <10}: {}", "Error".bold(), error);
+        }
+        Ok(output)
+    }
+
+    fn json(&self) -> Result<String> {
+        serde_json::to_string(self).map_err(|e| anyhow!(e))
+    }
+}
+
+struct CheckResults(Vec<CheckResult>);
+
+impl print::Print for CheckResults {
+    fn short(&self) -> Result<String> {
+        let output: Result<Vec<String>> = self.0.iter().map(print::Print::short).collect();
+        Ok(output?.join("\n"))
+    }
+
+    fn long(&self) -> Result<String> {
+        let output: Result<Vec<String>> = self.0.iter().map(print::Print::long).collect();
+        Ok(output?.join("\n\n"))
+    }
+
+    fn json(&self) -> Result<String> {
+        serde_json::to_string(&self.0).map_err(|e| anyhow!(e))
+    }
+}
+
+pub fn check(
+    cfg: &config::Local,
+    format: print::Format,
+    name: Option<String>,
+    all: bool,
+) -> Result<String> {
+    let names: Vec<String> = if all {
+        cfg.chains().keys().cloned().collect()
+    } else {
+        vec![name
+            .or(cfg.upstream())
+            .ok_or(anyhow!("No chain or upstream"))?]
+    };
+
+    let mut results = vec![];
+    for name in names {
+        let chain = match cfg.chain(&name) {
+            Some(chain) => chain,
+            None => return Err(anyhow!("no such remote '{name}'.")),
+        };
+        results.push(CheckResult::new(cfg, name, &chain));
+    }
+
+    let failed = results.iter().filter(|result| !result.is_healthy()).count();
+    let output = print_with_format(CheckResults(results), format)?;
+    if failed > 0 {
+        // results are still printed, so that failing remotes can be identified
+        println!("{output}");
+        return Err(anyhow!("{failed} remote(s) failed the check."));
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use super::*;
+
+    /// drand testnet (curl -sS https://pl-us.testnet.drand.sh/7672797f548f3f4748ac4bf3352fc6c6b6468c9ad40ad456a397545c6e2df5bf/public/1000000)
+    const BEACON: &str = r#"{
+        "round": 1000000,
+        "randomness": "6671747f7d838f18159c474579ea19e8d863e8c25e5271fd7f18ca2ac85181cf",
+        "signature": "86b265e10e060805d20dca88f70f6b5e62d5956e7790d32029dfb73fbcd1996bc7aebdea7aeaf74dac0ca2b3ce8f7a6a0399f224a05fe740c0bac9da638212082b0ed21b1a8c5e44a33123f28955ef0713e93e21f6af0cda4073d9a73387434d"
+    }"#;
+    const ROUND: u64 = 1000000;
+    const PERIOD: u64 = 3;
+    const TESTNET_PUBLIC_KEY: &str = "8200fc249deb0148eb918d6e213980c5d01acd7fc251900d9260136da3b54836ce125172399ddc69c4e3e11429b62c11";
+    const MAINNET_PUBLIC_KEY: &str = "868f005eb8e6e4ca0a47c8a77ceaa5309a47978a7c71bc5cce96366b5d7a569937c529eeda66c7293784a9402801af31";
+
+    /// drand testnet chain info, with a genesis time faked so that `lag` rounds have been produced since `BEACON`
+    fn chain_info(public_key: &str, lag: u64) -> ChainInfo {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        serde_json::from_value(serde_json::json!({
+            "public_key": public_key,
+            "period": PERIOD,
+            "genesis_time": now - (ROUND + lag - 1) * PERIOD,
+            "hash": "7672797f548f3f4748ac4bf3352fc6c6b6468c9ad40ad456a397545c6e2df5bf",
+            "groupHash": "65083634d852ae169e21b6ce5f0410be9ed4cc679b9970236f7875cff667e13d",
+            "schemeID": "pedersen-bls-unchained",
+            "metadata": {
+                "beaconID": "testnet-unchained-3s"
+            }
+        }))
+        .unwrap()
+    }
+
+    /// Remote serving `served` chain info, and `BEACON` as its latest beacon.
+    fn remote(served: &ChainInfo) -> (mockito::ServerGuard, [mockito::Mock; 2]) {
+        let mut server = mockito::Server::new();
+        let info_mock = server
+            .mock("GET", "/info")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(served).unwrap())
+            .create();
+        let latest_mock = server
+            .mock("GET", "/public/latest")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(BEACON)
+            .create();
+        (server, [info_mock, latest_mock])
+    }
+
+    /// Check a remote serving `served` chain info, against `stored` chain info.
+    fn check_remote(stored: ChainInfo, served: &ChainInfo) -> CheckResult {
+        let (server, _mocks) = remote(served);
+        CheckResult::new(
+            &config::Local::default(),
+            "test".to_owned(),
+            &ConfigChain::new(&server.url(), stored),
+        )
+    }
+
+    /// Update a remote serving `served` chain info, with `stored` chain info in the configuration.
+    fn update_remote(
+        stored: ChainInfo,
+        served: &ChainInfo,
+        force: bool,
+    ) -> (config::Local, Result<String>) {
+        let (server, _mocks) = remote(served);
+        let mut cfg = config::Local::default();
+        cfg.add_chain("test".to_owned(), ConfigChain::new(&server.url(), stored))
+            .unwrap();
+        let result = update_chain(&mut cfg, "test", force);
+        (cfg, result)
+    }
+
+    #[test]
+    fn remote_check_healthy_works() {
+        let info = chain_info(TESTNET_PUBLIC_KEY, 0);
+        let result = check_remote(info.clone(), &info);
+        assert!(result.is_healthy());
+        assert_eq!(result.latest_round, Some(ROUND));
+        assert!(result.lag.unwrap() <= MAX_LAG);
+        assert!(result.error.is_none());
+
+        let json: serde_json::Value =
+            serde_json::from_str(&print::Print::json(&result).unwrap()).unwrap();
+        assert_eq!(json["status"], "healthy");
+        assert_eq!(json["latest_round"], ROUND);
+    }
+
+    #[test]
+    fn remote_check_lagging_fails() {
+        let info = chain_info(TESTNET_PUBLIC_KEY, 10);
+        let result = check_remote(info.clone(), &info);
+        assert!(!result.is_healthy());
+        assert_eq!(result.status, CheckStatus::Lagging);
+        assert_eq!(result.latest_round, Some(ROUND));
+        assert!(result.lag.unwrap() >= 10);
+    }
+
+    #[test]
+    fn remote_check_wrong_chain_fails() {
+        let stored = chain_info(TESTNET_PUBLIC_KEY, 0);
+        let served = chain_info(MAINNET_PUBLIC_KEY, 0);
+        let result = check_remote(stored, &served);
+        assert!(!result.is_healthy());
+        assert_eq!(result.status, CheckStatus::WrongChain);
+        assert_eq!(result.latest_round, None);
+        assert!(result.error.is_some());
+    }
+
+    #[test]
+    fn remote_update_unchanged_works() {
+        let info = chain_info(TESTNET_PUBLIC_KEY, 0);
+        let (cfg, result) = update_remote(info.clone(), &info, false);
+        assert_eq!(result.unwrap(), "test: up to date");
+        assert_eq!(cfg.chain("test").unwrap().info(), info);
+    }
+
+    #[test]
+    fn remote_update_metadata_works() {
+        let stored = chain_info(TESTNET_PUBLIC_KEY, 0);
+        let mut served = serde_json::to_value(&stored).unwrap();
+        served["metadata"]["beaconID"] = "testnet".into();
+        let served: ChainInfo = serde_json::from_value(served).unwrap();
+
+        let (cfg, result) = update_remote(stored, &served, false);
+        let result = result.unwrap();
+        assert!(result.starts_with("test: updated"));
+        assert!(result.contains("testnet-unchained-3s -> testnet"));
+        assert_eq!(cfg.chain("test").unwrap().info(), served);
+    }
+
+    #[test]
+    fn remote_update_public_key_fails() {
+        let stored = chain_info(TESTNET_PUBLIC_KEY, 0);
+        let served = chain_info(MAINNET_PUBLIC_KEY, 0);
+
+        let (cfg, result) = update_remote(stored.clone(), &served, false);
+        assert!(result.unwrap_err().to_string().contains("--force"));
+        assert_eq!(cfg.chain("test").unwrap().info(), stored);
+
+        let (cfg, result) = update_remote(stored, &served, true);
+        assert!(result.unwrap().contains(MAINNET_PUBLIC_KEY));
+        assert_eq!(cfg.chain("test").unwrap().info(), served);
+    }
+
+    #[test]
+    fn remote_export_import_works() {
+        let mut cfg = config::Local::default();
+        cfg.add_chain(
+            "testnet".to_owned(),
+            ConfigChain::new("https://testnet.example", chain_info(TESTNET_PUBLIC_KEY, 0)),
+        )
+        .unwrap();
+        cfg.add_chain(
+            "mainnet".to_owned(),
+            ConfigChain::new("https://mainnet.example", chain_info(MAINNET_PUBLIC_KEY, 0)),
+        )
+        .unwrap();
+
+        let exported = export_chains(&cfg).unwrap();
+        let mut imported = config::Local::default();
+        let names = import_chains(
+            &mut imported,
+            serde_json::from_str(&exported).unwrap(),
+            false,
+            false,
+        )
+        .unwrap();
+        assert_eq!(names, vec!["mainnet", "testnet"]);
+        assert_eq!(imported.chains(), cfg.chains());
+
+        // importing the same remotes again is a no-op
+        let names = import_chains(
+            &mut imported,
+            serde_json::from_str(&exported).unwrap(),
+            false,
+            false,
+        )
+        .unwrap();
+        assert!(names.is_empty());
+        assert_eq!(imported.chains(), cfg.chains());
+    }
+
+    #[test]
+    fn remote_import_collision_works() {
+        let local = ConfigChain::new("https://local.example", chain_info(TESTNET_PUBLIC_KEY, 0));
+        let shared = ConfigChain::new("https://shared.example", chain_info(TESTNET_PUBLIC_KEY, 0));
+        let chains = Chains::from([("testnet".to_owned(), shared.clone())]);
+
+        let mut cfg = config::Local::default();
+        cfg.add_chain("testnet".to_owned(), local.clone()).unwrap();
+        assert!(import_chains(&mut cfg, chains.clone(), false, false).is_err());
+        assert_eq!(cfg.chain("testnet"), Some(local.clone()));
+
+        let names = import_chains(&mut cfg, chains.clone(), false, true).unwrap();
+        assert!(names.is_empty());
+        assert_eq!(cfg.chain("testnet"), Some(local));
+
+        let names = import_chains(&mut cfg, chains, true, false).unwrap();
+        assert_eq!(names, vec!["testnet"]);
+        assert_eq!(cfg.chain("testnet"), Some(shared));
+    }
+}
@@ -0,0 +1,214 @@
+use std::io::Read;
+
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use drand_core::{beacon::ApiBeacon, chain::ChainInfo};
+use serde::Serialize;
+
+use crate::{
+    cmd::crypt::file_or_stdin,
+    config::{self, ConfigChain},
+    print::{print_with_format, Format, Print},
+};
+
+#[derive(Serialize)]
+pub(crate) struct VerifyResult {
+    valid: bool,
+    chain: String,
+    round: Option<u64>,
+    reason: Option<String>,
+}
+
+impl VerifyResult {
+    /// Verify `beacon`, in drand API JSON format, against `info`, without network access.
+    /// Failing beacons are reported in the result, errors are reserved to chain info which cannot be used.
+    pub(crate) fn new(info: &ChainInfo, beacon: &str, expected_round: Option<u64>) -> Result<Self> {
+        let prepared = info.prepare()?;
+        let chain = hex::encode(info.hash());
+
+        let beacon: ApiBeacon = match serde_json::from_str(beacon) {
+            Ok(beacon) => beacon,
+            Err(e) => {
+                return Ok(Self {
+                    valid: false,
+                    chain,
+                    round: None,
+                    reason: Some(format!("malformed beacon: {e}")),
+                })
+            }
+        };
+        let round = beacon.round();
+
+        let reason = match expected_round {
+            Some(expected) if expected != round => Some(format!(
+                "beacon round {round} does not match expected round {expected}"
+            )),
+            _ => match beacon.verify_detailed(&prepared) {
+                Ok(outcome) if outcome.is_ok() => None,
+                Ok(outcome) => Some(outcome.to_string()),
+                Err(e) => Some(e.to_string()),
+            },
+        };
+
+        Ok(Self {
+            valid: reason.is_none(),
+            chain,
+            round: Some(round),
+            reason,
+        })
+    }
+}
+
+impl Print for VerifyResult {
+    fn short(&self) -> Result<String> {
+        match self.reason.as_ref() {
+            Some(reason) => Ok(reason.clone()),
+            None => Ok("beacon is valid".to_owned()),
+        }
+    }
+
+    fn long(&self) -> Result<String> {
+        let round = self
+            .round
+            .map(|round| round.to_string())
+            .unwrap_or_default();
+        Ok(format!(
+            r"{: <10}: {}
+{: <10}: {}
+{: <10}: {}",
+            "Chain".bold(),
+            self.chain,
+            "Round".bold(),
+            round,
+            "Verdict".bold(),
+            self.short()?,
+        ))
+    }
+
+    fn json(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+}
+
+pub fn verify(
+    _cfg: &config::Local,
+    format: Format,
+    chain: ConfigChain,
+    input: Option<String>,
+    round: Option<u64>,
+) -> Result<String> {
+    let mut beacon = String::new();
+    file_or_stdin(input)?
+        .read_to_string(&mut beacon)
+        .map_err(|_e| anyhow!("cannot read input"))?;
+
+    let result = VerifyResult::new(&chain.info(), &beacon, round)?;
+    match result.reason.clone() {
+        None => print_with_format(result, format),
+        Some(reason) => {
+            // the verdict is still printed, so it can be processed alongside the exit code
+            if format != Format::Short {
+                println!("{}", print_with_format(result, format)?);
+            }
+            Err(anyhow!(reason))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// drand mainnet (curl -sS https://drand.cloudflare.com/info)
+    const CHAIN_INFO: &str = r#"{
+        "public_key": "868f005eb8e6e4ca0a47c8a77ceaa5309a47978a7c71bc5cce96366b5d7a569937c529eeda66c7293784a9402801af31",
+        "period": 30,
+        "genesis_time": 1595431050,
+        "hash": "8990e7a9aaed2ffed73dbd7092123d6f289930540d7651336225dc172e51b2ce",
+        "groupHash": "176f93498eac9ca337150b46d21dd58673ea4e3581185f869672e59fa4cb390a",
+        "schemeID": "pedersen-bls-chained",
+        "metadata": {
+            "beaconID": "default"
+        }
+    }"#;
+
+    /// drand mainnet (curl -sS https://drand.cloudflare.com/public/1000000)
+    const BEACON: &str = r#"{
+        "round": 1000000,
+        "randomness": "a26ba4d229c666f52a06f1a9be1278dcc7a80dbc1dd2004a1ae7b63cb79fd37e",
+        "signature": "87e355169c4410a8ad6d3e7f5094b2122932c1062f603e6628aba2e4cb54f46c3bf1083c3537cd3b99e8296784f46fb40e090961cf9634f02c7dc2a96b69fc3c03735bc419962780a71245b72f81882cf6bb9c961bcf32da5624993bb747c9e5",
+        "previous_signature": "86bbc40c9d9347568967add4ddf6e351aff604352a7e1eec9b20dea4ca531ed6c7d38de9956ffc3bb5a7fabe28b3a36b069c8113bd9824135c3bff9b03359476f6b03beec179d4aeff456f4d34bbf702b9af78c3bb44e1892ace8e581bf4afa9"
+    }"#;
+
+    fn chain_info() -> ChainInfo {
+        serde_json::from_str(CHAIN_INFO).unwrap()
+    }
+
+    #[test]
+    fn verify_valid_beacon_works() {
+        let result = VerifyResult::new(&chain_info(), BEACON, None).unwrap();
+        assert!(result.valid);
+        assert_eq!(result.round, Some(1000000));
+        assert_eq!(result.reason, None);
+
+        let result = VerifyResult::new(&chain_info(), BEACON, Some(1000000)).unwrap();
+        assert!(result.valid);
+
+        let json: serde_json::Value = serde_json::from_str(&result.json().unwrap()).unwrap();
+        assert_eq!(json["valid"], true);
+        assert_eq!(json["round"], 1000000);
+        assert_eq!(json["chain"], hex::encode(chain_info().hash()));
+    }
+
+    #[test]
+    fn verify_unexpected_round_fails() {
+        let result = VerifyResult::new(&chain_info(), BEACON, Some(1000001)).unwrap();
+        assert!(!result.valid);
+        assert_eq!(result.round, Some(1000000));
+        assert!(result.reason.is_some());
+    }
+
+    #[test]
+    fn verify_tampered_randomness_fails() {
+        let beacon = BEACON.replace(
+            "a26ba4d229c666f52a06f1a9be1278dcc7a80dbc1dd2004a1ae7b63cb79fd37e",
+            "a26ba4d229c666f52a06f1a9be1278dcc7a80dbc1dd2004a1ae7b63cb79fd37f",
+        );
+        let result = VerifyResult::new(&chain_info(), &beacon, None).unwrap();
+        assert!(!result.valid);
+        assert!(result.reason.is_some());
+
+        let json: serde_json::Value = serde_json::from_str(&result.json().unwrap()).unwrap();
+        assert_eq!(json["valid"], false);
+        assert!(json["reason"].is_string());
+    }
+
+    #[test]
+    fn verify_wrong_chain_fails() {
+        // same scheme, with the public key of drand testnet
+        let info: ChainInfo = serde_json::from_str(&CHAIN_INFO.replace(
+            "868f005eb8e6e4ca0a47c8a77ceaa5309a47978a7c71bc5cce96366b5d7a569937c529eeda66c7293784a9402801af31",
+            "8200fc249deb0148eb918d6e213980c5d01acd7fc251900d9260136da3b54836ce125172399ddc69c4e3e11429b62c11",
+        ))
+        .unwrap();
+        let result = VerifyResult::new(&info, BEACON, None).unwrap();
+        assert!(!result.valid);
+        assert!(result.reason.is_some());
+
+        // chained beacon, on an unchained scheme
+        let info: ChainInfo = serde_json::from_str(
+            &CHAIN_INFO.replace("pedersen-bls-chained", "pedersen-bls-unchained"),
+        )
+        .unwrap();
+        let result = VerifyResult::new(&info, BEACON, None).unwrap();
+        assert!(!result.valid);
+    }
+
+    #[test]
+    fn verify_malformed_beacon_fails() {
+        let result = VerifyResult::new(&chain_info(), "{}", None).unwrap();
+        assert!(!result.valid);
+        assert_eq!(result.round, None);
+        assert!(result.reason.unwrap().starts_with("malformed beacon"));
+    }
+}
@@ -1,8 +1,8 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Duration};
 
 use anyhow::{anyhow, Result};
 
-use drand_core::chain::ChainInfo;
+use drand_core::{chain::ChainInfo, ChainOptions, DrandError, HttpClient};
 use serde::{Deserialize, Serialize};
 
 pub type Chains = HashMap<String, ConfigChain>;
@@ -11,11 +11,18 @@ pub type Chains = HashMap<String, ConfigChain>;
 pub struct Local {
     upstream: Option<String>,
     chains: Chains,
+    /// Time allowed for requests to remotes. Set from the command line, and not stored.
+    #[serde(skip)]
+    timeout: Option<Duration>,
+    /// Whether progress is reported on stderr. Set from the command line, and not stored.
+    #[serde(skip)]
+    progress: bool,
 }
 
 impl Local {
     const APP_NAME: &'static str = env!("CARGO_PKG_NAME");
     const CONFIG_NAME: Option<&'static str> = Some("default");
+    const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
 
     pub fn load() -> Self {
         confy::load(Self::APP_NAME, Self::CONFIG_NAME).unwrap()
@@ -31,6 +38,50 @@ impl Local {
         confy::store(Self::APP_NAME, Self::CONFIG_NAME, self).map_err(|err| anyhow!(err))
     }
 
+    pub fn timeout(&self) -> Duration {
+        self.timeout.unwrap_or(Self::DEFAULT_TIMEOUT)
+    }
+
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = Some(timeout);
+    }
+
+    pub fn progress(&self) -> bool {
+        self.progress
+    }
+
+    pub fn set_progress(&mut self, progress: bool) {
+        self.progress = progress;
+    }
+
+    /// Client for the remote at `url`, giving up on requests after the configured timeout.
+    pub fn client(&self, url: &str, options: ChainOptions) -> Result<HttpClient> {
+        Ok(HttpClient::builder(url)
+            .options(options)
+            .connect_timeout(self.timeout())
+            .timeout(self.timeout())
+            .build()?)
+    }
+
+    /// Report `err` as a timeout of remote `name` if it is one, and as is otherwise.
+    pub fn remote_error(&self, name: &str, err: anyhow::Error) -> anyhow::Error {
+        match err.downcast_ref::<DrandError>() {
+            Some(e) if e.is_timeout() => anyhow!(
+                "remote '{name}' timed out after {}s.",
+                self.timeout().as_secs()
+            ),
+            _ => err,
+        }
+    }
+
+    /// Report `err` as a timeout of the upstream remote if it is one, and as is otherwise.
+    pub fn upstream_error(&self, err: anyhow::Error) -> anyhow::Error {
+        match self.upstream() {
+            Some(name) => self.remote_error(&name, err),
+            None => err,
+        }
+    }
+
     pub fn upstream(&self) -> Option<String> {
         self.upstream.clone()
     }
@@ -86,6 +137,15 @@ impl Local {
             .ok_or(anyhow!("no such remote '{name}'."))
     }
 
+    pub fn set_info_chain(&mut self, name: String, info: ChainInfo) -> Result<()> {
+        self.chains
+            .get_mut(&name)
+            .map(|v| {
+                v.info = info;
+            })
+            .ok_or(anyhow!("no such remote '{name}'."))
+    }
+
     pub fn set_upstream(&mut self, upstream: &str) -> Result<()> {
         self.chains
             .get(upstream)
@@ -116,7 +176,7 @@ impl From<Local> for Option<&str> {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct ConfigChain {
     url: String,
     info: ChainInfo,
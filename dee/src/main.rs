@@ -1,5 +1,5 @@
 use anyhow::anyhow;
-use std::process;
+use std::{io::IsTerminal, process, time::Duration};
 
 mod cli;
 mod cmd;
@@ -10,9 +10,12 @@ mod time;
 fn main() {
     let cli = cli::build();
     let mut cfg: config::Local = config::Local::load();
+    cfg.set_timeout(Duration::from_secs(cli.timeout));
     env_logger::Builder::new()
         .filter_level(cli.verbose.log_level_filter())
         .init();
+    // progress is only reported to a user watching stderr, and is silenced by --quiet
+    cfg.set_progress(!cli.verbose.is_silent() && std::io::stderr().is_terminal());
 
     let output = match cli.command {
         cli::Commands::Rand {
@@ -21,8 +24,30 @@ fn main() {
             long,
             json,
             beacon,
+            watch,
+            count,
+            randomness_format,
+            raw,
+            output,
+            force,
         } => match cfg.set_upstream_and_chain(set_upstream) {
-            Ok(chain) => cmd::rand(&cfg, print::Format::new(long, json), chain, beacon, verify),
+            Ok(chain) if watch => cmd::rand::watch(
+                &cfg,
+                print::Format::new(long, json),
+                randomness_format,
+                chain,
+                verify,
+                count,
+            ),
+            Ok(chain) if raw => cmd::rand::raw(&cfg, chain, beacon, verify, output, force),
+            Ok(chain) => cmd::rand(
+                &cfg,
+                print::Format::new(long, json),
+                randomness_format,
+                chain,
+                beacon,
+                verify,
+            ),
             Err(err) => Err(err),
         },
         cli::Commands::Crypt {
@@ -31,15 +56,23 @@ fn main() {
             inspect,
             set_upstream,
             round,
+            recipients,
             armor,
             output,
             input,
         } => {
             let is_inspect = inspect.is_true();
-            match cfg.set_upstream_and_chain(set_upstream) {
-                Ok(chain) => match (encrypt, decrypt, is_inspect) {
+            let recipients: anyhow::Result<Vec<_>> = recipients
+                .into_iter()
+                .map(|name| match cfg.chain(&name) {
+                    Some(chain) => Ok((name, chain)),
+                    None => Err(anyhow!("no such remote '{name}'.")),
+                })
+                .collect();
+            match (cfg.set_upstream_and_chain(set_upstream), recipients) {
+                (Ok(chain), Ok(recipients)) => match (encrypt, decrypt, is_inspect) {
                     (true, false, false) => {
-                        cmd::crypt::encrypt(&cfg, output, input, armor, chain, round)
+                        cmd::crypt::encrypt(&cfg, output, input, armor, chain, recipients, round)
                     }
                     (_, true, _) => cmd::crypt::decrypt(&cfg, output, input, chain),
                     (_, _, true) => cmd::crypt::inspect(
@@ -50,9 +83,19 @@ fn main() {
                     ),
                     _ => unreachable!(),
                 },
-                Err(err) => Err(err),
+                (Err(err), _) | (_, Err(err)) => Err(err),
             }
         }
+        cli::Commands::Verify {
+            set_upstream,
+            round,
+            long,
+            json,
+            input,
+        } => match cfg.set_upstream_and_chain(set_upstream) {
+            Ok(chain) => cmd::verify(&cfg, print::Format::new(long, json), chain, input, round),
+            Err(err) => Err(err),
+        },
         cli::Commands::Remote { command } => match command {
             Some(command) => match command {
                 cli::RemoteCommand::Add { name, url } => cmd::remote::add(&mut cfg, name, &url),
@@ -68,9 +111,30 @@ fn main() {
                         .ok_or(anyhow!("No chain or upstream"))
                         .unwrap(),
                 ),
+                cli::RemoteCommand::Export { output } => cmd::remote::export(&cfg, output),
+                cli::RemoteCommand::Import {
+                    overwrite,
+                    skip_existing,
+                    input,
+                } => cmd::remote::import(&mut cfg, input, overwrite, skip_existing),
+                cli::RemoteCommand::Update { all, force, name } => {
+                    cmd::remote::update(&mut cfg, name, all, force)
+                }
+                cli::RemoteCommand::Check {
+                    long,
+                    json,
+                    all,
+                    name,
+                } => cmd::remote::check(&cfg, print::Format::new(long, json), name, all),
             },
             None => cmd::remote::list(&cfg),
         },
+        cli::Commands::Config { command } => match command {
+            cli::ConfigCommand::Path => cmd::config::path(),
+            cli::ConfigCommand::Show { json } => cmd::config::show(&cfg, json),
+            cli::ConfigCommand::Reset { yes } => cmd::config::reset(&mut cfg, yes),
+        },
+        cli::Commands::Completion { shell } => cmd::completion(shell),
     };
 
     match output {
@@ -1,10 +1,10 @@
 use anyhow::{anyhow, Result};
 use colored::Colorize;
 use drand_core::beacon::RandomnessBeaconTime;
-use drand_core::{ChainOptions, HttpClient};
+use drand_core::ChainOptions;
 use time::format_description::well_known::Rfc3339;
 
-use crate::config::ConfigChain;
+use crate::config::{self, ConfigChain};
 use crate::print::Print;
 
 impl Print for RandomnessBeaconTime {
@@ -32,19 +32,26 @@ impl Print for RandomnessBeaconTime {
 }
 
 pub fn round_from_option(
+    cfg: &config::Local,
     chain: &ConfigChain,
     round: Option<String>,
 ) -> Result<RandomnessBeaconTime> {
     let info = chain.info();
 
-    let client = HttpClient::new(
+    let client = cfg.client(
         &chain.url(),
-        Some(ChainOptions::new(true, true, Some(info.clone().into()))),
+        ChainOptions::builder()
+            .chain_verification(info.clone().into())
+            .build(),
     )?;
 
     let round = match round {
         Some(round) => round,
-        None => client.latest()?.round().to_string(),
+        None => client
+            .latest()
+            .map_err(|e| cfg.upstream_error(e.into()))?
+            .round()
+            .to_string(),
     };
 
     match RandomnessBeaconTime::parse(&info.into(), &round) {
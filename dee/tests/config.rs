@@ -0,0 +1,137 @@
+//! Tests of `dee config`, each run against a configuration in its own temporary directory.
+// Configuration is located with HOME and XDG_CONFIG_HOME, which Windows does not use
+#![cfg(unix)]
+
+use std::{fs, path::PathBuf};
+
+use assert_cmd::Command;
+
+/// drand testnet (curl -sS https://pl-us.testnet.drand.sh/7672797f548f3f4748ac4bf3352fc6c6b6468c9ad40ad456a397545c6e2df5bf/info)
+const REMOTES: &str = r#"{
+    "testnet": {
+        "url": "https://pl-us.testnet.drand.sh/7672797f548f3f4748ac4bf3352fc6c6b6468c9ad40ad456a397545c6e2df5bf",
+        "info": {
+            "public_key": "8200fc249deb0148eb918d6e213980c5d01acd7fc251900d9260136da3b54836ce125172399ddc69c4e3e11429b62c11",
+            "period": 3,
+            "genesis_time": 1651677099,
+            "hash": "7672797f548f3f4748ac4bf3352fc6c6b6468c9ad40ad456a397545c6e2df5bf",
+            "groupHash": "65083634d852ae169e21b6ce5f0410be9ed4cc679b9970236f7875cff667e13d",
+            "schemeID": "pedersen-bls-unchained",
+            "metadata": {
+                "beaconID": "testnet-unchained-3s"
+            }
+        }
+    }
+}"#;
+
+struct TempConfig(PathBuf);
+
+impl TempConfig {
+    fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("dee-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        Self(dir)
+    }
+
+    fn dee(&self) -> Command {
+        let mut cmd = Command::cargo_bin("dee").unwrap();
+        cmd.env("HOME", &self.0)
+            .env("XDG_CONFIG_HOME", self.0.join(".config"));
+        cmd
+    }
+
+    fn show(&self) -> serde_json::Value {
+        let output = self
+            .dee()
+            .args(["config", "show", "--json"])
+            .assert()
+            .success();
+        serde_json::from_slice(&output.get_output().stdout).unwrap()
+    }
+
+    fn import(&self, remotes: &str) {
+        self.dee()
+            .args(["remote", "import"])
+            .write_stdin(remotes)
+            .assert()
+            .success();
+    }
+}
+
+impl Drop for TempConfig {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[test]
+fn config_path_works() {
+    let config = TempConfig::new("path");
+    let output = config.dee().args(["config", "path"]).assert().success();
+    let path = String::from_utf8(output.get_output().stdout.clone()).unwrap();
+    assert!(path.trim().starts_with(config.0.to_str().unwrap()));
+    assert!(path.trim().ends_with("default.toml"));
+}
+
+#[test]
+fn config_show_works() {
+    let config = TempConfig::new("show");
+    assert_eq!(
+        config.show(),
+        serde_json::json!({ "upstream": null, "chains": {} })
+    );
+
+    config.import(REMOTES);
+    let shown = config.show();
+    assert_eq!(shown["upstream"], "testnet");
+    assert_eq!(
+        shown["chains"],
+        serde_json::from_str::<serde_json::Value>(REMOTES).unwrap()
+    );
+
+    let output = config.dee().args(["config", "show"]).assert().success();
+    let output = String::from_utf8(output.get_output().stdout.clone()).unwrap();
+    assert!(output.contains("testnet"));
+
+    // the dump is imported as is in another configuration
+    let other = TempConfig::new("show-import");
+    other.import(&shown.to_string());
+    assert_eq!(other.show(), shown);
+}
+
+#[test]
+fn config_reset_works() {
+    let config = TempConfig::new("reset");
+    config.import(REMOTES);
+
+    config
+        .dee()
+        .args(["config", "reset"])
+        .write_stdin("n\n")
+        .assert()
+        .failure();
+    assert_eq!(config.show()["upstream"], "testnet");
+
+    config
+        .dee()
+        .args(["config", "reset"])
+        .write_stdin("y\n")
+        .assert()
+        .success();
+    assert_eq!(
+        config.show(),
+        serde_json::json!({ "upstream": null, "chains": {} })
+    );
+
+    config.import(REMOTES);
+    config
+        .dee()
+        .args(["config", "reset", "--yes"])
+        .assert()
+        .success();
+    assert_eq!(
+        config.show(),
+        serde_json::json!({ "upstream": null, "chains": {} })
+    );
+}
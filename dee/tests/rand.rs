@@ -0,0 +1,168 @@
+//! Tests of `dee rand` beacon specifiers, against a local remote serving drand mainnet.
+// Configuration is located with HOME and XDG_CONFIG_HOME, which Windows does not use
+#![cfg(unix)]
+
+use std::{
+    fs,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use assert_cmd::Command;
+
+/// drand mainnet (curl -sS https://drand.cloudflare.com/info)
+const CHAIN_INFO: &str = r#"{
+    "public_key": "868f005eb8e6e4ca0a47c8a77ceaa5309a47978a7c71bc5cce96366b5d7a569937c529eeda66c7293784a9402801af31",
+    "period": 30,
+    "genesis_time": 1595431050,
+    "hash": "8990e7a9aaed2ffed73dbd7092123d6f289930540d7651336225dc172e51b2ce",
+    "groupHash": "176f93498eac9ca337150b46d21dd58673ea4e3581185f869672e59fa4cb390a",
+    "schemeID": "pedersen-bls-chained",
+    "metadata": {
+        "beaconID": "default"
+    }
+}"#;
+
+const ROUND: u64 = 1000000;
+const PERIOD: u64 = 30;
+
+/// Round far enough in the future not to be produced yet.
+const FUTURE_ROUND: u64 = 100000000;
+
+/// drand mainnet (curl -sS https://drand.cloudflare.com/public/1000000)
+const BEACON: &str = r#"{
+    "round": 1000000,
+    "randomness": "a26ba4d229c666f52a06f1a9be1278dcc7a80dbc1dd2004a1ae7b63cb79fd37e",
+    "signature": "87e355169c4410a8ad6d3e7f5094b2122932c1062f603e6628aba2e4cb54f46c3bf1083c3537cd3b99e8296784f46fb40e090961cf9634f02c7dc2a96b69fc3c03735bc419962780a71245b72f81882cf6bb9c961bcf32da5624993bb747c9e5",
+    "previous_signature": "86bbc40c9d9347568967add4ddf6e351aff604352a7e1eec9b20dea4ca531ed6c7d38de9956ffc3bb5a7fabe28b3a36b069c8113bd9824135c3bff9b03359476f6b03beec179d4aeff456f4d34bbf702b9af78c3bb44e1892ace8e581bf4afa9"
+}"#;
+
+const RANDOMNESS: &str = "a26ba4d229c666f52a06f1a9be1278dcc7a80dbc1dd2004a1ae7b63cb79fd37e";
+
+/// Remote serving `info`, `BEACON` as round `ROUND`, and nothing yet for `FUTURE_ROUND`.
+fn remote(info: &serde_json::Value) -> (mockito::ServerGuard, [mockito::Mock; 3]) {
+    let mut server = mockito::Server::new();
+    let info_mock = server
+        .mock("GET", "/info")
+        .match_query(mockito::Matcher::Any)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(info.to_string())
+        .create();
+    let beacon_mock = server
+        .mock("GET", format!("/public/{ROUND}").as_str())
+        .match_query(mockito::Matcher::Any)
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(BEACON)
+        .create();
+    let future_mock = server
+        .mock("GET", format!("/public/{FUTURE_ROUND}").as_str())
+        .match_query(mockito::Matcher::Any)
+        .with_status(404)
+        .create();
+    (server, [info_mock, beacon_mock, future_mock])
+}
+
+struct TempConfig(PathBuf);
+
+impl TempConfig {
+    /// Configuration with `info` served by `server` as its upstream.
+    fn new(name: &str, server: &mockito::ServerGuard, info: &serde_json::Value) -> Self {
+        let dir = std::env::temp_dir().join(format!("dee-rand-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let config = Self(dir);
+        let remotes = serde_json::json!({
+            "mainnet": { "url": server.url(), "info": info }
+        });
+        config
+            .dee()
+            .args(["remote", "import"])
+            .write_stdin(remotes.to_string())
+            .assert()
+            .success();
+        config
+    }
+
+    fn dee(&self) -> Command {
+        let mut cmd = Command::cargo_bin("dee").unwrap();
+        cmd.env("HOME", &self.0)
+            .env("XDG_CONFIG_HOME", self.0.join(".config"));
+        cmd
+    }
+
+    /// Randomness printed by `dee rand beacon`.
+    fn rand(&self, beacon: &str) -> String {
+        let output = self.dee().args(["rand", beacon]).assert().success();
+        String::from_utf8(output.get_output().stdout.clone()).unwrap()
+    }
+}
+
+impl Drop for TempConfig {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[test]
+fn rand_round_works() {
+    let info: serde_json::Value = serde_json::from_str(CHAIN_INFO).unwrap();
+    let (server, _mocks) = remote(&info);
+    let config = TempConfig::new("round", &server, &info);
+
+    assert_eq!(config.rand(&ROUND.to_string()).trim(), RANDOMNESS);
+}
+
+#[test]
+fn rand_date_works() {
+    let info: serde_json::Value = serde_json::from_str(CHAIN_INFO).unwrap();
+    let (server, _mocks) = remote(&info);
+    let config = TempConfig::new("date", &server, &info);
+
+    // round 1000000 is produced at 1625431020, and until the next one
+    for beacon in [
+        "2021-07-04T20:37:00Z",
+        "2021-07-04 20:37:00",
+        "2021-07-04T20:37:29Z",
+        "@1625431020",
+    ] {
+        assert_eq!(config.rand(beacon).trim(), RANDOMNESS, "{beacon}");
+    }
+}
+
+#[test]
+fn rand_duration_works() {
+    // genesis is moved so that round 1000000 is produced ten minutes ago, halfway through its period
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+    let mut info: serde_json::Value = serde_json::from_str(CHAIN_INFO).unwrap();
+    info["genesis_time"] = (now - 10 * 60 - (ROUND - 1) * PERIOD - PERIOD / 2).into();
+    let (server, _mocks) = remote(&info);
+    let config = TempConfig::new("duration", &server, &info);
+
+    assert_eq!(config.rand("-10m").trim(), RANDOMNESS);
+    assert_eq!(config.rand("-600s").trim(), RANDOMNESS);
+}
+
+#[test]
+fn rand_future_round_fails() {
+    let info: serde_json::Value = serde_json::from_str(CHAIN_INFO).unwrap();
+    let (server, _mocks) = remote(&info);
+    let config = TempConfig::new("future", &server, &info);
+
+    let output = config
+        .dee()
+        .args(["rand", &FUTURE_ROUND.to_string()])
+        .assert()
+        .failure();
+    let stderr = String::from_utf8(output.get_output().stderr.clone()).unwrap();
+    assert!(
+        stderr.contains(&format!(
+            "Too early. Beacon round is {FUTURE_ROUND}, estimated in "
+        )),
+        "{stderr}"
+    );
+}
@@ -0,0 +1,84 @@
+//! Tests of `--timeout`, against a non-routable address which never responds.
+// Configuration is located with HOME and XDG_CONFIG_HOME, which Windows does not use
+#![cfg(unix)]
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use assert_cmd::Command;
+
+const NON_ROUTABLE_URL: &str = "http://10.255.255.1";
+
+/// Requests are retried, so a timeout of 1 second has to give up well before this.
+const MAX_ELAPSED: Duration = Duration::from_secs(10);
+
+/// drand testnet chain info, served from a non-routable address.
+const REMOTES: &str = r#"{
+    "slow": {
+        "url": "http://10.255.255.1",
+        "info": {
+            "public_key": "8200fc249deb0148eb918d6e213980c5d01acd7fc251900d9260136da3b54836ce125172399ddc69c4e3e11429b62c11",
+            "period": 3,
+            "genesis_time": 1651677099,
+            "hash": "7672797f548f3f4748ac4bf3352fc6c6b6468c9ad40ad456a397545c6e2df5bf",
+            "groupHash": "65083634d852ae169e21b6ce5f0410be9ed4cc679b9970236f7875cff667e13d",
+            "schemeID": "pedersen-bls-unchained",
+            "metadata": {
+                "beaconID": "testnet-unchained-3s"
+            }
+        }
+    }
+}"#;
+
+fn dee(dir: &Path) -> Command {
+    let mut cmd = Command::cargo_bin("dee").unwrap();
+    cmd.env("HOME", dir)
+        .env("XDG_CONFIG_HOME", dir.join(".config"))
+        .env_remove("DEE_TIMEOUT");
+    cmd
+}
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("dee-timeout-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn remote_add_timeout_works() {
+    let dir = temp_dir("add");
+    let start = Instant::now();
+    let output = dee(&dir)
+        .args(["remote", "add", "--timeout", "1", "slow", NON_ROUTABLE_URL])
+        .assert()
+        .failure();
+    assert!(start.elapsed() < MAX_ELAPSED);
+    let stderr = String::from_utf8(output.get_output().stderr.clone()).unwrap();
+    assert!(stderr.contains("remote 'slow' timed out after 1s"));
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn rand_timeout_env_works() {
+    let dir = temp_dir("rand");
+    dee(&dir)
+        .args(["remote", "import"])
+        .write_stdin(REMOTES)
+        .assert()
+        .success();
+
+    let start = Instant::now();
+    let output = dee(&dir)
+        .args(["rand", "-u", "slow"])
+        .env("DEE_TIMEOUT", "1")
+        .assert()
+        .failure();
+    assert!(start.elapsed() < MAX_ELAPSED);
+    let stderr = String::from_utf8(output.get_output().stderr.clone()).unwrap();
+    assert!(stderr.contains("remote 'slow' timed out after 1s"));
+    let _ = fs::remove_dir_all(&dir);
+}
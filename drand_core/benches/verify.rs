@@ -0,0 +1,126 @@
+use ark_bls12_381::{g2, Fr, G1Projective};
+use ark_ec::{
+    hashing::{curve_maps::wb::WBMap, map_to_curve_hasher::MapToCurveBasedHasher, HashToCurve},
+    models::short_weierstrass,
+    CurveGroup, Group,
+};
+use ark_ff::field_hashers::DefaultFieldHasher;
+use ark_serialize::CanonicalSerialize;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use drand_core::{
+    beacon::{verify_batch, ApiBeacon},
+    chain::ChainInfo,
+};
+use sha2::{Digest, Sha256};
+
+const G2_DOMAIN: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+
+/// Unchained beacons for rounds 1 to `rounds`, signed with a test key, alongside chain info to verify them.
+fn signed_beacons(rounds: u64) -> (ChainInfo, Vec<ApiBeacon>) {
+    let secret_key = Fr::from(0xd4a2du64);
+    let mut public_key = vec![];
+    (G1Projective::generator() * secret_key)
+        .into_affine()
+        .serialize_compressed(&mut public_key)
+        .unwrap();
+    let info = serde_json::from_value(serde_json::json!({
+        "public_key": hex::encode(public_key),
+        "period": 3,
+        "genesis_time": 1651677099,
+        "hash": "7672797f548f3f4748ac4bf3352fc6c6b6468c9ad40ad456a397545c6e2df5bf",
+        "groupHash": "65083634d852ae169e21b6ce5f0410be9ed4cc679b9970236f7875cff667e13d",
+        "schemeID": "pedersen-bls-unchained",
+        "metadata": {
+            "beaconID": "bench"
+        }
+    }))
+    .unwrap();
+
+    let mapper = MapToCurveBasedHasher::<
+        short_weierstrass::Projective<g2::Config>,
+        DefaultFieldHasher<Sha256, 128>,
+        WBMap<g2::Config>,
+    >::new(G2_DOMAIN)
+    .unwrap();
+    let beacons = (1..=rounds)
+        .map(|round| {
+            let hash = mapper.hash(&Sha256::digest(round.to_be_bytes())).unwrap();
+            let mut signature = vec![];
+            (hash * secret_key)
+                .into_affine()
+                .serialize_compressed(&mut signature)
+                .unwrap();
+            serde_json::from_value(serde_json::json!({
+                "round": round,
+                "randomness": hex::encode(Sha256::digest(&signature)),
+                "signature": hex::encode(signature),
+            }))
+            .unwrap()
+        })
+        .collect();
+    (info, beacons)
+}
+
+fn verify(c: &mut Criterion) {
+    let mut group = c.benchmark_group("verify");
+    for rounds in [16, 128, 1000] {
+        let (info, beacons) = signed_beacons(rounds);
+        group.bench_with_input(
+            BenchmarkId::new("sequential", rounds),
+            &beacons,
+            |b, beacons| {
+                b.iter(|| {
+                    beacons
+                        .iter()
+                        .all(|beacon| beacon.verify(info.clone()).unwrap())
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("prepared", rounds),
+            &beacons,
+            |b, beacons| {
+                let prepared = info.prepare().unwrap();
+                b.iter(|| {
+                    beacons
+                        .iter()
+                        .all(|beacon| beacon.verify_prepared(&prepared).unwrap())
+                })
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("batch", rounds), &beacons, |b, beacons| {
+            b.iter(|| verify_batch(&info, beacons).unwrap())
+        });
+    }
+    group.finish();
+}
+
+/// Hashing a round to G2 with a hasher built for each verification, as it used to be, or built once.
+fn hash_to_curve(c: &mut Criterion) {
+    type G2Hasher = MapToCurveBasedHasher<
+        short_weierstrass::Projective<g2::Config>,
+        DefaultFieldHasher<Sha256, 128>,
+        WBMap<g2::Config>,
+    >;
+    let message = Sha256::digest(1u64.to_be_bytes());
+
+    let mut group = c.benchmark_group("hash_to_curve");
+    group.bench_function("new_hasher", |b| {
+        b.iter(|| G2Hasher::new(G2_DOMAIN).unwrap().hash(&message).unwrap())
+    });
+    let hasher = G2Hasher::new(G2_DOMAIN).unwrap();
+    group.bench_function("cached_hasher", |b| {
+        b.iter(|| hasher.hash(&message).unwrap())
+    });
+    group.finish();
+
+    // single verifications only pay for hashing, once the hasher of the chain tag is cached
+    let (info, beacons) = signed_beacons(1);
+    let prepared = info.prepare().unwrap();
+    c.bench_function("verify_single", |b| {
+        b.iter(|| beacons[0].verify_prepared(&prepared).unwrap())
+    });
+}
+
+criterion_group!(benches, verify, hash_to_curve);
+criterion_main!(benches);
@@ -1,49 +1,298 @@
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt;
+
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use thiserror::Error;
 #[cfg(feature = "time")]
-use time::{
-    ext::NumericalDuration, format_description::well_known::Rfc3339, Duration, OffsetDateTime,
-};
+use time::{format_description::well_known::Rfc3339, Duration, OffsetDateTime};
 
 #[cfg(feature = "time")]
 use crate::chain::ChainTimeInfo;
-#[cfg(feature = "time")]
 use crate::DrandError;
-use crate::{chain::ChainInfo, Result};
+use crate::{
+    chain::{ChainInfo, PreparedChainInfo, Scheme, SignatureGroup},
+    Result,
+};
 
-#[derive(Error, Debug)]
+#[derive(Debug)]
 pub enum BeaconError {
+    BeforeGenesis,
     #[cfg(feature = "time")]
-    #[error("cannot parse duration")]
     DurationParse,
-    #[error("beacon not found")]
+    ChainLinkage {
+        round: u64,
+    },
+    InvalidBeacon {
+        round: u64,
+    },
+    /// `field` is `got` bytes long instead of `expected`.
+    InvalidLength {
+        field: &'static str,
+        expected: usize,
+        got: usize,
+    },
+    /// Chain period is zero, which leaves rounds undefined.
+    InvalidPeriod,
+    /// `input` is not valid JSON of the expected shape, such as `"beacon"` or `"chain info"`.
+    MalformedJson {
+        input: &'static str,
+        message: String,
+    },
     NotFound,
-    #[error("parsing failed")]
     Parsing,
-    #[error("round mismatch")]
+    Rollback {
+        round: u64,
+        highest: u64,
+    },
     RoundMismatch,
-    #[error("validation failed")]
-    Validation,
+    /// Time is outside of the range of [`OffsetDateTime`], such as a genesis time far in the future.
+    #[cfg(feature = "time")]
+    TimeOutOfRange,
+    Unchained,
+    UnknownScheme {
+        scheme: String,
+    },
+    UnsupportedScheme {
+        scheme: String,
+    },
+    Validation {
+        reason: VerificationOutcome,
+    },
+}
+
+impl fmt::Display for BeaconError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BeforeGenesis => write!(f, "time is before chain genesis"),
+            #[cfg(feature = "time")]
+            Self::DurationParse => write!(f, "cannot parse duration"),
+            Self::ChainLinkage { round } => {
+                write!(f, "round {round} is not linked to the previous beacon")
+            }
+            Self::InvalidBeacon { round } => write!(f, "beacon for round {round} is invalid"),
+            Self::InvalidLength {
+                field,
+                expected,
+                got,
+            } => write!(
+                f,
+                "invalid {field} length: expected {expected} bytes, got {got}"
+            ),
+            Self::InvalidPeriod => write!(f, "invalid chain period: expected at least 1 second"),
+            Self::MalformedJson { input, message } => write!(f, "malformed {input}: {message}"),
+            Self::NotFound => write!(f, "beacon not found"),
+            Self::Parsing => write!(f, "parsing failed"),
+            Self::Rollback { round, highest } => write!(
+                f,
+                "round {round} is older than previously seen round {highest}"
+            ),
+            Self::RoundMismatch => write!(f, "round mismatch"),
+            #[cfg(feature = "time")]
+            Self::TimeOutOfRange => write!(f, "time is out of range"),
+            Self::Unchained => write!(f, "unchained beacons are not linked to previous ones"),
+            Self::UnknownScheme { scheme } => write!(f, "unknown scheme {scheme}"),
+            Self::UnsupportedScheme { scheme } => {
+                write!(f, "scheme {scheme} is not supported by enabled features")
+            }
+            Self::Validation { .. } => write!(f, "validation failed"),
+        }
+    }
+}
+
+/// The reason of a validation failure is exposed as the error source.
+#[cfg(feature = "std")]
+impl std::error::Error for BeaconError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Validation { reason } => Some(reason),
+            _ => None,
+        }
+    }
+}
+
+/// Outcome of the verification of a beacon against chain info, see [`ApiBeacon::verify_detailed`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VerificationOutcome {
+    Ok,
+    /// Beacon is not of the kind produced by the chain scheme, such as an unchained beacon on a chained scheme.
+    SchemeMismatch {
+        beacon: &'static str,
+        chain: Scheme,
+    },
+    SignatureInvalid,
+    /// Round 0 is the chain genesis, which is not a signed beacon.
+    RoundZero,
+    /// Round is further ahead of the current round of the chain than [`VerifyOptions::with_max_round_ahead`] tolerates.
+    RoundAhead {
+        round: u64,
+        current: u64,
+    },
+    /// Randomness is not the hash of the signature. `expected` is computed from the signature, `actual` is carried by the beacon.
+    RandomnessMismatch {
+        expected: [u8; 32],
+        actual: [u8; 32],
+    },
+}
+
+impl VerificationOutcome {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, Self::Ok)
+    }
+}
+
+impl fmt::Display for VerificationOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ok => write!(f, "beacon is valid"),
+            Self::SchemeMismatch { beacon, chain } => {
+                write!(f, "{beacon} beacon does not match chain scheme {chain}")
+            }
+            Self::SignatureInvalid => write!(f, "signature is invalid"),
+            Self::RoundZero => write!(f, "round 0 is not a beacon"),
+            Self::RoundAhead { round, current } => {
+                write!(f, "round {round} is ahead of current round {current}")
+            }
+            Self::RandomnessMismatch { expected, actual } => write!(
+                f,
+                "randomness {} does not match signature hash {}",
+                hex::encode(actual),
+                hex::encode(expected)
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VerificationOutcome {}
+
+/// Checks performed when verifying a beacon, on top of its signature.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifyOptions {
+    check_randomness: bool,
+    #[cfg(feature = "std")]
+    max_round_ahead: Option<u64>,
+}
+
+impl VerifyOptions {
+    pub fn new() -> Self {
+        Self {
+            check_randomness: true,
+            #[cfg(feature = "std")]
+            max_round_ahead: None,
+        }
+    }
+
+    /// Whether beacon randomness has to be `sha256(signature)`. Enabled by default.
+    /// Deployments deriving randomness differently can disable it, and use [`ApiBeacon::canonical_randomness`] instead.
+    pub fn with_check_randomness(mut self, check_randomness: bool) -> Self {
+        self.check_randomness = check_randomness;
+        self
+    }
+
+    pub fn is_check_randomness(&self) -> bool {
+        self.check_randomness
+    }
+
+    /// Number of rounds a beacon can be ahead of the current round of the chain, derived from chain info and the system clock.
+    /// Beacons further ahead cannot have been produced yet, and are rejected with [`VerificationOutcome::RoundAhead`].
+    /// Disabled by default, as it relies on the local clock.
+    #[cfg(feature = "std")]
+    pub fn with_max_round_ahead(mut self, max_round_ahead: Option<u64>) -> Self {
+        self.max_round_ahead = max_round_ahead;
+        self
+    }
+
+    #[cfg(feature = "std")]
+    pub fn max_round_ahead(&self) -> Option<u64> {
+        self.max_round_ahead
+    }
+}
+
+impl Default for VerifyOptions {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-#[derive(Clone, Debug, Serialize)]
+/// Beacon retrieved from a chain, along with the time it is produced at.
+///
+/// It serializes as drand API does, without time. Beacons saved with [`RandomnessBeacon::serialize_with_time`] are loaded back with their time.
+/// When `time` is absent, it defaults to 0, and [`RandomnessBeacon::from_api`] recomputes it from chain info.
+///
+/// ```rust
+/// use drand_core::beacon::RandomnessBeacon;
+///
+/// # fn main() -> Result<(), serde_json::Error> {
+/// let saved = r#"{
+///     "round": 1000000,
+///     "randomness": "a26ba4d229c666f52a06f1a9be1278dcc7a80dbc1dd2004a1ae7b63cb79fd37e",
+///     "signature": "87e355169c4410a8ad6d3e7f5094b2122932c1062f603e6628aba2e4cb54f46c3bf1083c3537cd3b99e8296784f46fb40e090961cf9634f02c7dc2a96b69fc3c03735bc419962780a71245b72f81882cf6bb9c961bcf32da5624993bb747c9e5",
+///     "previous_signature": "86bbc40c9d9347568967add4ddf6e351aff604352a7e1eec9b20dea4ca531ed6c7d38de9956ffc3bb5a7fabe28b3a36b069c8113bd9824135c3bff9b03359476f6b03beec179d4aeff456f4d34bbf702b9af78c3bb44e1892ace8e581bf4afa9",
+///     "time": 1625431020
+/// }"#;
+///
+/// // load an archived beacon
+/// let beacon: RandomnessBeacon = serde_json::from_str(saved)?;
+/// assert_eq!(beacon.round(), 1000000);
+/// assert_eq!(beacon.time(), 1625431020);
+///
+/// // and save it again, keeping its time
+/// let saved = serde_json::to_string(&beacon.serialize_with_time())?;
+/// let reloaded: RandomnessBeacon = serde_json::from_str(&saved)?;
+/// assert_eq!(reloaded.time(), beacon.time());
+/// # Ok(())
+/// # }
+/// ```
+/// Beacons are equal when all their fields are, including their time.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct RandomnessBeacon {
     #[serde(flatten)]
     beacon: ApiBeacon,
-    #[serde(skip_serializing)]
+    #[serde(skip_serializing, default)]
     time: u64,
 }
 
 impl RandomnessBeacon {
+    #[cfg(test)]
     pub(crate) fn new(beacon: ApiBeacon, time: u64) -> Self {
         Self { beacon, time }
     }
 
+    /// Beacon of the chain described by `info`. Its time is the one the round is produced at.
+    /// The beacon is not verified, see [`RandomnessBeacon::verify`].
+    pub fn from_api(beacon: ApiBeacon, info: &ChainInfo) -> Self {
+        let time = info.time_info().time_of_round(beacon.round());
+        Self { beacon, time }
+    }
+
     pub fn verify(&self, info: ChainInfo) -> Result<bool> {
         self.beacon.verify(info)
     }
 
+    /// Verify against chain info whose public key has already been deserialized, see [`ChainInfo::prepare`].
+    pub fn verify_prepared(&self, info: &PreparedChainInfo) -> Result<bool> {
+        self.beacon.verify_prepared(info)
+    }
+
+    /// Verify against chain info, reporting why verification failed.
+    pub fn verify_detailed(&self, info: &PreparedChainInfo) -> Result<VerificationOutcome> {
+        self.beacon.verify_detailed(info)
+    }
+
+    /// Verify against chain info as [`Self::verify_detailed`] does, with some checks relaxed by `options`.
+    pub fn verify_with_options(
+        &self,
+        info: &PreparedChainInfo,
+        options: &VerifyOptions,
+    ) -> Result<VerificationOutcome> {
+        self.beacon.verify_with_options(info, options)
+    }
+
     pub fn round(&self) -> u64 {
         self.beacon.round()
     }
@@ -52,6 +301,16 @@ impl RandomnessBeacon {
         self.beacon.randomness()
     }
 
+    /// Randomness, whose length is checked when the beacon is parsed.
+    pub fn randomness_bytes(&self) -> [u8; 32] {
+        self.beacon.randomness_bytes()
+    }
+
+    /// Randomness derived from the signature, see [`ApiBeacon::canonical_randomness`].
+    pub fn canonical_randomness(&self) -> [u8; 32] {
+        self.beacon.canonical_randomness()
+    }
+
     pub fn is_unchained(&self) -> bool {
         self.beacon.is_unchained()
     }
@@ -60,46 +319,254 @@ impl RandomnessBeacon {
         self.beacon.signature()
     }
 
+    /// Signature, typed by the group it is on. Its length is checked when the beacon is parsed.
+    pub fn signature_bytes(&self) -> Signature {
+        self.beacon.signature_bytes()
+    }
+
+    /// Signature of the previous round, which is signed over by chained beacons.
+    pub fn previous_signature(&self) -> Option<Vec<u8>> {
+        self.beacon.previous_signature()
+    }
+
+    /// Check this beacon directly follows `previous` on a chained scheme.
+    /// Rounds have to be consecutive, and this beacon has to sign over the previous signature.
+    /// This does not verify signatures themselves, see [`verify_chain`] for that.
+    pub fn verify_previous(&self, previous: &RandomnessBeacon) -> Result<()> {
+        let Some(previous_signature) = self.previous_signature() else {
+            return Err(Box::new(BeaconError::Unchained).into());
+        };
+        if previous.is_unchained() {
+            return Err(Box::new(BeaconError::Unchained).into());
+        }
+        if previous.round().checked_add(1) != Some(self.round())
+            || previous_signature != previous.signature()
+        {
+            return Err(Box::new(BeaconError::ChainLinkage {
+                round: self.round(),
+            })
+            .into());
+        }
+        Ok(())
+    }
+
     pub fn time(&self) -> u64 {
         self.time
     }
 
+    /// Minified JSON, as served by drand `/public/<round>` endpoints. Retrieval time is not included.
+    pub fn to_json(&self) -> String {
+        self.beacon.to_json()
+    }
+
+    /// Serialize the beacon alongside the time it is produced at.
+    /// The default serialization stays the one of drand API, which does not include time.
+    pub fn serialize_with_time(&self) -> TimedRandomnessBeacon<'_> {
+        TimedRandomnessBeacon {
+            beacon: &self.beacon,
+            time: self.time,
+            #[cfg(feature = "time")]
+            datetime: OffsetDateTime::from_unix_timestamp(self.time as i64).ok(),
+        }
+    }
+
+    /// Derive `len` bytes for `domain` from the beacon randomness, using HKDF-SHA256 (RFC 5869).
+    /// Applications using the same beacon obtain independent values by using distinct domains.
+    ///
+    /// Input key material is the randomness, salt is the hash of `info`, and HKDF info is the round as 8 big-endian bytes followed by `domain`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is more than 8160 bytes, the maximum HKDF-SHA256 output length.
+    pub fn derive(&self, info: &ChainInfo, domain: &[u8], len: usize) -> Vec<u8> {
+        let hkdf_info = [self.round().to_be_bytes().as_slice(), domain].concat();
+        hkdf_sha256(&info.hash(), &self.randomness_bytes(), &hkdf_info, len)
+    }
+
     #[cfg(test)]
     pub(crate) fn beacon(&self) -> ApiBeacon {
         self.beacon.clone()
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// Same as the underlying [`ApiBeacon`].
+impl fmt::Display for RandomnessBeacon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.beacon.fmt(f)
+    }
+}
+
+/// Beacon serialized with drand API fields, followed by `time` in unix seconds.
+/// With the `time` feature, `datetime` is the same time as an RFC3339 string.
+#[derive(Debug, Serialize)]
+pub struct TimedRandomnessBeacon<'a> {
+    #[serde(flatten)]
+    beacon: &'a ApiBeacon,
+    time: u64,
+    #[cfg(feature = "time")]
+    #[serde(
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    datetime: Option<OffsetDateTime>,
+}
+
+/// Random number generation seeded with the beacon randomness.
+/// Values only depend on the beacon randomness. Independent parties using the same beacon derive the same values,
+/// as ChaCha20 output is stable across `rand_chacha` 0.3 releases.
+#[cfg(feature = "rand")]
+impl RandomnessBeacon {
+    /// ChaCha20 RNG seeded with the beacon randomness.
+    pub fn rng(&self) -> rand_chacha::ChaCha20Rng {
+        self.seed_rng()
+    }
+
+    /// RNG of type `R` seeded with the beacon randomness.
+    pub fn seed_rng<R: rand::SeedableRng<Seed = [u8; 32]>>(&self) -> R {
+        R::from_seed(self.randomness_bytes())
+    }
+
+    /// First `u64` output by [`Self::rng`].
+    pub fn rand_u64(&self) -> u64 {
+        rand::RngCore::next_u64(&mut self.rng())
+    }
+
+    /// Uniform value in `[0, n)`, drawn from [`Self::rng`].
+    /// Outputs above the largest multiple of `n` are rejected, so that the reduction is unbiased.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is 0.
+    pub fn choose(&self, n: u64) -> u64 {
+        assert!(n > 0, "cannot choose from an empty range");
+        // 2^64 mod n, the number of outputs that would bias the reduction
+        let rejected = (u64::MAX % n + 1) % n;
+        let mut rng = self.rng();
+        loop {
+            let value = rand::RngCore::next_u64(&mut rng);
+            if value <= u64::MAX - rejected {
+                return value % n;
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(untagged)]
 /// Random beacon as generated by drand.
 /// These can be chained or unchained, and should be verifiable against a chain.
+/// Beacons are equal when they are of the same kind, and all their fields are, whether they are verified or not.
 pub enum ApiBeacon {
     ChainedBeacon(ChainedBeacon),
     UnchainedBeacon(UnchainedBeacon),
 }
 
 impl ApiBeacon {
+    /// Beacon for `round`, chained if it has a previous signature. Randomness is derived from the signature.
+    #[cfg(feature = "proto")]
+    pub(crate) fn from_signatures(
+        round: u64,
+        signature: Vec<u8>,
+        previous_signature: Option<Vec<u8>>,
+    ) -> Result<Self> {
+        let randomness = Sha256::digest(&signature).into();
+        let signature = Signature::try_from(signature.as_slice())?;
+        let beacon = match previous_signature {
+            Some(previous_signature) => Self::ChainedBeacon(ChainedBeacon {
+                round,
+                randomness,
+                signature,
+                previous_signature,
+            }),
+            None => Self::UnchainedBeacon(UnchainedBeacon {
+                round,
+                randomness,
+                signature,
+            }),
+        };
+        Ok(beacon)
+    }
+
     pub fn verify(&self, info: ChainInfo) -> Result<bool> {
-        if self.is_unchained() != info.is_unchained()
-            || self.is_g1() && !info.scheme_id().contains("g1")
-        {
-            return Ok(false);
+        self.verify_prepared(&info.prepare()?)
+    }
+
+    /// Verify against chain info whose public key has already been deserialized, see [`ChainInfo::prepare`].
+    pub fn verify_prepared(&self, info: &PreparedChainInfo) -> Result<bool> {
+        Ok(self.verify_detailed(info)?.is_ok())
+    }
+
+    /// Verify against chain info, reporting why verification failed.
+    /// Errors are reserved to chain info or signatures which cannot be used, such as an unknown scheme,
+    /// or a signature whose length is not the one of the chain scheme group.
+    pub fn verify_detailed(&self, info: &PreparedChainInfo) -> Result<VerificationOutcome> {
+        self.verify_with_options(info, &VerifyOptions::default())
+    }
+
+    /// Verify against chain info as [`Self::verify_detailed`] does, with some checks relaxed by `options`.
+    pub fn verify_with_options(
+        &self,
+        info: &PreparedChainInfo,
+        options: &VerifyOptions,
+    ) -> Result<VerificationOutcome> {
+        let scheme = info.scheme();
+        // a beacon of another kind is an outcome, as it can be served by a relay for another chain
+        if self.is_unchained() != scheme.is_unchained() {
+            return Ok(VerificationOutcome::SchemeMismatch {
+                beacon: self.kind(),
+                chain: info.info().scheme(),
+            });
+        }
+        // the signature group is set by the scheme, signatures of another length are malformed rather than misrouted
+        let expected = scheme.signature_group().signature_len();
+        let got = self.signature_bytes().as_ref().len();
+        if got != expected {
+            return Err(Box::new(BeaconError::InvalidLength {
+                field: "signature",
+                expected,
+                got,
+            })
+            .into());
+        }
+        if self.round() == 0 {
+            return Ok(VerificationOutcome::RoundZero);
+        }
+        #[cfg(feature = "std")]
+        if let Some(max_round_ahead) = options.max_round_ahead() {
+            let current = info.info().current_round();
+            if self.round() > current.saturating_add(max_round_ahead) {
+                return Ok(VerificationOutcome::RoundAhead {
+                    round: self.round(),
+                    current,
+                });
+            }
         }
 
-        let signature_verify = crate::bls_signatures::verify(
-            self.dst(&info),
-            &self.signature(),
-            &self.message()?,
-            &info.public_key(),
-        )?;
+        let message = scheme.message(self)?;
+        let signature = self.signature();
+        if !info.verifier().verify(&signature, &message)? {
+            return Ok(VerificationOutcome::SignatureInvalid);
+        }
 
-        let mut hasher = Sha256::new();
-        hasher.update(self.signature());
-        let randomness = hasher.finalize().to_vec();
-        let randomness_verify = randomness == self.randomness();
+        if !options.is_check_randomness() {
+            return Ok(VerificationOutcome::Ok);
+        }
+        let expected = scheme.randomness(&signature);
+        let actual = self.randomness_bytes();
+        if expected != actual {
+            return Ok(VerificationOutcome::RandomnessMismatch { expected, actual });
+        }
+        Ok(VerificationOutcome::Ok)
+    }
 
-        Ok(signature_verify && randomness_verify)
+    /// Message the beacon signature is on, see [`chained_message`] and [`unchained_message`].
+    /// Fails if the previous signature of a chained beacon does not have the expected length.
+    /// `bls-bn254-unchained-on-g1` chains sign `keccak256(round)` instead.
+    pub fn message(&self) -> Result<Vec<u8>> {
+        match self {
+            Self::ChainedBeacon(chained) => chained.message(),
+            Self::UnchainedBeacon(unchained) => unchained.message(),
+        }
     }
 
     pub fn round(&self) -> u64 {
@@ -110,19 +577,20 @@ impl ApiBeacon {
     }
 
     pub fn randomness(&self) -> Vec<u8> {
+        self.randomness_bytes().to_vec()
+    }
+
+    /// Randomness carried by the beacon. If it was omitted, as drand v2 API allows, it is derived from the signature as drand does.
+    pub fn randomness_bytes(&self) -> [u8; 32] {
         match self {
-            Self::ChainedBeacon(chained) => chained.randomness.clone(),
-            Self::UnchainedBeacon(unchained) => unchained.randomness.clone(),
+            Self::ChainedBeacon(chained) => chained.randomness,
+            Self::UnchainedBeacon(unchained) => unchained.randomness,
         }
     }
 
-    fn dst(&self, info: &ChainInfo) -> &[u8] {
-        // Name of the HashToCurve RFC compliant scheme has been decided upon in https://github.com/drand/drand/pull/1249
-        if info.is_rfc9380() && info.scheme_id().contains("g1") {
-            crate::bls_signatures::G1_DOMAIN
-        } else {
-            crate::bls_signatures::G2_DOMAIN
-        }
+    /// Randomness as drand derives it, `sha256(signature)`, regardless of the one carried by the beacon.
+    pub fn canonical_randomness(&self) -> [u8; 32] {
+        Sha256::digest(self.signature()).into()
     }
 
     pub fn is_unchained(&self) -> bool {
@@ -132,28 +600,148 @@ impl ApiBeacon {
         }
     }
 
-    fn is_g1(&self) -> bool {
+    /// Kind of beacon, as reported by [`VerificationOutcome::SchemeMismatch`].
+    fn kind(&self) -> &'static str {
         match self {
-            Self::ChainedBeacon(_) => false,
-            Self::UnchainedBeacon(unchained) => unchained.signature.len() == 48,
+            Self::ChainedBeacon(_) => "chained",
+            Self::UnchainedBeacon(_) => "unchained",
         }
     }
 
     pub fn signature(&self) -> Vec<u8> {
+        self.signature_bytes().as_ref().to_vec()
+    }
+
+    pub fn signature_bytes(&self) -> Signature {
         match self {
             Self::ChainedBeacon(chained) => chained.signature.clone(),
             Self::UnchainedBeacon(unchained) => unchained.signature.clone(),
         }
     }
-}
 
-impl Message for ApiBeacon {
-    fn message(&self) -> Result<Vec<u8>> {
+    pub fn previous_signature(&self) -> Option<Vec<u8>> {
         match self {
-            Self::ChainedBeacon(chained) => chained.message(),
-            Self::UnchainedBeacon(unchained) => unchained.message(),
+            Self::ChainedBeacon(chained) => Some(chained.previous_signature.clone()),
+            Self::UnchainedBeacon(_) => None,
+        }
+    }
+
+    /// Minified JSON, as served by drand `/public/<round>` endpoints.
+    pub fn to_json(&self) -> String {
+        // fields are an integer and hex strings, which always serialize
+        serde_json::to_string(self).unwrap()
+    }
+}
+
+/// Single line `round=<n> randomness=<hex> signature=<hex>`. The format is stable.
+impl fmt::Display for ApiBeacon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "round={} randomness={} signature={}",
+            self.round(),
+            hex::encode(self.randomness_bytes()),
+            hex::encode(self.signature_bytes()),
+        )
+    }
+}
+
+/// Beacon which has been verified against its chain, see [`verify_beacon_json`].
+#[derive(Clone, Debug)]
+pub struct VerifiedBeacon {
+    beacon: RandomnessBeacon,
+}
+
+impl VerifiedBeacon {
+    /// Verified beacon, with the time it is produced at.
+    pub fn beacon(&self) -> &RandomnessBeacon {
+        &self.beacon
+    }
+
+    pub fn into_beacon(self) -> RandomnessBeacon {
+        self.beacon
+    }
+}
+
+/// Verify `beacon`, as served by drand `/public/<round>` endpoints, against `chain_info`, as served by `/info` endpoints.
+/// Both inputs are JSON strings, and no request is performed.
+///
+/// Inputs which cannot be parsed fail with [`BeaconError::MalformedJson`], naming the malformed input.
+/// Beacons which do not verify fail with [`BeaconError::Validation`], and the reason of the failure.
+pub fn verify_beacon_json(chain_info: &str, beacon: &str) -> Result<VerifiedBeacon> {
+    let malformed = |input: &'static str| {
+        move |e: serde_json::Error| -> DrandError {
+            Box::new(BeaconError::MalformedJson {
+                input,
+                message: e.to_string(),
+            })
+            .into()
+        }
+    };
+    let info: ChainInfo = serde_json::from_str(chain_info).map_err(malformed("chain info"))?;
+    let beacon: ApiBeacon = serde_json::from_str(beacon).map_err(malformed("beacon"))?;
+
+    let reason = beacon.verify_detailed(&info.prepare()?)?;
+    if !reason.is_ok() {
+        return Err(Box::new(BeaconError::Validation { reason }).into());
+    }
+    Ok(VerifiedBeacon {
+        beacon: RandomnessBeacon::from_api(beacon, &info),
+    })
+}
+
+/// Verify a contiguous slice of chained `beacons` against `info`.
+/// Each beacon signature is verified, and each beacon has to follow the one before it, as checked by [`RandomnessBeacon::verify_previous`].
+pub fn verify_chain(info: &ChainInfo, beacons: &[RandomnessBeacon]) -> Result<()> {
+    if info.is_unchained() || beacons.iter().any(RandomnessBeacon::is_unchained) {
+        return Err(Box::new(BeaconError::Unchained).into());
+    }
+    let prepared = info.prepare()?;
+    for beacon in beacons {
+        let reason = beacon.verify_detailed(&prepared)?;
+        if !reason.is_ok() {
+            return Err(Box::new(BeaconError::Validation { reason }).into());
+        }
+    }
+    for pair in beacons.windows(2) {
+        pair[1].verify_previous(&pair[0])?;
+    }
+    Ok(())
+}
+
+/// Verify `beacons` of the same chain against `info`.
+/// On BLS12-381 schemes, signatures are verified with a single pairing check, which is faster than verifying beacons one by one.
+/// If this check fails, beacons are verified one by one, and the first invalid beacon is reported as [`BeaconError::InvalidBeacon`].
+pub fn verify_batch(info: &ChainInfo, beacons: &[ApiBeacon]) -> Result<bool> {
+    let prepared = info.prepare()?;
+    let scheme = prepared.scheme();
+    let is_batch = scheme.signature_group() != SignatureGroup::Bn254G1
+        && beacons.iter().all(|beacon| {
+            beacon.is_unchained() == scheme.is_unchained()
+                && scheme.signature_group().signature_len() == beacon.signature().len()
+                && scheme.randomness(&beacon.signature()) == beacon.randomness_bytes()
+        });
+
+    if is_batch {
+        let signatures: Vec<Vec<u8>> = beacons.iter().map(ApiBeacon::signature).collect();
+        let hashes = beacons
+            .iter()
+            .map(|beacon| scheme.message(beacon))
+            .collect::<Result<Vec<_>>>()?;
+        if prepared.verifier().verify_batch(&signatures, &hashes)? {
+            return Ok(true);
         }
     }
+
+    for beacon in beacons {
+        if !beacon.verify_prepared(&prepared)? {
+            return Err(Box::new(BeaconError::InvalidBeacon {
+                round: beacon.round(),
+            })
+            .into());
+        }
+    }
+    Ok(true)
 }
 
 impl From<ChainedBeacon> for ApiBeacon {
@@ -173,61 +761,196 @@ trait Message {
     fn message(&self) -> Result<Vec<u8>>;
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// Message signed for a chained beacon, `sha256(previous_signature || round)`, with `round` as 8 bytes big-endian.
+/// `previous_signature` is the 96 bytes signature of the previous round, or the genesis seed for round 1, 32 bytes on drand networks.
+pub fn chained_message(previous_signature: &[u8], round: u64) -> Vec<u8> {
+    Sha256::new()
+        .chain_update(previous_signature)
+        .chain_update(round.to_be_bytes())
+        .finalize()
+        .to_vec()
+}
+
+/// Message signed for an unchained beacon, `sha256(round)`, with `round` as 8 bytes big-endian.
+pub fn unchained_message(round: u64) -> Vec<u8> {
+    Sha256::digest(round.to_be_bytes()).to_vec()
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+/// Signature of a beacon, typed by the group it is on.
+pub enum Signature {
+    /// BLS12-381 G1 point, compressed.
+    G1([u8; 48]),
+    /// BLS12-381 G2 point, compressed.
+    G2([u8; 96]),
+    /// BN254 G1 point, uncompressed.
+    Bn254G1([u8; 64]),
+}
+
+impl AsRef<[u8]> for Signature {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            Self::G1(signature) => signature,
+            Self::G2(signature) => signature,
+            Self::Bn254G1(signature) => signature,
+        }
+    }
+}
+
+impl TryFrom<&[u8]> for Signature {
+    type Error = DrandError;
+
+    fn try_from(value: &[u8]) -> Result<Self> {
+        let parsing = |_| -> DrandError { Box::new(BeaconError::Parsing).into() };
+        match value.len() {
+            48 => Ok(Self::G1(value.try_into().map_err(parsing)?)),
+            64 => Ok(Self::Bn254G1(value.try_into().map_err(parsing)?)),
+            96 => Ok(Self::G2(value.try_into().map_err(parsing)?)),
+            _ => Err(Box::new(BeaconError::Parsing).into()),
+        }
+    }
+}
+
+impl hex::FromHex for Signature {
+    type Error = hex::FromHexError;
+
+    fn from_hex<T: AsRef<[u8]>>(hex: T) -> core::result::Result<Self, Self::Error> {
+        let signature = <Vec<u8> as hex::FromHex>::from_hex(hex)?;
+        Self::try_from(signature.as_slice()).map_err(|_| hex::FromHexError::InvalidStringLength)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "ChainedBeaconJson")]
 /// Chained drand beacon.
 /// Each signature depends on the previous one, as well as on the round.
 pub struct ChainedBeacon {
     round: u64,
-    #[serde(with = "hex::serde")]
-    randomness: Vec<u8>,
-    #[serde(with = "hex::serde")]
-    signature: Vec<u8>,
-    #[serde(with = "hex::serde")]
+    #[serde(with = "crate::hex_serde")]
+    randomness: [u8; 32],
+    #[serde(with = "crate::hex_serde")]
+    signature: Signature,
+    #[serde(with = "crate::hex_serde")]
     previous_signature: Vec<u8>,
 }
 
+/// Longest genesis seed signed over by round 1 of a chained chain.
+/// drand uses the 32 bytes group hash, private networks can use other seeds, up to the length of a signature.
+const MAX_GENESIS_SEED_LEN: usize = 96;
+
 impl Message for ChainedBeacon {
     fn message(&self) -> Result<Vec<u8>> {
-        // First round signature is on the genesis seed, which size is 32B, and not 96B like G2 signatures.
-        let len = if self.round == 1 { 32 } else { 96 };
-        let mut buf = vec![0; len + 8];
-        let (signature_buf, round_buf) = buf.split_at_mut(len);
-
-        signature_buf.clone_from_slice(&self.previous_signature);
-        round_buf.clone_from_slice(&self.round.to_be_bytes());
-
-        let mut hasher = Sha256::new();
-        hasher.update(buf);
-        Ok(hasher.finalize().to_vec())
+        // First round signature is on the genesis seed, which is not a 96B G2 signature.
+        let valid = match self.round {
+            1 => (1..=MAX_GENESIS_SEED_LEN).contains(&self.previous_signature.len()),
+            _ => self.previous_signature.len() == 96,
+        };
+        if !valid {
+            return Err(Box::new(BeaconError::Parsing).into());
+        }
+        Ok(chained_message(&self.previous_signature, self.round))
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "UnchainedBeaconJson")]
 /// Unchained drand beacon.
 /// Each signature only depends on the round number.
 pub struct UnchainedBeacon {
     round: u64,
-    #[serde(with = "hex::serde")]
-    randomness: Vec<u8>,
-    #[serde(with = "hex::serde")]
-    signature: Vec<u8>,
+    #[serde(with = "crate::hex_serde")]
+    randomness: [u8; 32],
+    #[serde(with = "crate::hex_serde")]
+    signature: Signature,
 }
 
 impl Message for UnchainedBeacon {
     fn message(&self) -> Result<Vec<u8>> {
-        let buf = self.round.to_be_bytes();
+        Ok(unchained_message(self.round))
+    }
+}
+
+/// Chained beacon as served, whose randomness may be omitted.
+#[derive(Deserialize)]
+struct ChainedBeaconJson {
+    round: u64,
+    #[serde(default, deserialize_with = "crate::hex_serde::deserialize_some")]
+    randomness: Option<[u8; 32]>,
+    #[serde(with = "crate::hex_serde")]
+    signature: Signature,
+    #[serde(with = "crate::hex_serde")]
+    previous_signature: Vec<u8>,
+}
 
-        let mut hasher = Sha256::new();
-        hasher.update(buf);
-        Ok(hasher.finalize().to_vec())
+impl From<ChainedBeaconJson> for ChainedBeacon {
+    fn from(json: ChainedBeaconJson) -> Self {
+        Self {
+            round: json.round,
+            randomness: json
+                .randomness
+                .unwrap_or_else(|| Sha256::digest(&json.signature).into()),
+            signature: json.signature,
+            previous_signature: json.previous_signature,
+        }
     }
 }
 
-#[cfg(feature = "time")]
-impl From<ChainInfo> for ChainTimeInfo {
-    fn from(value: ChainInfo) -> Self {
-        Self::new(value.genesis_time(), value.period())
+/// Unchained beacon as served, whose randomness may be omitted.
+#[derive(Deserialize)]
+struct UnchainedBeaconJson {
+    round: u64,
+    #[serde(default, deserialize_with = "crate::hex_serde::deserialize_some")]
+    randomness: Option<[u8; 32]>,
+    #[serde(with = "crate::hex_serde")]
+    signature: Signature,
+}
+
+impl From<UnchainedBeaconJson> for UnchainedBeacon {
+    fn from(json: UnchainedBeaconJson) -> Self {
+        Self {
+            round: json.round,
+            randomness: json
+                .randomness
+                .unwrap_or_else(|| Sha256::digest(&json.signature).into()),
+            signature: json.signature,
+        }
+    }
+}
+
+const SHA256_BLOCK_LEN: usize = 64;
+
+fn hmac_sha256(key: &[u8], message: &[&[u8]]) -> [u8; 32] {
+    let mut block = [0u8; SHA256_BLOCK_LEN];
+    if key.len() > SHA256_BLOCK_LEN {
+        block[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = Sha256::new();
+    inner.update(block.map(|b| b ^ 0x36));
+    for chunk in message {
+        inner.update(chunk);
+    }
+    let mut outer = Sha256::new();
+    outer.update(block.map(|b| b ^ 0x5c));
+    outer.update(inner.finalize());
+    outer.finalize().into()
+}
+
+/// HKDF-SHA256 extract and expand, as per RFC 5869.
+fn hkdf_sha256(salt: &[u8], ikm: &[u8], info: &[u8], len: usize) -> Vec<u8> {
+    assert!(len <= 255 * 32, "HKDF-SHA256 output is at most 8160 bytes");
+    let prk = hmac_sha256(salt, &[ikm]);
+
+    let mut okm = Vec::with_capacity(len);
+    let mut t: Vec<u8> = vec![];
+    for i in 1..=len.div_ceil(32) as u8 {
+        t = hmac_sha256(&prk, &[&t, info, &[i]]).to_vec();
+        okm.extend_from_slice(&t);
     }
+    okm.truncate(len);
+    okm
 }
 
 #[cfg(feature = "time")]
@@ -246,17 +969,23 @@ pub struct RandomnessBeaconTime {
 impl RandomnessBeaconTime {
     /// round can be:
     /// * a specific round. e.g. 123,
-    /// * a duration. e.g. 30s,
-    /// * an RFC3339 date. e.g. 2023-06-28 21:30:22
+    /// * a duration, negative to look back in time. e.g. 30s, 1h30m, -10m,
+    /// * an RFC3339 date. e.g. 2023-06-28 21:30:22,
+    /// * a unix timestamp in seconds, prefixed with `@`. e.g. @1700000000
+    ///
+    /// Plain integers are always rounds, timestamps require the `@` prefix.
     pub fn parse(info: &ChainTimeInfo, round: &str) -> Result<Self> {
         match (
             round.parse::<u64>(),
             Self::parse_duration(round),
             Self::parse_offset_datetime(round),
+            Self::parse_unix_time(round),
         ) {
-            (Ok(round), Err(_), Err(_)) => Ok(Self::from_round(info, round)),
-            (Err(_), Ok(relative), Err(_)) => Ok(Self::from_duration(info, relative)),
-            (Err(_), Err(_), Ok(absolute)) => Ok(Self::from_datetime(info, absolute)),
+            (Ok(round), Err(_), Err(_), Err(_)) => Self::from_round(info, round),
+            (Err(_), Ok(relative), Err(_), Err(_)) => Self::from_duration(info, relative),
+            (Err(_), Err(_), Ok(absolute), Err(_)) | (Err(_), Err(_), Err(_), Ok(absolute)) => {
+                Self::from_datetime(info, absolute)
+            }
             _ => Err(Box::new(BeaconError::Parsing).into()),
         }
     }
@@ -273,58 +1002,104 @@ impl RandomnessBeaconTime {
         self.absolute
     }
 
-    pub fn from_round(info: &ChainTimeInfo, round: u64) -> Self {
-        let genesis = OffsetDateTime::from_unix_timestamp(info.genesis_time() as i64).unwrap();
-
-        let absolute = genesis + (((round - 1) * info.period()) as i64).seconds();
+    /// Time of `round`. Rounds start at 1, emitted at genesis, so round 0 fails with [`BeaconError::BeforeGenesis`].
+    pub fn from_round(info: &ChainTimeInfo, round: u64) -> Result<Self> {
+        if round == 0 {
+            return Err(Box::new(BeaconError::BeforeGenesis).into());
+        }
+        let absolute = info.datetime_of_round(round)?;
         let relative = absolute - OffsetDateTime::now_utc();
-        Self {
+        Ok(Self {
             round,
             relative,
             absolute,
-        }
+        })
     }
 
-    fn from_duration(info: &ChainTimeInfo, relative: Duration) -> Self {
-        let genesis = OffsetDateTime::from_unix_timestamp(info.genesis_time() as i64).unwrap();
+    /// Round at `relative` from now. Negative durations give past rounds, down to round 1 at genesis.
+    fn from_duration(info: &ChainTimeInfo, relative: Duration) -> Result<Self> {
+        let absolute = OffsetDateTime::now_utc()
+            .checked_add(relative)
+            .ok_or_else(|| -> DrandError { Box::new(BeaconError::TimeOutOfRange).into() })?;
+        let round = Self::round_at(info, absolute)?;
 
-        let absolute = OffsetDateTime::now_utc() + relative;
-        let round = ((absolute - genesis).whole_seconds() / (info.period() as i64) + 1) as u64;
-
-        Self {
+        Ok(Self {
             round,
             relative,
             absolute,
-        }
+        })
     }
 
-    fn from_datetime(info: &ChainTimeInfo, absolute: OffsetDateTime) -> Self {
-        let genesis = OffsetDateTime::from_unix_timestamp(info.genesis_time() as i64).unwrap();
-
+    fn from_datetime(info: &ChainTimeInfo, absolute: OffsetDateTime) -> Result<Self> {
         let relative = absolute - OffsetDateTime::now_utc();
-        let round = ((absolute - genesis).whole_seconds() / (info.period() as i64) + 1) as u64;
+        let round = Self::round_at(info, absolute)?;
 
-        Self {
+        Ok(Self {
             round,
             relative,
             absolute,
+        })
+    }
+
+    /// Round emitted at `absolute`, see [`ChainTimeInfo::round_at`]. Instants before genesis have no round, and fail with [`BeaconError::BeforeGenesis`].
+    fn round_at(info: &ChainTimeInfo, absolute: OffsetDateTime) -> Result<u64> {
+        match u64::try_from(absolute.unix_timestamp()).map(|unix_time| info.round_at(unix_time)) {
+            Ok(0) | Err(_) => Err(Box::new(BeaconError::BeforeGenesis).into()),
+            Ok(round) => Ok(round),
         }
     }
 
+    /// Duration is a sequence of integers followed by a unit, such as `30s` or `1h30m`, optionally separated by whitespace.
+    /// Units are `ms`, `s`, `m` or `min`, `h`, and `d`. A leading sign applies to the whole duration.
     fn parse_duration(duration: &str) -> Result<Duration> {
-        let l = duration.len() - 1;
-        let principal = duration[0..l]
-            .parse::<i64>()
-            .map_err(|_| -> DrandError { Box::new(BeaconError::DurationParse).into() })?;
-
-        let duration = match duration.chars().last().unwrap() {
-            's' => principal.seconds(),
-            'm' => principal.minutes(),
-            'h' => principal.hours(),
-            'd' => principal.days(),
-            _char => return Err(Box::new(BeaconError::DurationParse).into()),
+        let error = || -> DrandError { Box::new(BeaconError::DurationParse).into() };
+
+        let duration = duration.trim();
+        let (sign, mut rest) = match duration.strip_prefix('-') {
+            Some(rest) => (-1, rest),
+            None => (1, duration.strip_prefix('+').unwrap_or(duration)),
         };
-        Ok(duration)
+        if rest.is_empty() {
+            return Err(error());
+        }
+
+        let mut milliseconds: i64 = 0;
+        while !rest.is_empty() {
+            let digits = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            let principal = rest[..digits].parse::<i64>().map_err(|_| error())?;
+            rest = rest[digits..].trim_start();
+
+            let letters = rest
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(rest.len());
+            let unit: i64 = match &rest[..letters] {
+                "ms" => 1,
+                "s" => 1_000,
+                "m" | "min" => 60 * 1_000,
+                "h" => 60 * 60 * 1_000,
+                "d" => 24 * 60 * 60 * 1_000,
+                _ => return Err(error()),
+            };
+            rest = rest[letters..].trim_start();
+
+            milliseconds = principal
+                .checked_mul(unit)
+                .and_then(|component| milliseconds.checked_add(component))
+                .ok_or_else(error)?;
+        }
+        Ok(Duration::milliseconds(sign * milliseconds))
+    }
+
+    /// Unix time in seconds, prefixed with `@` as with GNU date.
+    fn parse_unix_time(timestamp: &str) -> Result<OffsetDateTime> {
+        timestamp
+            .strip_prefix('@')
+            .and_then(|timestamp| timestamp.parse::<u64>().ok())
+            .and_then(|timestamp| i64::try_from(timestamp).ok())
+            .and_then(|timestamp| OffsetDateTime::from_unix_timestamp(timestamp).ok())
+            .ok_or_else(|| -> DrandError { Box::new(BeaconError::Parsing).into() })
     }
 
     fn parse_offset_datetime(datetime: &str) -> Result<OffsetDateTime> {
@@ -347,12 +1122,16 @@ impl RandomnessBeaconTime {
 
 #[cfg(test)]
 pub mod tests {
+    #[cfg(feature = "time")]
     use std::ops::Sub;
+    #[cfg(feature = "time")]
+    use time::ext::NumericalDuration;
 
     use crate::chain::{
         tests::chained_chain_info,
         tests::{unchained_chain_info, unchained_chain_on_g1_info, unchained_chain_on_g1_rfc_info},
     };
+    use crate::DrandError;
 
     use super::*;
 
@@ -413,35 +1192,6 @@ pub mod tests {
         }"#).unwrap()
     }
 
-    impl PartialEq for ApiBeacon {
-        fn eq(&self, other: &Self) -> bool {
-            match (self, other) {
-                (Self::ChainedBeacon(chained), Self::ChainedBeacon(other)) => chained == other,
-                (Self::UnchainedBeacon(unchained), Self::UnchainedBeacon(other)) => {
-                    unchained == other
-                }
-                _ => false,
-            }
-        }
-    }
-
-    impl PartialEq for ChainedBeacon {
-        fn eq(&self, other: &Self) -> bool {
-            self.randomness == other.randomness
-                && self.round == other.round
-                && self.signature == other.signature
-                && self.previous_signature == other.previous_signature
-        }
-    }
-
-    impl PartialEq for UnchainedBeacon {
-        fn eq(&self, other: &Self) -> bool {
-            self.randomness == other.randomness
-                && self.round == other.round
-                && self.signature == other.signature
-        }
-    }
-
     #[test]
     fn randomness_beacon_verification_success_works() {
         match chained_beacon().verify(chained_chain_info()) {
@@ -472,6 +1222,21 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn randomness_beacon_g1_domains_works() {
+        // legacy and RFC 9380 chains hash to G1 with different tags, whose hashers are cached side by side
+        let legacy = unchained_chain_on_g1_info().prepare().unwrap();
+        let rfc = unchained_chain_on_g1_rfc_info().prepare().unwrap();
+        for _ in 0..3 {
+            assert!(unchained_beacon_on_g1().verify_prepared(&legacy).unwrap());
+            assert!(unchained_beacon_on_g1_rfc().verify_prepared(&rfc).unwrap());
+            assert!(!unchained_beacon_on_g1().verify_prepared(&rfc).unwrap());
+            assert!(!unchained_beacon_on_g1_rfc()
+                .verify_prepared(&legacy)
+                .unwrap());
+        }
+    }
+
     #[test]
     fn randomness_beacon_verification_failure_works() {
         match invalid_beacon().verify(chained_chain_info()) {
@@ -486,10 +1251,20 @@ pub mod tests {
             ),
         }
 
+        // signatures on another group than the chain scheme are malformed
         match unchained_beacon_on_g1().verify(unchained_chain_info()) {
-            Ok(ok) => assert!(!ok, "Unchained beacon on G1 should not validate on chained info"),
-            Err(_err) => panic!(
-                "Unchained beacon on G1 should not validate on chained info without returning an error"
+            Ok(_ok) => panic!("Unchained beacon on G1 should not verify on unchained G2 info"),
+            Err(DrandError::Beacon(e)) => assert!(matches!(
+                *e,
+                BeaconError::InvalidLength {
+                    field: "signature",
+                    expected: 96,
+                    got: 48
+                }
+            )),
+            Err(err) => panic!(
+                "Unchained beacon on G1 should fail to parse on unchained G2 info, got {}",
+                err
             ),
         }
 
@@ -510,6 +1285,1318 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn randomness_beacon_verify_prepared_works() {
+        let beacons = [
+            chained_beacon(),
+            chained_beacon_1(),
+            unchained_beacon(),
+            unchained_beacon_on_g1(),
+            unchained_beacon_on_g1_rfc(),
+            invalid_beacon(),
+        ];
+        for info in [
+            chained_chain_info(),
+            unchained_chain_info(),
+            unchained_chain_on_g1_info(),
+            unchained_chain_on_g1_rfc_info(),
+        ] {
+            let prepared = info.prepare().unwrap();
+            assert_eq!(prepared.info(), &info);
+            for beacon in beacons.iter() {
+                match (
+                    beacon.verify(info.clone()),
+                    beacon.verify_prepared(&prepared),
+                ) {
+                    (Ok(expected), Ok(actual)) => assert_eq!(expected, actual),
+                    (Err(_), Err(_)) => (),
+                    (expected, actual) => panic!(
+                        "Prepared verification should match, expected {:?}, got {:?}",
+                        expected, actual
+                    ),
+                }
+            }
+        }
+
+        // public key is not a point
+        let mut info = serde_json::to_value(chained_chain_info()).unwrap();
+        info["public_key"] = hex::encode([1u8; 48]).into();
+        let info: ChainInfo = serde_json::from_value(info).unwrap();
+        assert!(info.prepare().is_err());
+    }
+
+    #[test]
+    fn randomness_beacon_typed_bytes_works() {
+        let beacon = RandomnessBeacon::new(chained_beacon(), 0);
+        assert_eq!(beacon.randomness_bytes().to_vec(), beacon.randomness());
+        assert!(matches!(beacon.signature_bytes(), Signature::G2(_)));
+        assert_eq!(beacon.signature_bytes().as_ref(), beacon.signature());
+
+        let beacon = RandomnessBeacon::new(unchained_beacon_on_g1(), 0);
+        assert!(matches!(beacon.signature_bytes(), Signature::G1(_)));
+        assert_eq!(beacon.signature_bytes().as_ref(), beacon.signature());
+    }
+
+    #[test]
+    fn randomness_beacon_display_works() {
+        let beacon = RandomnessBeacon::new(chained_beacon(), 0);
+        assert_eq!(
+            beacon.to_string(),
+            "round=1000000 randomness=a26ba4d229c666f52a06f1a9be1278dcc7a80dbc1dd2004a1ae7b63cb79fd37e signature=87e355169c4410a8ad6d3e7f5094b2122932c1062f603e6628aba2e4cb54f46c3bf1083c3537cd3b99e8296784f46fb40e090961cf9634f02c7dc2a96b69fc3c03735bc419962780a71245b72f81882cf6bb9c961bcf32da5624993bb747c9e5"
+        );
+        assert_eq!(
+            beacon.to_json(),
+            r#"{"round":1000000,"randomness":"a26ba4d229c666f52a06f1a9be1278dcc7a80dbc1dd2004a1ae7b63cb79fd37e","signature":"87e355169c4410a8ad6d3e7f5094b2122932c1062f603e6628aba2e4cb54f46c3bf1083c3537cd3b99e8296784f46fb40e090961cf9634f02c7dc2a96b69fc3c03735bc419962780a71245b72f81882cf6bb9c961bcf32da5624993bb747c9e5","previous_signature":"86bbc40c9d9347568967add4ddf6e351aff604352a7e1eec9b20dea4ca531ed6c7d38de9956ffc3bb5a7fabe28b3a36b069c8113bd9824135c3bff9b03359476f6b03beec179d4aeff456f4d34bbf702b9af78c3bb44e1892ace8e581bf4afa9"}"#
+        );
+
+        let beacon = unchained_beacon();
+        assert_eq!(
+            beacon.to_string(),
+            "round=1000000 randomness=6671747f7d838f18159c474579ea19e8d863e8c25e5271fd7f18ca2ac85181cf signature=86b265e10e060805d20dca88f70f6b5e62d5956e7790d32029dfb73fbcd1996bc7aebdea7aeaf74dac0ca2b3ce8f7a6a0399f224a05fe740c0bac9da638212082b0ed21b1a8c5e44a33123f28955ef0713e93e21f6af0cda4073d9a73387434d"
+        );
+        assert_eq!(
+            beacon.to_json(),
+            r#"{"round":1000000,"randomness":"6671747f7d838f18159c474579ea19e8d863e8c25e5271fd7f18ca2ac85181cf","signature":"86b265e10e060805d20dca88f70f6b5e62d5956e7790d32029dfb73fbcd1996bc7aebdea7aeaf74dac0ca2b3ce8f7a6a0399f224a05fe740c0bac9da638212082b0ed21b1a8c5e44a33123f28955ef0713e93e21f6af0cda4073d9a73387434d"}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<ApiBeacon>(&beacon.to_json()).unwrap(),
+            beacon
+        );
+    }
+
+    #[test]
+    fn randomness_beacon_eq_hash_works() {
+        use std::collections::HashSet;
+
+        // equality is field by field, so a beacon equals its parsed copy
+        let beacon = chained_beacon();
+        assert_eq!(
+            serde_json::from_str::<ApiBeacon>(&beacon.to_json()).unwrap(),
+            beacon
+        );
+        assert_ne!(chained_beacon(), chained_beacon_1());
+        assert_ne!(unchained_beacon_on_g1(), unchained_beacon_on_g1_rfc());
+        assert_ne!(
+            RandomnessBeacon::new(chained_beacon(), 0),
+            RandomnessBeacon::new(chained_beacon(), 1)
+        );
+
+        let beacons: HashSet<ApiBeacon> = [
+            chained_beacon(),
+            chained_beacon(),
+            unchained_beacon(),
+            unchained_beacon(),
+            unchained_beacon_on_g1(),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(beacons.len(), 3);
+        assert!(beacons.contains(&unchained_beacon()));
+        assert!(!beacons.contains(&chained_beacon_1()));
+    }
+
+    #[test]
+    fn randomness_beacon_serialize_with_time_works() {
+        let info = chained_chain_info();
+        let beacon =
+            RandomnessBeacon::new(chained_beacon(), info.time_info().time_of_round(1000000));
+        assert_eq!(beacon.time(), 1625431020);
+
+        // default serialization is the one of drand API
+        assert_eq!(serde_json::to_string(&beacon).unwrap(), beacon.to_json());
+
+        let api = beacon.to_json();
+        let api = api.strip_suffix('}').unwrap();
+        let expected = if cfg!(feature = "time") {
+            format!(r#"{api},"time":1625431020,"datetime":"2021-07-04T20:37:00Z"}}"#)
+        } else {
+            format!(r#"{api},"time":1625431020}}"#)
+        };
+        assert_eq!(
+            serde_json::to_string(&beacon.serialize_with_time()).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn randomness_beacon_deserialize_works() {
+        let info = chained_chain_info();
+        let beacon = RandomnessBeacon::from_api(chained_beacon(), &info);
+        assert_eq!(
+            beacon.time(),
+            info.time_info().time_of_round(beacon.round())
+        );
+
+        // saved with time
+        let saved = serde_json::to_string(&beacon.serialize_with_time()).unwrap();
+        let reloaded: RandomnessBeacon = serde_json::from_str(&saved).unwrap();
+        assert_eq!(reloaded.time(), beacon.time());
+        assert_eq!(reloaded.to_json(), beacon.to_json());
+        assert!(reloaded.verify(info.clone()).unwrap());
+
+        // saved as drand API does, time is recomputed from chain info
+        let saved = serde_json::to_string(&beacon).unwrap();
+        let reloaded: RandomnessBeacon = serde_json::from_str(&saved).unwrap();
+        assert_eq!(reloaded.time(), 0);
+        assert_eq!(reloaded.to_json(), beacon.to_json());
+        let reloaded =
+            RandomnessBeacon::from_api(serde_json::from_str::<ApiBeacon>(&saved).unwrap(), &info);
+        assert_eq!(reloaded.time(), beacon.time());
+
+        let beacon = RandomnessBeacon::from_api(unchained_beacon(), &unchained_chain_info());
+        let saved = serde_json::to_string(&beacon.serialize_with_time()).unwrap();
+        let reloaded: RandomnessBeacon = serde_json::from_str(&saved).unwrap();
+        assert!(reloaded.is_unchained());
+        assert_eq!(reloaded.time(), beacon.time());
+        assert!(reloaded.verify(unchained_chain_info()).unwrap());
+    }
+
+    #[test]
+    fn beacon_message_works() {
+        // messages signed by the fixtures, which verify against their chain info
+        for (beacon, expected) in [
+            (
+                chained_beacon(),
+                "79fcd2842ac7b7b513a492e937f6941d842779bf3a4f7cf55214288aba1259c9",
+            ),
+            (
+                chained_beacon_1(),
+                "3bdd496a35f3c3b6b4318da8b77d3ce4793cbdf26e08156f6fd8c49e85b727cb",
+            ),
+            (
+                unchained_beacon(),
+                "ce59b701970051bef0d7efdc1a4196c49ce1bbaaf9c5403626ad7adcc41737e7",
+            ),
+            (
+                unchained_beacon_on_g1(),
+                "218e1b871a658f75c71d431be5566ae9e5abf5e04607e404e8d26cbf5e4429e8",
+            ),
+        ] {
+            assert_eq!(hex::encode(beacon.message().unwrap()), expected);
+        }
+
+        let beacon = chained_beacon();
+        assert_eq!(
+            chained_message(&beacon.previous_signature().unwrap(), beacon.round()),
+            beacon.message().unwrap()
+        );
+        assert_eq!(
+            unchained_message(1000000),
+            unchained_beacon().message().unwrap()
+        );
+
+        // previous signature of the wrong length
+        let ApiBeacon::ChainedBeacon(mut chained) = beacon else {
+            panic!("beacon should be chained")
+        };
+        chained.previous_signature.truncate(48);
+        match ApiBeacon::from(chained).message() {
+            Err(DrandError::Beacon(e)) => assert!(matches!(*e, BeaconError::Parsing)),
+            _ => panic!("message should not be built from a truncated previous signature"),
+        }
+    }
+
+    #[test]
+    fn verify_beacon_json_works() {
+        fn json<T: Serialize>(value: &T) -> String {
+            serde_json::to_string(value).unwrap()
+        }
+
+        for (info, beacon) in [
+            (chained_chain_info(), chained_beacon()),
+            (unchained_chain_info(), unchained_beacon()),
+            (unchained_chain_on_g1_info(), unchained_beacon_on_g1()),
+            (
+                unchained_chain_on_g1_rfc_info(),
+                unchained_beacon_on_g1_rfc(),
+            ),
+        ] {
+            let verified = verify_beacon_json(&json(&info), &json(&beacon)).unwrap();
+            assert_eq!(verified.beacon().round(), beacon.round());
+            assert_eq!(verified.beacon().randomness(), beacon.randomness());
+            assert_eq!(
+                verified.beacon().time(),
+                info.time_info().time_of_round(beacon.round())
+            );
+        }
+
+        let info = json(&chained_chain_info());
+        match verify_beacon_json(&info, &json(&invalid_beacon())) {
+            Ok(_) => panic!("invalid beacon should not verify"),
+            Err(DrandError::Beacon(e)) => assert!(matches!(
+                *e,
+                BeaconError::Validation {
+                    reason: VerificationOutcome::SignatureInvalid
+                }
+            )),
+            Err(err) => panic!("invalid beacon should not verify, got {err}"),
+        }
+
+        for (info, beacon, input) in [
+            ("not json", json(&chained_beacon()), "chain info"),
+            (info.as_str(), "{}".to_string(), "beacon"),
+            (info.as_str(), r#"{"round":1}"#.to_string(), "beacon"),
+        ] {
+            match verify_beacon_json(info, &beacon) {
+                Ok(_) => panic!("malformed {input} should not verify"),
+                Err(DrandError::Beacon(e)) => assert!(
+                    matches!(*e, BeaconError::MalformedJson { input: malformed, .. } if malformed == input),
+                    "{e}"
+                ),
+                Err(err) => panic!("malformed {input} should not verify, got {err}"),
+            }
+        }
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn randomness_beacon_rng_works() {
+        use rand::RngCore;
+
+        let beacon = RandomnessBeacon::new(chained_beacon(), 0);
+        let mut rng = beacon.rng();
+        assert_eq!(rng.next_u64(), 17808174025754809951);
+        assert_eq!(rng.next_u64(), 5910963607548599339);
+        assert_eq!(beacon.rand_u64(), 17808174025754809951);
+        let mut rng: rand_chacha::ChaCha20Rng = beacon.seed_rng();
+        assert_eq!(rng.next_u64(), 17808174025754809951);
+
+        assert_eq!(beacon.choose(1), 0);
+        assert_eq!(beacon.choose(2), 1);
+        assert_eq!(beacon.choose(6), 1);
+        assert_eq!(beacon.choose(100), 51);
+        assert_eq!(beacon.choose(1000000), 809951);
+        // first output is rejected, as it is above the largest multiple of n
+        assert_eq!(beacon.choose((1 << 63) + 1), 5910963607548599339);
+    }
+
+    #[test]
+    fn hkdf_sha256_works() {
+        // RFC 5869 test case 1
+        let okm = hkdf_sha256(
+            &hex::decode("000102030405060708090a0b0c").unwrap(),
+            &[0x0b; 22],
+            &hex::decode("f0f1f2f3f4f5f6f7f8f9").unwrap(),
+            42,
+        );
+        assert_eq!(
+            hex::encode(okm),
+            "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865"
+        );
+    }
+
+    #[test]
+    fn randomness_beacon_derive_works() {
+        // Test vectors for drand mainnet round 1000000
+        let beacon = RandomnessBeacon::new(chained_beacon(), 0);
+        let info = chained_chain_info();
+        assert_eq!(
+            hex::encode(beacon.derive(&info, b"coin_flip", 32)),
+            "6c3958b1f208a336b755fbd99cc44593637e2bc74074cc755e4d90f695075972"
+        );
+        assert_eq!(
+            hex::encode(beacon.derive(&info, b"lottery", 64)),
+            "536e0b906105b45c3b21684cc7e7a31663c3f22ea23b0eb03426395fd744b5f45d10889da9151cf3ada3c53076c1a9ca21e91e8f8696e9019544fcab11403e19"
+        );
+        assert_eq!(
+            hex::encode(beacon.derive(&info, b"", 16)),
+            "9125597397ffcc9904aa10f261b6540a"
+        );
+        assert!(beacon.derive(&info, b"coin_flip", 0).is_empty());
+
+        // domains are independent, and the shorter output is a prefix of the longer one
+        assert_ne!(
+            beacon.derive(&info, b"coin_flip", 32),
+            beacon.derive(&info, b"lottery", 32)
+        );
+        assert_eq!(
+            beacon.derive(&info, b"lottery", 32),
+            beacon.derive(&info, b"lottery", 64)[..32]
+        );
+    }
+
+    #[test]
+    fn randomness_beacon_length_violation_fails() {
+        let valid = serde_json::to_value(unchained_beacon()).unwrap();
+
+        // randomness is 31 bytes
+        let mut beacon = valid.clone();
+        beacon["randomness"] = valid["randomness"].as_str().unwrap()[2..].into();
+        assert!(serde_json::from_value::<ApiBeacon>(beacon).is_err());
+
+        // signature is 95 bytes
+        let mut beacon = valid.clone();
+        beacon["signature"] = valid["signature"].as_str().unwrap()[2..].into();
+        assert!(serde_json::from_value::<ApiBeacon>(beacon).is_err());
+
+        // signature is neither on G1 nor on G2
+        let mut beacon = valid.clone();
+        beacon["signature"] = hex::encode([0u8; 50]).into();
+        assert!(serde_json::from_value::<ApiBeacon>(beacon).is_err());
+
+        match Signature::try_from([0u8; 50].as_slice()) {
+            Ok(_) => panic!("50 bytes should not be a signature"),
+            Err(DrandError::Beacon(e)) => assert!(matches!(*e, BeaconError::Parsing)),
+            Err(err) => panic!("50 bytes should not be a signature, got {}", err),
+        }
+    }
+
+    #[test]
+    fn randomness_beacon_round_zero_fails() {
+        let info = unchained_chain_info().prepare().unwrap();
+        let mut beacon = serde_json::to_value(unchained_beacon()).unwrap();
+        beacon["round"] = 0.into();
+        let beacon: ApiBeacon = serde_json::from_value(beacon).unwrap();
+
+        assert_eq!(
+            beacon.verify_detailed(&info).unwrap(),
+            VerificationOutcome::RoundZero
+        );
+        let options = VerifyOptions::new().with_check_randomness(false);
+        assert_eq!(
+            beacon.verify_with_options(&info, &options).unwrap(),
+            VerificationOutcome::RoundZero
+        );
+        assert!(!beacon.verify(unchained_chain_info()).unwrap());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn randomness_beacon_max_round_ahead_works() {
+        let beacon = unchained_beacon();
+        let published = unchained_chain_info();
+
+        // unchained signatures only depend on the round, so the same beacon is ahead of a chain starting now
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let starting = ChainInfo::new(
+            published.public_key(),
+            published.period(),
+            now,
+            published.hash(),
+            published.group_hash(),
+            published.scheme_id(),
+            published.metadata(),
+        )
+        .unwrap();
+
+        // disabled by default
+        assert_eq!(VerifyOptions::default().max_round_ahead(), None);
+        for info in [&published, &starting] {
+            assert_eq!(
+                beacon.verify_detailed(&info.prepare().unwrap()).unwrap(),
+                VerificationOutcome::Ok
+            );
+        }
+
+        let options = VerifyOptions::new().with_max_round_ahead(Some(2));
+        assert_eq!(options.max_round_ahead(), Some(2));
+        assert_eq!(
+            beacon
+                .verify_with_options(&published.prepare().unwrap(), &options)
+                .unwrap(),
+            VerificationOutcome::Ok
+        );
+        match beacon
+            .verify_with_options(&starting.prepare().unwrap(), &options)
+            .unwrap()
+        {
+            VerificationOutcome::RoundAhead { round, current } => {
+                assert_eq!(round, beacon.round());
+                assert!(current <= 2);
+            }
+            outcome => panic!("beacon should be ahead, got {outcome}"),
+        }
+
+        // tolerance covers the beacon round
+        let options = VerifyOptions::new().with_max_round_ahead(Some(beacon.round()));
+        assert_eq!(
+            beacon
+                .verify_with_options(&starting.prepare().unwrap(), &options)
+                .unwrap(),
+            VerificationOutcome::Ok
+        );
+    }
+
+    #[test]
+    fn randomness_beacon_verify_options_works() {
+        let info = unchained_chain_info().prepare().unwrap();
+        let expected = unchained_beacon().randomness_bytes();
+
+        let mut incorrect = serde_json::to_value(unchained_beacon()).unwrap();
+        incorrect["randomness"] = hex::encode([0xaa; 32]).into();
+        let mut missing = serde_json::to_value(unchained_beacon()).unwrap();
+        missing.as_object_mut().unwrap().remove("randomness");
+
+        // omitted randomness is derived from the signature, and verifies
+        let derived: ApiBeacon = serde_json::from_value(missing).unwrap();
+        assert_eq!(derived, unchained_beacon());
+        assert_eq!(
+            derived
+                .verify_with_options(&info, &VerifyOptions::default())
+                .unwrap(),
+            VerificationOutcome::Ok
+        );
+
+        let actual = [0xaa; 32];
+        let beacon: ApiBeacon = serde_json::from_value(incorrect).unwrap();
+        assert_eq!(beacon.randomness_bytes(), actual);
+        assert_eq!(beacon.canonical_randomness(), expected);
+
+        // randomness is checked by default
+        assert_eq!(
+            beacon
+                .verify_with_options(&info, &VerifyOptions::default())
+                .unwrap(),
+            VerificationOutcome::RandomnessMismatch { expected, actual }
+        );
+        assert!(!beacon.verify_prepared(&info).unwrap());
+
+        let options = VerifyOptions::new().with_check_randomness(false);
+        assert_eq!(
+            beacon.verify_with_options(&info, &options).unwrap(),
+            VerificationOutcome::Ok
+        );
+
+        // signature is still checked
+        let forged = serde_json::to_value(&beacon).unwrap();
+        let mut forged: ApiBeacon = serde_json::from_value(forged).unwrap();
+        if let ApiBeacon::UnchainedBeacon(unchained) = &mut forged {
+            unchained.round += 1;
+        }
+        assert_eq!(
+            forged.verify_with_options(&info, &options).unwrap(),
+            VerificationOutcome::SignatureInvalid
+        );
+    }
+
+    #[test]
+    fn randomness_beacon_without_randomness_works() {
+        for (beacon, info) in [
+            (chained_beacon(), chained_chain_info()),
+            (chained_beacon_1(), chained_chain_info()),
+            (unchained_beacon(), unchained_chain_info()),
+            (unchained_beacon_on_g1(), unchained_chain_on_g1_info()),
+        ] {
+            let with_randomness = serde_json::to_value(&beacon).unwrap();
+            let mut without_randomness = with_randomness.clone();
+            without_randomness
+                .as_object_mut()
+                .unwrap()
+                .remove("randomness");
+
+            for json in [with_randomness.clone(), without_randomness] {
+                let parsed: ApiBeacon = serde_json::from_value(json.clone()).unwrap();
+                assert_eq!(parsed, beacon);
+                assert_eq!(parsed.randomness_bytes(), parsed.canonical_randomness());
+                assert!(parsed.verify(info.clone()).unwrap());
+                // derived randomness is serialized, as served by drand v1 API
+                assert_eq!(serde_json::to_value(&parsed).unwrap(), with_randomness);
+
+                let parsed: RandomnessBeacon = serde_json::from_value(json).unwrap();
+                assert_eq!(parsed.randomness_bytes(), beacon.randomness_bytes());
+            }
+        }
+    }
+
+    #[test]
+    fn randomness_beacon_verification_detailed_works() {
+        let outcome = |beacon: ApiBeacon, info: ChainInfo| {
+            beacon.verify_detailed(&info.prepare().unwrap()).unwrap()
+        };
+
+        assert_eq!(
+            outcome(chained_beacon(), chained_chain_info()),
+            VerificationOutcome::Ok
+        );
+        assert_eq!(
+            outcome(invalid_beacon(), chained_chain_info()),
+            VerificationOutcome::SignatureInvalid
+        );
+        assert_eq!(
+            outcome(unchained_beacon(), chained_chain_info()),
+            VerificationOutcome::SchemeMismatch {
+                beacon: "unchained",
+                chain: Scheme::PedersenBlsChained
+            }
+        );
+        assert_eq!(
+            outcome(chained_beacon(), unchained_chain_info()),
+            VerificationOutcome::SchemeMismatch {
+                beacon: "chained",
+                chain: Scheme::PedersenBlsUnchained
+            }
+        );
+        assert_eq!(
+            outcome(unchained_beacon_on_g1(), chained_chain_info()),
+            VerificationOutcome::SchemeMismatch {
+                beacon: "unchained",
+                chain: Scheme::PedersenBlsChained
+            }
+        );
+        // same group, but a different hash to curve
+        assert_eq!(
+            outcome(unchained_beacon_on_g1(), unchained_chain_on_g1_rfc_info()),
+            VerificationOutcome::SignatureInvalid
+        );
+        // same kind, but a signature of another group is malformed, and not an outcome
+        assert_invalid_length(
+            unchained_beacon_on_g1().verify_detailed(&unchained_chain_info().prepare().unwrap()),
+            "signature",
+            96,
+            48,
+        );
+
+        let mut beacon = serde_json::to_value(unchained_beacon()).unwrap();
+        beacon["randomness"] = hex::encode([0u8; 32]).into();
+        let beacon: ApiBeacon = serde_json::from_value(beacon).unwrap();
+        let expected = unchained_beacon().randomness_bytes();
+        assert_eq!(
+            outcome(beacon.clone(), unchained_chain_info()),
+            VerificationOutcome::RandomnessMismatch {
+                expected,
+                actual: [0u8; 32]
+            }
+        );
+        assert!(!beacon.verify(unchained_chain_info()).unwrap());
+        assert_eq!(
+            VerificationOutcome::RandomnessMismatch {
+                expected,
+                actual: [0u8; 32]
+            }
+            .to_string(),
+            format!(
+                "randomness {} does not match signature hash {}",
+                hex::encode([0u8; 32]),
+                hex::encode(expected)
+            )
+        );
+    }
+
+    fn assert_invalid_length<T: fmt::Debug>(
+        result: Result<T>,
+        field: &str,
+        expected: usize,
+        got: usize,
+    ) {
+        match result {
+            Err(DrandError::Beacon(e)) => match *e {
+                BeaconError::InvalidLength {
+                    field: actual_field,
+                    expected: actual_expected,
+                    got: actual_got,
+                } => assert_eq!(
+                    (actual_field, actual_expected, actual_got),
+                    (field, expected, got)
+                ),
+                e => panic!("{field} should have an invalid length, got {e}"),
+            },
+            Err(err) => panic!("{field} should have an invalid length, got {err}"),
+            Ok(ok) => panic!("{field} should have an invalid length, got {ok:?}"),
+        }
+    }
+
+    #[test]
+    fn randomness_beacon_swapped_groups_fails() {
+        // signatures of G1 and G2 fixtures are swapped, keeping the rest of the beacon
+        let with_signature = |beacon: ApiBeacon, from: &ApiBeacon| -> ApiBeacon {
+            let mut beacon = serde_json::to_value(beacon).unwrap();
+            beacon["signature"] = hex::encode(from.signature()).into();
+            serde_json::from_value(beacon).unwrap()
+        };
+        let on_g2 = with_signature(unchained_beacon_on_g1(), &unchained_beacon());
+        let on_g1 = with_signature(unchained_beacon(), &unchained_beacon_on_g1());
+        assert_invalid_length(
+            on_g2.verify(unchained_chain_on_g1_info()),
+            "signature",
+            48,
+            96,
+        );
+        assert_invalid_length(
+            on_g2.verify(unchained_chain_on_g1_rfc_info()),
+            "signature",
+            48,
+            96,
+        );
+        assert_invalid_length(on_g1.verify(unchained_chain_info()), "signature", 96, 48);
+        assert_invalid_length(
+            with_signature(chained_beacon(), &unchained_beacon_on_g1())
+                .verify(chained_chain_info()),
+            "signature",
+            96,
+            48,
+        );
+
+        // public keys of G1 and G2 chains are swapped, keeping the scheme
+        let with_public_key = |info: ChainInfo, from: &ChainInfo| -> ChainInfo {
+            let mut info = serde_json::to_value(info).unwrap();
+            info["public_key"] = hex::encode(from.public_key()).into();
+            serde_json::from_value(info).unwrap()
+        };
+        let g1_info = with_public_key(unchained_chain_on_g1_info(), &unchained_chain_info());
+        let g2_info = with_public_key(unchained_chain_info(), &unchained_chain_on_g1_info());
+        assert_invalid_length(g1_info.prepare(), "public key", 96, 48);
+        assert_invalid_length(
+            unchained_beacon_on_g1().verify(g1_info),
+            "public key",
+            96,
+            48,
+        );
+        assert_invalid_length(g2_info.prepare(), "public key", 48, 96);
+        assert_invalid_length(unchained_beacon().verify(g2_info), "public key", 48, 96);
+    }
+
+    #[test]
+    fn randomness_beacon_truncated_signature_fails() {
+        // a signature truncated to the length of another group parses, but is rejected by the chain scheme
+        let truncate = |beacon: ApiBeacon, len: usize| -> ApiBeacon {
+            let mut beacon = serde_json::to_value(beacon).unwrap();
+            let signature = hex::decode(beacon["signature"].as_str().unwrap()).unwrap();
+            beacon["signature"] = hex::encode(&signature[..len]).into();
+            serde_json::from_value(beacon).unwrap()
+        };
+        #[cfg_attr(not(feature = "bn254"), allow(unused_mut))]
+        let mut truncated = vec![
+            (truncate(chained_beacon(), 48), chained_chain_info()),
+            (truncate(chained_beacon(), 64), chained_chain_info()),
+            (truncate(unchained_beacon(), 48), unchained_chain_info()),
+            (truncate(unchained_beacon(), 64), unchained_chain_info()),
+        ];
+        #[cfg(feature = "bn254")]
+        {
+            let (info, beacon) = signed_bn254_beacon(1);
+            truncated.push((truncate(beacon, 48), info));
+        }
+        for (beacon, info) in truncated {
+            let got = beacon.signature().len();
+            let expected = info.scheme().signature_len().unwrap();
+            assert_invalid_length(beacon.verify(info), "signature", expected, got);
+        }
+
+        // G1 signatures are the shortest, truncating them gives a length which does not parse at all
+        for beacon in [unchained_beacon_on_g1(), unchained_beacon_on_g1_rfc()] {
+            let mut beacon = serde_json::to_value(beacon).unwrap();
+            beacon["signature"] = hex::encode([1u8; 47]).into();
+            assert!(serde_json::from_value::<ApiBeacon>(beacon).is_err());
+        }
+    }
+
+    #[test]
+    fn chained_beacon_genesis_seed_works() {
+        let with_previous = |beacon: ApiBeacon, previous_signature: &[u8]| -> ApiBeacon {
+            let mut beacon = serde_json::to_value(beacon).unwrap();
+            beacon["previous_signature"] = hex::encode(previous_signature).into();
+            serde_json::from_value(beacon).unwrap()
+        };
+        let assert_parsing = |beacon: ApiBeacon| match beacon.message() {
+            Ok(_) => panic!("previous signature length should be rejected"),
+            Err(DrandError::Beacon(e)) => assert!(matches!(*e, BeaconError::Parsing)),
+            Err(err) => panic!("previous signature should fail to parse, got {}", err),
+        };
+
+        // round 1 signs over the genesis seed, whatever its length
+        for len in [32, 48, MAX_GENESIS_SEED_LEN] {
+            let seed = vec![0x17; len];
+            let beacon = with_previous(chained_beacon_1(), &seed);
+            assert_eq!(beacon.message().unwrap(), chained_message(&seed, 1));
+            // the signature is on the published seed
+            assert!(!beacon.verify(chained_chain_info()).unwrap());
+        }
+        assert!(chained_beacon_1().verify(chained_chain_info()).unwrap());
+
+        // empty and oversized seeds are rejected, as are truncated signatures of later rounds
+        assert_parsing(with_previous(chained_beacon_1(), &[]));
+        assert_parsing(with_previous(
+            chained_beacon_1(),
+            &[0x17; MAX_GENESIS_SEED_LEN + 1],
+        ));
+        let previous_signature = chained_beacon().previous_signature().unwrap();
+        assert_parsing(with_previous(chained_beacon(), &previous_signature[..95]));
+        assert_parsing(with_previous(chained_beacon(), &previous_signature[..32]));
+        match with_previous(chained_beacon(), &previous_signature[..48])
+            .verify(chained_chain_info())
+        {
+            Err(DrandError::Beacon(e)) => assert!(matches!(*e, BeaconError::Parsing)),
+            _ => panic!("truncated previous signature should fail to parse"),
+        }
+    }
+
+    #[test]
+    fn randomness_beacon_verification_unknown_scheme_works() {
+        let mut info = serde_json::to_value(unchained_chain_on_g1_info()).unwrap();
+        info["schemeID"] = "bls-bls24-unchained-on-g1".into();
+        let info: ChainInfo = serde_json::from_value(info).unwrap();
+
+        match unchained_beacon_on_g1().verify(info) {
+            Ok(_) => panic!("Beacon should not be verified on an unknown scheme"),
+            Err(DrandError::Beacon(e)) => assert!(
+                matches!(*e, BeaconError::UnknownScheme { ref scheme } if scheme == "bls-bls24-unchained-on-g1")
+            ),
+            Err(err) => panic!(
+                "Beacon should not be verified on an unknown scheme, got {}",
+                err
+            ),
+        }
+    }
+
+    #[test]
+    fn randomness_beacon_custom_scheme_works() {
+        use ark_bls12_381::{g1, Fr, G2Projective};
+        use ark_ec::{
+            hashing::{
+                curve_maps::wb::WBMap, map_to_curve_hasher::MapToCurveBasedHasher, HashToCurve,
+            },
+            models::short_weierstrass,
+            CurveGroup, Group,
+        };
+        use ark_ff::field_hashers::DefaultFieldHasher;
+        use ark_serialize::CanonicalSerialize;
+
+        use crate::chain::{SchemeDescriptor, SchemeRegistry, SignatureGroup};
+
+        const DST: &[u8] = b"BLS_SIG_CUSTOM12381G1_XMD:SHA-256_SSWU_RO_NUL_";
+        fn randomness(signature: &[u8]) -> [u8; 32] {
+            Sha256::digest(Sha256::digest(signature)).into()
+        }
+
+        // chain and beacon of a fork signing on G1 with its own DST, and hashing signatures twice for randomness
+        let secret_key = Fr::from(0xd4a2du64);
+        let mut public_key = vec![];
+        (G2Projective::generator() * secret_key)
+            .into_affine()
+            .serialize_compressed(&mut public_key)
+            .unwrap();
+        let mut info = serde_json::to_value(unchained_chain_on_g1_rfc_info()).unwrap();
+        info["public_key"] = hex::encode(public_key).into();
+        info["schemeID"] = "custom-unchained-on-g1".into();
+        let info: ChainInfo = serde_json::from_value(info).unwrap();
+
+        let round = 1000;
+        let hash = MapToCurveBasedHasher::<
+            short_weierstrass::Projective<g1::Config>,
+            DefaultFieldHasher<Sha256, 128>,
+            WBMap<g1::Config>,
+        >::new(DST)
+        .unwrap()
+        .hash(&unchained_message(round))
+        .unwrap();
+        let mut signature = vec![];
+        (hash * secret_key)
+            .into_affine()
+            .serialize_compressed(&mut signature)
+            .unwrap();
+        let beacon: ApiBeacon = UnchainedBeacon {
+            round,
+            randomness: randomness(&signature),
+            signature: Signature::try_from(signature.as_slice()).unwrap(),
+        }
+        .into();
+
+        // unknown schemes are not guessed
+        match info.prepare() {
+            Err(DrandError::Beacon(e)) => assert!(matches!(*e, BeaconError::UnknownScheme { .. })),
+            _ => panic!("custom scheme should not be prepared without a descriptor"),
+        }
+
+        let registry = SchemeRegistry::new().with_scheme(
+            SchemeDescriptor::new("custom-unchained-on-g1", SignatureGroup::G1, DST)
+                .with_randomness_fn(randomness),
+        );
+        let prepared = info.prepare_with_registry(&registry).unwrap();
+        assert_eq!(prepared.scheme().dst(), DST);
+        assert_eq!(
+            beacon.verify_detailed(&prepared).unwrap(),
+            VerificationOutcome::Ok
+        );
+
+        // signature is for another round
+        let ApiBeacon::UnchainedBeacon(mut other_round) = beacon.clone() else {
+            panic!("custom beacon should be unchained")
+        };
+        other_round.round += 1;
+        assert_eq!(
+            ApiBeacon::from(other_round)
+                .verify_detailed(&prepared)
+                .unwrap(),
+            VerificationOutcome::SignatureInvalid
+        );
+
+        // built-in schemes on the same group use another DST
+        let mut info = serde_json::to_value(info).unwrap();
+        info["schemeID"] = "bls-unchained-g1-rfc9380".into();
+        let info: ChainInfo = serde_json::from_value(info).unwrap();
+        assert_eq!(
+            beacon
+                .verify_detailed(&info.prepare_with_registry(&registry).unwrap())
+                .unwrap(),
+            VerificationOutcome::SignatureInvalid
+        );
+    }
+
+    /// drand mainnet round 999999, as referenced by round 1000000.
+    /// Its own previous signature is not known, so it links to round 1000000 but does not verify.
+    fn chained_beacon_999999() -> RandomnessBeacon {
+        let signature = chained_beacon().previous_signature().unwrap();
+        let beacon = ChainedBeacon {
+            round: 999999,
+            randomness: Sha256::digest(&signature).into(),
+            signature: Signature::try_from(signature.as_slice()).unwrap(),
+            previous_signature: vec![0; 96],
+        };
+        RandomnessBeacon::new(beacon.into(), 0)
+    }
+
+    /// Chained beacons for rounds 1 to `rounds`, signed with a test key, alongside chain info to verify them.
+    pub(crate) fn signed_chain(rounds: u64) -> (ChainInfo, Vec<RandomnessBeacon>) {
+        use ark_bls12_381::{g2, Fr, G1Projective};
+        use ark_ec::{
+            hashing::{
+                curve_maps::wb::WBMap, map_to_curve_hasher::MapToCurveBasedHasher, HashToCurve,
+            },
+            models::short_weierstrass,
+            CurveGroup, Group,
+        };
+        use ark_ff::field_hashers::DefaultFieldHasher;
+        use ark_serialize::CanonicalSerialize;
+
+        let secret_key = Fr::from(0xd4a2du64);
+        let mut public_key = vec![];
+        (G1Projective::generator() * secret_key)
+            .into_affine()
+            .serialize_compressed(&mut public_key)
+            .unwrap();
+        let chained = chained_chain_info();
+        let info = ChainInfo::new(
+            public_key,
+            chained.period(),
+            chained.genesis_time(),
+            chained.hash(),
+            chained.group_hash(),
+            chained.scheme_id(),
+            chained.metadata(),
+        )
+        .unwrap();
+
+        let mapper = MapToCurveBasedHasher::<
+            short_weierstrass::Projective<g2::Config>,
+            DefaultFieldHasher<Sha256, 128>,
+            WBMap<g2::Config>,
+        >::new(crate::bls_signatures::G2_DOMAIN)
+        .unwrap();
+        let mut previous_signature = info.group_hash();
+        let beacons = (1..=rounds)
+            .map(|round| {
+                let mut beacon = ChainedBeacon {
+                    round,
+                    randomness: [0; 32],
+                    signature: Signature::G2([0; 96]),
+                    previous_signature: previous_signature.clone(),
+                };
+                let hash = mapper.hash(&beacon.message().unwrap()).unwrap();
+                let mut signature = vec![];
+                (hash * secret_key)
+                    .into_affine()
+                    .serialize_compressed(&mut signature)
+                    .unwrap();
+                beacon.randomness = Sha256::digest(&signature).into();
+                beacon.signature = Signature::try_from(signature.as_slice()).unwrap();
+                previous_signature = signature;
+                RandomnessBeacon::new(beacon.into(), 0)
+            })
+            .collect();
+        (info, beacons)
+    }
+
+    /// Unchained beacon on BN254 for `round`, signed with a test key, alongside evmnet-like chain info to verify it.
+    #[cfg(feature = "bn254")]
+    fn signed_bn254_beacon(round: u64) -> (ChainInfo, ApiBeacon) {
+        use ark_bn254::{Fr, G2Projective};
+        use ark_ec::{CurveGroup, Group};
+
+        use crate::bls_signatures::{bn254, BN254_G1_DOMAIN};
+
+        let secret_key = Fr::from(0xd4a2du64);
+        let public_key = (G2Projective::generator() * secret_key).into_affine();
+        let rfc = unchained_chain_on_g1_rfc_info();
+        let info = ChainInfo::new(
+            bn254::g2_to_uncompressed(&public_key),
+            rfc.period(),
+            rfc.genesis_time(),
+            rfc.hash(),
+            rfc.group_hash(),
+            "bls-bn254-unchained-on-g1".to_owned(),
+            rfc.metadata(),
+        )
+        .unwrap();
+
+        let message = sha3::Keccak256::digest(round.to_be_bytes());
+        let hash = bn254::hash_to_g1(BN254_G1_DOMAIN, &message).unwrap();
+        let signature = bn254::g1_to_uncompressed(&(hash * secret_key).into_affine());
+        let beacon = UnchainedBeacon {
+            round,
+            randomness: Sha256::digest(&signature).into(),
+            signature: Signature::try_from(signature.as_slice()).unwrap(),
+        };
+        (info, beacon.into())
+    }
+
+    #[cfg(feature = "bn254")]
+    #[test]
+    fn randomness_beacon_bn254_verification_works() {
+        let (info, beacon) = signed_bn254_beacon(1000000);
+        assert_eq!(info.scheme(), Scheme::BlsBn254UnchainedOnG1);
+        match beacon.verify(info.clone()) {
+            Ok(ok) => assert!(ok),
+            Err(err) => panic!("BN254 beacon should validate on BN254 info, got {}", err),
+        }
+
+        // signature is for another round
+        let ApiBeacon::UnchainedBeacon(mut other_round) = beacon.clone() else {
+            panic!("BN254 beacon should be unchained")
+        };
+        other_round.round += 1;
+        match ApiBeacon::from(other_round).verify(info.clone()) {
+            Ok(ok) => assert!(!ok, "BN254 beacon should not validate for another round"),
+            Err(err) => panic!(
+                "BN254 beacon should not validate for another round without returning an error, got {}",
+                err
+            ),
+        }
+
+        // curves are not mixed
+        assert!(
+            !matches!(beacon.verify(unchained_chain_on_g1_rfc_info()), Ok(true)),
+            "BN254 beacon should not validate on BLS12-381 G1 info"
+        );
+        assert!(
+            !matches!(beacon.verify(unchained_chain_on_g1_info()), Ok(true)),
+            "BN254 beacon should not validate on BLS12-381 G1 (non Hash to curve RFC compliant) info"
+        );
+        assert!(
+            !matches!(unchained_beacon_on_g1_rfc().verify(info.clone()), Ok(true)),
+            "BLS12-381 G1 beacon should not validate on BN254 info"
+        );
+        assert!(
+            !matches!(unchained_beacon().verify(info.clone()), Ok(true)),
+            "BLS12-381 G2 beacon should not validate on BN254 info"
+        );
+        match chained_beacon().verify(info) {
+            Ok(ok) => assert!(!ok, "Chained beacon should not validate on BN254 info"),
+            Err(err) => panic!(
+                "Chained beacon should not validate on BN254 info without returning an error, got {}",
+                err
+            ),
+        }
+    }
+
+    #[test]
+    fn randomness_beacon_verify_previous_works() {
+        let beacon = RandomnessBeacon::new(chained_beacon(), 0);
+        let previous = chained_beacon_999999();
+        assert!(beacon.verify_previous(&previous).is_ok());
+
+        // rounds are not consecutive
+        let first = RandomnessBeacon::new(chained_beacon_1(), 0);
+        match beacon.verify_previous(&first) {
+            Ok(()) => panic!("round 1 should not precede round 1000000"),
+            Err(DrandError::Beacon(e)) => {
+                assert!(matches!(*e, BeaconError::ChainLinkage { round: 1000000 }))
+            }
+            Err(err) => panic!("round 1 should not precede round 1000000, got {}", err),
+        }
+        assert!(previous.verify_previous(&beacon).is_err());
+
+        // unchained beacons are not linked
+        let unchained = RandomnessBeacon::new(unchained_beacon(), 0);
+        for (beacon, previous) in [(&unchained, &previous), (&beacon, &unchained)] {
+            match beacon.verify_previous(previous) {
+                Ok(()) => panic!("unchained beacons should not be linked"),
+                Err(DrandError::Beacon(e)) => assert!(matches!(*e, BeaconError::Unchained)),
+                Err(err) => panic!("unchained beacons should not be linked, got {}", err),
+            }
+        }
+    }
+
+    #[test]
+    fn verify_chain_works() {
+        let (info, beacons) = signed_chain(4);
+        assert!(verify_chain(&info, &beacons).is_ok());
+        assert!(verify_chain(&info, &beacons[1..]).is_ok());
+        assert!(verify_chain(&info, &[]).is_ok());
+
+        // a missing round breaks the chain
+        let gap = [beacons[0].clone(), beacons[2].clone()];
+        match verify_chain(&info, &gap) {
+            Ok(()) => panic!("chain with a gap should not verify"),
+            Err(DrandError::Beacon(e)) => {
+                assert!(matches!(*e, BeaconError::ChainLinkage { round: 3 }))
+            }
+            Err(err) => panic!("chain with a gap should not verify, got {}", err),
+        }
+
+        // every beacon is verified
+        let linked = [
+            chained_beacon_999999(),
+            RandomnessBeacon::new(chained_beacon(), 0),
+        ];
+        match verify_chain(&chained_chain_info(), &linked) {
+            Ok(()) => panic!("chain with an invalid signature should not verify"),
+            Err(DrandError::Beacon(e)) => assert!(matches!(
+                *e,
+                BeaconError::Validation {
+                    reason: VerificationOutcome::SignatureInvalid
+                }
+            )),
+            Err(err) => panic!(
+                "chain with an invalid signature should not verify, got {}",
+                err
+            ),
+        }
+
+        // unchained beacons cannot form a chain
+        let unchained = [RandomnessBeacon::new(unchained_beacon(), 0)];
+        match verify_chain(&unchained_chain_info(), &unchained) {
+            Ok(()) => panic!("unchained beacons should not verify as a chain"),
+            Err(DrandError::Beacon(e)) => assert!(matches!(*e, BeaconError::Unchained)),
+            Err(err) => panic!(
+                "unchained beacons should not verify as a chain, got {}",
+                err
+            ),
+        }
+    }
+
+    #[test]
+    fn verify_batch_works() {
+        let (info, beacons) = signed_chain(8);
+        let mut beacons: Vec<ApiBeacon> = beacons.into_iter().map(|b| b.beacon).collect();
+        assert!(verify_batch(&info, &beacons).unwrap());
+        assert!(verify_batch(&info, &[]).unwrap());
+
+        for (info, beacon) in [
+            (chained_chain_info(), chained_beacon()),
+            (unchained_chain_info(), unchained_beacon()),
+            (unchained_chain_on_g1_info(), unchained_beacon_on_g1()),
+            (
+                unchained_chain_on_g1_rfc_info(),
+                unchained_beacon_on_g1_rfc(),
+            ),
+        ] {
+            assert!(verify_batch(&info, &[beacon]).unwrap());
+        }
+
+        // beacon in the middle carries the signature of the next round
+        let ApiBeacon::ChainedBeacon(next) = beacons[4].clone() else {
+            panic!("signed beacons should be chained")
+        };
+        let ApiBeacon::ChainedBeacon(corrupted) = &mut beacons[3] else {
+            panic!("signed beacons should be chained")
+        };
+        corrupted.signature = next.signature;
+        corrupted.randomness = next.randomness;
+        match verify_batch(&info, &beacons) {
+            Ok(_) => panic!("batch with an invalid beacon should not verify"),
+            Err(DrandError::Beacon(e)) => {
+                assert!(matches!(*e, BeaconError::InvalidBeacon { round: 4 }))
+            }
+            Err(err) => panic!(
+                "batch with an invalid beacon should not verify, got {}",
+                err
+            ),
+        }
+
+        // beacons from another chain
+        match verify_batch(
+            &chained_chain_info(),
+            &[chained_beacon(), unchained_beacon()],
+        ) {
+            Ok(_) => panic!("batch with an unchained beacon should not verify on chained info"),
+            Err(DrandError::Beacon(e)) => {
+                assert!(matches!(*e, BeaconError::InvalidBeacon { round: 1000000 }))
+            }
+            Err(err) => panic!(
+                "batch with an unchained beacon should not verify on chained info, got {}",
+                err
+            ),
+        }
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn randomness_beacon_time_before_genesis_fails() {
+        let chain: ChainTimeInfo = unchained_chain_info().into();
+        let genesis = OffsetDateTime::from_unix_timestamp(chain.genesis_time() as i64).unwrap();
+
+        let first = RandomnessBeaconTime::from_round(&chain, 1).unwrap();
+        assert_eq!(first.absolute(), genesis);
+        let second = RandomnessBeaconTime::from_round(&chain, 2).unwrap();
+        assert_eq!(
+            second.absolute(),
+            genesis + (chain.period() as i64).seconds()
+        );
+
+        let before_genesis = [
+            RandomnessBeaconTime::from_round(&chain, 0),
+            RandomnessBeaconTime::parse(&chain, "0"),
+            RandomnessBeaconTime::from_datetime(&chain, genesis - 1.seconds()),
+            RandomnessBeaconTime::parse(&chain, "2000-01-01T00:00:00Z"),
+            RandomnessBeaconTime::parse(&chain, "-100000d"),
+        ];
+        for time in before_genesis {
+            match time {
+                Ok(time) => panic!("round {} should be before genesis", time.round()),
+                Err(DrandError::Beacon(e)) => assert!(matches!(*e, BeaconError::BeforeGenesis)),
+                Err(err) => panic!("time should be before genesis, got {err}"),
+            }
+        }
+
+        // genesis itself is round 1
+        let time = RandomnessBeaconTime::from_datetime(&chain, genesis).unwrap();
+        assert_eq!(time.round(), 1);
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn randomness_beacon_time_out_of_range_fails() {
+        // genesis and period served by a hostile relay, beyond the years OffsetDateTime represents
+        let chain = ChainTimeInfo::new(u64::MAX - 1, u64::MAX / 2);
+        let times = [
+            RandomnessBeaconTime::from_round(&chain, 1),
+            RandomnessBeaconTime::from_round(&chain, 3),
+            RandomnessBeaconTime::from_round(&ChainTimeInfo::new(1 << 40, 3), 1),
+            // parses, but millions of years from now
+            RandomnessBeaconTime::parse(&unchained_chain_info().into(), "1000000000d"),
+        ];
+        for time in times {
+            match time {
+                Ok(time) => panic!("round {} should be out of range", time.round()),
+                Err(DrandError::Beacon(e)) => assert!(matches!(*e, BeaconError::TimeOutOfRange)),
+                Err(err) => panic!("time should be out of range, got {err}"),
+            }
+        }
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn randomness_beacon_time_negative_duration_works() {
+        let chain: ChainTimeInfo = unchained_chain_info().into();
+
+        let now = RandomnessBeaconTime::parse(&chain, "0s").unwrap();
+        let negative_zero = RandomnessBeaconTime::parse(&chain, "-0s").unwrap();
+        assert_eq!(negative_zero.round(), now.round());
+
+        // 10 minutes is a whole number of 3s periods, so past and future rounds are as far from the current one
+        let past = RandomnessBeaconTime::parse(&chain, "-10m").unwrap();
+        let now = RandomnessBeaconTime::parse(&chain, "0s").unwrap();
+        let future = RandomnessBeaconTime::parse(&chain, "10m").unwrap();
+        assert!(past.relative().is_negative());
+        assert!(past.round() < now.round() && now.round() < future.round());
+        assert_eq!(now.round() - past.round(), 10 * 60 / chain.period());
+        assert_eq!(future.round() - now.round(), 10 * 60 / chain.period());
+
+        // looking back further than genesis has no round
+        match RandomnessBeaconTime::parse(&chain, "-100000d") {
+            Ok(time) => panic!("round {} should be before genesis", time.round()),
+            Err(DrandError::Beacon(e)) => assert!(matches!(*e, BeaconError::BeforeGenesis)),
+            Err(err) => panic!("time should be before genesis, got {err}"),
+        }
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn randomness_beacon_time_unix_time_works() {
+        let chain: ChainTimeInfo = chained_chain_info().into();
+        let genesis = chain.genesis_time();
+
+        let time = RandomnessBeaconTime::parse(&chain, &format!("@{genesis}")).unwrap();
+        assert_eq!(time.round(), 1);
+        assert_eq!(time.absolute().unix_timestamp() as u64, genesis);
+        let time = RandomnessBeaconTime::parse(&chain, &format!("@{}", genesis + 30)).unwrap();
+        assert_eq!(time.round(), 2);
+        let time = RandomnessBeaconTime::parse(&chain, &format!("@{}", genesis + 59)).unwrap();
+        assert_eq!(time.round(), 2);
+
+        // plain integers remain rounds, even when they look like timestamps
+        let time = RandomnessBeaconTime::parse(&chain, "1700000000").unwrap();
+        assert_eq!(time.round(), 1700000000);
+        let time = RandomnessBeaconTime::parse(&chain, "@1700000000").unwrap();
+        assert_eq!(time.round(), (1700000000 - genesis) / chain.period() + 1);
+
+        match RandomnessBeaconTime::parse(&chain, &format!("@{}", genesis - 1)) {
+            Ok(time) => panic!("round {} should be before genesis", time.round()),
+            Err(DrandError::Beacon(e)) => assert!(matches!(*e, BeaconError::BeforeGenesis)),
+            Err(err) => panic!("time should be before genesis, got {err}"),
+        }
+        for invalid in [
+            "@",
+            "@-1",
+            "@1.5",
+            "@ 1700000000",
+            "@@1700000000",
+            "1700000000@",
+        ] {
+            assert!(
+                RandomnessBeaconTime::parse(&chain, invalid).is_err(),
+                "{invalid:?} should not parse"
+            );
+        }
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn parse_duration_works() {
+        let valid = [
+            ("30s", 30.seconds()),
+            ("5m", 5.minutes()),
+            ("2h", 2.hours()),
+            ("1d", 1.days()),
+            ("250ms", 250.milliseconds()),
+            ("90min", 90.minutes()),
+            ("-10m", (-10).minutes()),
+            ("1h30m", 90.minutes()),
+            ("2d12h", 60.hours()),
+            ("1m 30s", 90.seconds()),
+            (" 1 h 30 m ", 90.minutes()),
+            ("1s500ms", 1500.milliseconds()),
+            ("0s", Duration::ZERO),
+            ("0h0m", Duration::ZERO),
+        ];
+        for (input, expected) in valid {
+            match RandomnessBeaconTime::parse_duration(input) {
+                Ok(duration) => assert_eq!(duration, expected, "{input:?}"),
+                Err(err) => panic!("{input:?} should parse, got {err}"),
+            }
+        }
+
+        let invalid = [
+            "",
+            " ",
+            "-",
+            "s",
+            "30",
+            "30x",
+            "1.5h",
+            "h30",
+            "1h-30m",
+            "1hh",
+            "30sé",
+            "é30s",
+            "2023-06-28",
+            "9223372036854775807d",
+        ];
+        for input in invalid {
+            match RandomnessBeaconTime::parse_duration(input) {
+                Ok(duration) => panic!("{input:?} should not parse, got {duration}"),
+                Err(DrandError::Beacon(e)) => {
+                    assert!(matches!(*e, BeaconError::DurationParse), "{input:?}")
+                }
+                Err(err) => panic!("{input:?} should fail with a duration error, got {err}"),
+            }
+        }
+    }
+
+    #[cfg(feature = "time")]
     #[test]
     fn randomness_beacon_time_success_works() {
         const FIRST_ROUND: u64 = 1;
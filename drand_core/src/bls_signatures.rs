@@ -1,44 +1,381 @@
 /// Verify BLS Signatures used in drand
 /// inspired from https://github.com/noislabs/drand-verify/blob/1017235f6bcfcc9fb433926c0dc1b9a013bd4df3/src/verify.rs#L58
-use std::ops::Neg;
+use alloc::{borrow::ToOwned, boxed::Box, string::String, sync::Arc, vec::Vec};
+use core::{fmt, ops::Neg};
 
-use ark_bls12_381::{g1, g2, G1Affine, G2Affine};
+use ark_bls12_381::{g1, g2, Bls12_381, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
 use ark_ec::{
     bls12::Bls12,
     hashing::{curve_maps::wb::WBMap, map_to_curve_hasher::MapToCurveBasedHasher, HashToCurve},
     models::short_weierstrass,
-    pairing::Pairing,
-    AffineRepr,
+    pairing::{Pairing, PairingOutput},
+    AffineRepr, CurveGroup,
 };
-use ark_ff::{field_hashers::DefaultFieldHasher, Zero};
-use ark_serialize::CanonicalDeserialize;
-use thiserror::Error;
+use ark_ff::{field_hashers::DefaultFieldHasher, PrimeField, Zero};
+use ark_serialize::{CanonicalDeserialize, SerializationError};
+use sha2::Digest;
 
-use crate::{DrandError, Result};
+use crate::{chain::Scheme, DrandError, Result};
 
+/// Domain separation tag of BLS signatures on BLS12-381 G1, hashed as per RFC 9380 with SHA-256, and the simplified SWU map.
+/// Tag format follows the BLS signatures draft, section 4.2.1 of draft-irtf-cfrg-bls-signature-05.
+/// Used by `bls-unchained-g1-rfc9380`.
 pub const G1_DOMAIN: &[u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_NUL_";
+/// Domain separation tag of BLS signatures on BLS12-381 G2, hashed the same way on G2.
+/// Used by `pedersen-bls-chained` and `pedersen-bls-unchained`, and by `bls-unchained-on-g1` even though its signatures are on G1.
 pub const G2_DOMAIN: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+/// Domain separation tag of BLS signatures on BN254 G1, hashed as per RFC 9380 with Keccak-256, and the Shallue-van de Woestijne map.
+/// Used by `bls-bn254-unchained-on-g1`, as defined by drand for its evmnet network.
+pub const BN254_G1_DOMAIN: &[u8] = b"BLS_SIG_BN254G1_XMD:KECCAK-256_SVDW_RO_NUL_";
 
-#[derive(Error, Debug)]
-pub enum VerificationError {
-    #[error("deserialization failed")]
+/// Domain separation tag signatures of `scheme` are hashed to the curve with, or `None` for unknown schemes.
+/// `bls-unchained-on-g1` is the only scheme whose tag does not name the group of its signatures: they moved to G1 before the tag was updated.
+pub fn dst_for_scheme(scheme: &Scheme) -> Option<&'static [u8]> {
+    match scheme {
+        Scheme::PedersenBlsChained | Scheme::PedersenBlsUnchained | Scheme::BlsUnchainedOnG1 => {
+            Some(G2_DOMAIN)
+        }
+        Scheme::BlsUnchainedG1Rfc9380 => Some(G1_DOMAIN),
+        Scheme::BlsBn254UnchainedOnG1 => Some(BN254_G1_DOMAIN),
+        Scheme::Unknown(_) => None,
+    }
+}
+
+/// Errors raised while decoding curve points, and computing pairings.
+#[derive(Debug)]
+pub enum BlsError {
+    /// Encoded point does not have the length of its group.
+    WrongLength {
+        expected: usize,
+        got: usize,
+    },
+    /// Encoding is malformed, such as invalid flags or a coordinate outside of the base field.
     Deserialization,
-    #[error("cannot initialise mapper for sha2 to BLS12-381 {curve}")]
-    Initialisation { curve: String },
-    #[error("invalid point")]
-    InvalidPoint,
+    Initialisation {
+        curve: String,
+    },
+    /// Coordinates do not satisfy the curve equation.
+    NotOnCurve,
+    /// Point is on the curve, but outside of the prime order subgroup.
+    NotInSubgroup,
+    /// Point is the identity, with which the pairing equality holds for crafted signatures.
+    Identity,
+    /// Final exponentiation failed, which only happens on degenerate Miller loop outputs.
+    PairingFailure,
+}
+
+impl fmt::Display for BlsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongLength { expected, got } => {
+                write!(
+                    f,
+                    "invalid point length: expected {expected} bytes, got {got}"
+                )
+            }
+            Self::Deserialization => write!(f, "deserialization failed"),
+            Self::Initialisation { curve } => {
+                write!(f, "cannot initialise mapper for sha2 to BLS12-381 {curve}")
+            }
+            Self::NotOnCurve => write!(f, "point is not on the curve"),
+            Self::NotInSubgroup => write!(f, "point is not in the prime order subgroup"),
+            Self::Identity => write!(f, "point is the identity"),
+            Self::PairingFailure => write!(f, "pairing computation failed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BlsError {}
+
+/// Public key deserialized once, including its subgroup check, to verify many signatures.
+#[derive(Clone, Debug)]
+pub enum PublicKey {
+    /// BLS12-381 G1 point, for signatures on G2.
+    G1(G1Affine),
+    /// BLS12-381 G2 point, for signatures on G1.
+    G2(G2Affine),
+    /// BN254 G2 point, for signatures on G1.
+    #[cfg(feature = "bn254")]
+    Bn254G2(ark_bn254::G2Affine),
+}
+
+impl PublicKey {
+    pub fn g1(data: &[u8]) -> Result<Self> {
+        g1_from_variable(data).map(Self::G1)
+    }
+
+    pub fn g2(data: &[u8]) -> Result<Self> {
+        g2_from_variable(data).map(Self::G2)
+    }
+
+    #[cfg(feature = "bn254")]
+    pub fn bn254_g2(data: &[u8]) -> Result<Self> {
+        bn254::g2_from_uncompressed(data).map(Self::Bn254G2)
+    }
+}
+
+/// Public key with the fixed inputs of the pairing check precomputed, to verify many signatures of a chain.
+/// G2 points carry their Miller loop lines, which [`verify`] computes again for each signature.
+#[derive(Clone, Debug)]
+pub struct PreparedVerifier {
+    dst: Vec<u8>,
+    /// Encoded public key, binding the scalars of batch verification.
+    public_key: Vec<u8>,
+    pairing: PreparedPairing,
+}
+
+#[derive(Clone, Debug)]
+enum PreparedPairing {
+    /// Signatures on G2, checked with `e(-g1, signature) * e(pk, hash) == 1`.
+    G2 {
+        minus_g1: G1Affine,
+        public_key: G1Affine,
+    },
+    /// Signatures on G1, checked with `e(-signature, g2) * e(hash, pk) == 1`.
+    G1 {
+        g2: <Bls12_381 as Pairing>::G2Prepared,
+        public_key: <Bls12_381 as Pairing>::G2Prepared,
+    },
+    /// Signatures on BN254 G1, checked as they are on BLS12-381 G1.
+    #[cfg(feature = "bn254")]
+    Bn254G1 {
+        g2: <ark_bn254::Bn254 as Pairing>::G2Prepared,
+        public_key: <ark_bn254::Bn254 as Pairing>::G2Prepared,
+    },
+}
+
+impl PreparedVerifier {
+    /// Verifier for `public_key`, decoded from `encoded`, and signatures hashed to the curve with `dst`.
+    pub fn new(dst: &[u8], public_key: &PublicKey, encoded: &[u8]) -> Self {
+        let pairing = match public_key {
+            PublicKey::G1(public_key) => PreparedPairing::G2 {
+                minus_g1: G1Affine::generator().neg(),
+                public_key: *public_key,
+            },
+            PublicKey::G2(public_key) => PreparedPairing::G1 {
+                g2: G2Affine::generator().into(),
+                public_key: (*public_key).into(),
+            },
+            #[cfg(feature = "bn254")]
+            PublicKey::Bn254G2(public_key) => PreparedPairing::Bn254G1 {
+                g2: ark_bn254::G2Affine::generator().into(),
+                public_key: (*public_key).into(),
+            },
+        };
+        Self {
+            dst: dst.to_vec(),
+            public_key: encoded.to_vec(),
+            pairing,
+        }
+    }
+
+    /// Check that signature is the actual aggregate of message and public key, as [`verify`] does.
+    pub fn verify(&self, signature: &[u8], hash: &[u8]) -> Result<bool> {
+        match &self.pairing {
+            PreparedPairing::G2 {
+                minus_g1,
+                public_key,
+            } => {
+                let hash_on_curve = g2_hasher(&self.dst)?
+                    .hash(hash)
+                    .map_err(|_| initialisation_error("G2"))?;
+                let sigma = g2_from_variable(signature)?;
+                pairing_product_is_identity::<Bls12_381>(
+                    [(*minus_g1).into(), (*public_key).into()],
+                    [sigma.into(), hash_on_curve.into()],
+                )
+            }
+            PreparedPairing::G1 { g2, public_key } => {
+                let hash_on_curve = g1_hasher(&self.dst)?
+                    .hash(hash)
+                    .map_err(|_| initialisation_error("G1"))?;
+                let sigma = g1_from_variable(signature)?;
+                pairing_product_is_identity::<Bls12_381>(
+                    [sigma.neg().into(), hash_on_curve.into()],
+                    [g2.clone(), public_key.clone()],
+                )
+            }
+            #[cfg(feature = "bn254")]
+            PreparedPairing::Bn254G1 { g2, public_key } => {
+                let hash_on_curve = bn254::hash_to_g1(&self.dst, hash)?;
+                let sigma = bn254::g1_from_uncompressed(signature)?;
+                pairing_product_is_identity::<ark_bn254::Bn254>(
+                    [sigma.neg().into(), hash_on_curve.into()],
+                    [g2.clone(), public_key.clone()],
+                )
+            }
+        }
+    }
+
+    /// Check that each signature is the actual aggregate of its message and the public key, with a single pairing check.
+    /// Signatures and hashes are combined with scalars derived from all inputs, so that invalid signatures cannot cancel each other out.
+    /// BN254 signatures are verified one by one.
+    pub fn verify_batch(&self, signatures: &[Vec<u8>], hashes: &[Vec<u8>]) -> Result<bool> {
+        if signatures.len() != hashes.len() {
+            return Ok(false);
+        }
+        if signatures.is_empty() {
+            return Ok(true);
+        }
+
+        let scalars = batch_scalars(&self.dst, signatures, hashes, &self.public_key);
+        match &self.pairing {
+            PreparedPairing::G2 {
+                minus_g1,
+                public_key,
+            } => {
+                let mapper = g2_hasher(&self.dst)?;
+                let mut sigma = G2Projective::zero();
+                let mut hash_on_curve = G2Projective::zero();
+                for ((signature, hash), scalar) in signatures.iter().zip(hashes).zip(&scalars) {
+                    sigma += g2_from_variable(signature)? * scalar;
+                    hash_on_curve +=
+                        mapper.hash(hash).map_err(|_| initialisation_error("G2"))? * scalar;
+                }
+                pairing_product_is_identity::<Bls12_381>(
+                    [(*minus_g1).into(), (*public_key).into()],
+                    [
+                        sigma.into_affine().into(),
+                        hash_on_curve.into_affine().into(),
+                    ],
+                )
+            }
+            PreparedPairing::G1 { g2, public_key } => {
+                let mapper = g1_hasher(&self.dst)?;
+                let mut sigma = G1Projective::zero();
+                let mut hash_on_curve = G1Projective::zero();
+                for ((signature, hash), scalar) in signatures.iter().zip(hashes).zip(&scalars) {
+                    sigma += g1_from_variable(signature)? * scalar;
+                    hash_on_curve +=
+                        mapper.hash(hash).map_err(|_| initialisation_error("G1"))? * scalar;
+                }
+                pairing_product_is_identity::<Bls12_381>(
+                    [
+                        sigma.into_affine().neg().into(),
+                        hash_on_curve.into_affine().into(),
+                    ],
+                    [g2.clone(), public_key.clone()],
+                )
+            }
+            #[cfg(feature = "bn254")]
+            PreparedPairing::Bn254G1 { .. } => {
+                for (signature, hash) in signatures.iter().zip(hashes) {
+                    if !self.verify(signature, hash)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+        }
+    }
+}
+
+type G1Hasher = MapToCurveBasedHasher<
+    short_weierstrass::Projective<g1::Config>,
+    DefaultFieldHasher<sha2::Sha256, 128>,
+    WBMap<g1::Config>,
+>;
+
+type G2Hasher = MapToCurveBasedHasher<
+    short_weierstrass::Projective<g2::Config>,
+    DefaultFieldHasher<sha2::Sha256, 128>,
+    WBMap<g2::Config>,
+>;
+
+fn initialisation_error(curve: &str) -> DrandError {
+    Box::new(BlsError::Initialisation {
+        curve: curve.to_owned(),
+    })
+    .into()
+}
+
+/// Number of domain separation tags hashers are kept for, per curve. drand schemes use at most two per curve.
+#[cfg(feature = "std")]
+const HASHER_CACHE_CAPACITY: usize = 8;
+
+/// Hashers built for the last domain separation tags used, as building one validates the parameters of the curve maps.
+/// Without the `std` feature, hashers are built on every use.
+struct HasherCache<H> {
+    #[cfg(feature = "std")]
+    hashers: std::sync::Mutex<Vec<(Vec<u8>, Arc<H>)>>,
+    #[cfg(not(feature = "std"))]
+    hashers: core::marker::PhantomData<fn() -> H>,
+}
+
+impl<H> HasherCache<H> {
+    const fn new() -> Self {
+        Self {
+            #[cfg(feature = "std")]
+            hashers: std::sync::Mutex::new(Vec::new()),
+            #[cfg(not(feature = "std"))]
+            hashers: core::marker::PhantomData,
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn get_or_try_init(&self, dst: &[u8], init: impl FnOnce() -> Result<H>) -> Result<Arc<H>> {
+        // hashers are not modified once cached, so a poisoned lock is still consistent
+        let mut hashers = self
+            .hashers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some((_, hasher)) = hashers.iter().find(|(cached, _)| cached == dst) {
+            return Ok(hasher.clone());
+        }
+        let hasher = Arc::new(init()?);
+        if hashers.len() == HASHER_CACHE_CAPACITY {
+            hashers.remove(0);
+        }
+        hashers.push((dst.to_vec(), hasher.clone()));
+        Ok(hasher)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn get_or_try_init(&self, _dst: &[u8], init: impl FnOnce() -> Result<H>) -> Result<Arc<H>> {
+        init().map(Arc::new)
+    }
+}
+
+fn g1_hasher(dst: &[u8]) -> Result<Arc<G1Hasher>> {
+    static HASHERS: HasherCache<G1Hasher> = HasherCache::new();
+    HASHERS.get_or_try_init(dst, || {
+        G1Hasher::new(dst).map_err(|_| initialisation_error("G1"))
+    })
+}
+
+fn g2_hasher(dst: &[u8]) -> Result<Arc<G2Hasher>> {
+    static HASHERS: HasherCache<G2Hasher> = HasherCache::new();
+    HASHERS.get_or_try_init(dst, || {
+        G2Hasher::new(dst).map_err(|_| initialisation_error("G2"))
+    })
+}
+
+/// Hash `message` to G1 with `dst`, as signed by schemes with signatures on G1.
+#[cfg_attr(not(feature = "tlock"), allow(dead_code))]
+pub(crate) fn hash_to_g1(dst: &[u8], message: &[u8]) -> Result<G1Affine> {
+    g1_hasher(dst)?
+        .hash(message)
+        .map_err(|_| initialisation_error("G1"))
+}
+
+/// Hash `message` to G2 with `dst`, as signed by schemes with signatures on G2.
+#[cfg_attr(not(feature = "tlock"), allow(dead_code))]
+pub(crate) fn hash_to_g2(dst: &[u8], message: &[u8]) -> Result<G2Affine> {
+    g2_hasher(dst)?
+        .hash(message)
+        .map_err(|_| initialisation_error("G2"))
 }
 
 /// Check that signature is the actual aggregate of message and public key.
-/// Calculated by `e(g2, signature) == e(pk, hash)`.
-/// `signature` and `hash` are on G2, `public_key` is on G1.
-pub fn verify(dst: &[u8], signature: &[u8], hash: &[u8], public_key: &[u8]) -> Result<bool> {
-    // 48 is bytes of G1
-    // G1Affine::identity().to_compressed().len()
-    if signature.len() == 48 {
-        verify_g1_on_g2(dst, signature, hash, public_key)
-    } else {
-        verify_g2_on_g1(dst, signature, hash, public_key)
+/// The group of `signature` and `hash` is the other one than `public_key`.
+/// Beacons are verified with [`PreparedVerifier`], this is the reference it is tested against.
+pub fn verify(dst: &[u8], signature: &[u8], hash: &[u8], public_key: &PublicKey) -> Result<bool> {
+    match public_key {
+        PublicKey::G1(public_key) => verify_g2_on_g1(dst, signature, hash, public_key),
+        PublicKey::G2(public_key) => verify_g1_on_g2(dst, signature, hash, public_key),
+        #[cfg(feature = "bn254")]
+        PublicKey::Bn254G2(public_key) => verify_bn254_g1_on_g2(dst, signature, hash, public_key),
     }
 }
 
@@ -49,30 +386,16 @@ pub fn verify_g2_on_g1(
     dst: &[u8],
     signature: &[u8],
     hash: &[u8],
-    public_key: &[u8],
+    public_key: &G1Affine,
 ) -> Result<bool> {
-    let mapper = MapToCurveBasedHasher::<
-        short_weierstrass::Projective<g2::Config>,
-        DefaultFieldHasher<sha2::Sha256, 128>,
-        WBMap<g2::Config>,
-    >::new(dst)
-    .map_err(|_| -> DrandError {
-        Box::new(VerificationError::Initialisation {
-            curve: "G2".to_owned(),
-        })
-        .into()
-    })?;
-    let hash_on_curve = mapper.hash(hash).map_err(|_| -> DrandError {
-        Box::new(VerificationError::Initialisation {
-            curve: "G2".to_owned(),
-        })
-        .into()
-    })?;
+    let hash_on_curve = g2_hasher(dst)?
+        .hash(hash)
+        .map_err(|_| initialisation_error("G2"))?;
 
+    not_identity(public_key)?;
     let g1 = G1Affine::generator();
     let sigma = g2_from_variable(signature)?;
-    let r = g1_from_variable(public_key)?;
-    Ok(fast_pairing_equality(&g1, &sigma, &r, &hash_on_curve))
+    fast_pairing_equality(&g1, &sigma, public_key, &hash_on_curve)
 }
 
 /// Check that signature is the actual aggregate of message and public key.
@@ -82,30 +405,67 @@ pub fn verify_g1_on_g2(
     dst: &[u8],
     signature: &[u8],
     hash: &[u8],
-    public_key: &[u8],
+    public_key: &G2Affine,
 ) -> Result<bool> {
-    let mapper = MapToCurveBasedHasher::<
-        short_weierstrass::Projective<g1::Config>,
-        DefaultFieldHasher<sha2::Sha256, 128>,
-        WBMap<g1::Config>,
-    >::new(dst)
-    .map_err(|_| -> DrandError {
-        Box::new(VerificationError::Initialisation {
-            curve: "G1".to_owned(),
-        })
-        .into()
-    })?;
-    let hash_on_curve = mapper.hash(hash).map_err(|_| -> DrandError {
-        Box::new(VerificationError::Initialisation {
-            curve: "G1".to_owned(),
-        })
-        .into()
-    })?;
+    let hash_on_curve = g1_hasher(dst)?
+        .hash(hash)
+        .map_err(|_| initialisation_error("G1"))?;
 
+    not_identity(public_key)?;
     let g2 = G2Affine::generator();
     let sigma = g1_from_variable(signature)?;
-    let s = g2_from_variable(public_key)?;
-    Ok(fast_pairing_equality(&sigma, &g2, &hash_on_curve, &s))
+    fast_pairing_equality(&sigma, &g2, &hash_on_curve, public_key)
+}
+
+/// 128-bit scalars, derived by hashing all inputs of the batch.
+fn batch_scalars(
+    dst: &[u8],
+    signatures: &[Vec<u8>],
+    hashes: &[Vec<u8>],
+    public_key: &[u8],
+) -> Vec<Fr> {
+    let mut hasher = sha2::Sha256::new();
+    for input in [dst, public_key] {
+        hasher.update((input.len() as u64).to_be_bytes());
+        hasher.update(input);
+    }
+    for (signature, hash) in signatures.iter().zip(hashes) {
+        for input in [signature, hash] {
+            hasher.update((input.len() as u64).to_be_bytes());
+            hasher.update(input);
+        }
+    }
+    let seed = hasher.finalize();
+
+    (0..signatures.len() as u64)
+        .map(|i| {
+            let scalar = sha2::Sha256::new()
+                .chain_update(seed)
+                .chain_update(i.to_be_bytes())
+                .finalize();
+            Fr::from_le_bytes_mod_order(&scalar[..16])
+        })
+        .collect()
+}
+
+/// Check that signature is the actual aggregate of message and public key on BN254.
+/// Calculated by `e(signature, g2) == e(hash, pk)`.
+/// `signature` and `hash` are on G1, `public_key` is on G2. Points are uncompressed, as served by drand.
+#[cfg(feature = "bn254")]
+pub fn verify_bn254_g1_on_g2(
+    dst: &[u8],
+    signature: &[u8],
+    hash: &[u8],
+    public_key: &ark_bn254::G2Affine,
+) -> Result<bool> {
+    let hash_on_curve = bn254::hash_to_g1(dst, hash)?;
+
+    not_identity(public_key)?;
+    let g2 = ark_bn254::G2Affine::generator();
+    let sigma = bn254::g1_from_uncompressed(signature)?;
+    let looped =
+        ark_bn254::Bn254::multi_miller_loop([sigma.neg(), hash_on_curve], [g2, *public_key]);
+    pairing_is_identity(ark_bn254::Bn254::final_exponentiation(looped))
 }
 
 /// Checks if e(p, q) == e(r, s)
@@ -117,28 +477,570 @@ pub fn verify_g1_on_g2(
 ///   (which is costly is to multiply in G2 because these are very big numbers)
 ///   we can do FinalExponentiation(MillerLoop( [a,b], [-c,d] )) which is the same
 ///   in an optimized way.
-fn fast_pairing_equality(p: &G1Affine, q: &G2Affine, r: &G1Affine, s: &G2Affine) -> bool {
+fn fast_pairing_equality(p: &G1Affine, q: &G2Affine, r: &G1Affine, s: &G2Affine) -> Result<bool> {
     let minus_p = p.neg();
     // "some number of (G1, G2) pairs" are the inputs of the miller loop
     let looped = Bls12::<ark_bls12_381::Config>::multi_miller_loop([minus_p, *r], [*q, *s]);
-    let value = Bls12::final_exponentiation(looped);
-    value.unwrap().is_zero()
+    pairing_is_identity(Bls12::final_exponentiation(looped))
+}
+
+/// Whether `e(a[0], b[0]) * e(a[1], b[1])` is the identity, computed with a single final exponentiation.
+fn pairing_product_is_identity<P: Pairing>(
+    a: [P::G1Prepared; 2],
+    b: [P::G2Prepared; 2],
+) -> Result<bool> {
+    pairing_is_identity(P::final_exponentiation(P::multi_miller_loop(a, b)))
+}
+
+/// Whether the output of a final exponentiation is the identity, in which case both sides of the pairing equation match.
+fn pairing_is_identity<P: Pairing>(value: Option<PairingOutput<P>>) -> Result<bool> {
+    value
+        .map(|value| value.is_zero())
+        .ok_or_else(|| Box::new(BlsError::PairingFailure).into())
+}
+
+fn wrong_length(expected: usize, got: usize) -> DrandError {
+    Box::new(BlsError::WrongLength { expected, got }).into()
+}
+
+/// Decompressing only fails on the encoding, or when there is no point for the abscissa.
+fn decompression_error(e: SerializationError) -> DrandError {
+    match e {
+        SerializationError::InvalidData => Box::new(BlsError::NotOnCurve).into(),
+        _ => Box::new(BlsError::Deserialization).into(),
+    }
+}
+
+/// Point on the curve and in the prime order subgroup, other than the identity, or the first check failing.
+/// Decoding is unchecked, so that these checks are explicit rather than left to the serialization library.
+fn checked<C: short_weierstrass::SWCurveConfig>(
+    point: short_weierstrass::Affine<C>,
+) -> Result<short_weierstrass::Affine<C>> {
+    not_identity(&point)?;
+    if !point.is_on_curve() {
+        return Err(Box::new(BlsError::NotOnCurve).into());
+    }
+    if !point.is_in_correct_subgroup_assuming_on_curve() {
+        return Err(Box::new(BlsError::NotInSubgroup).into());
+    }
+    Ok(point)
+}
+
+fn not_identity<C: short_weierstrass::SWCurveConfig>(
+    point: &short_weierstrass::Affine<C>,
+) -> Result<()> {
+    if point.is_zero() {
+        return Err(Box::new(BlsError::Identity).into());
+    }
+    Ok(())
 }
 
-fn g1_from_variable(data: &[u8]) -> Result<G1Affine> {
+pub(crate) fn g1_from_variable(data: &[u8]) -> Result<G1Affine> {
     if data.len() != 48 {
-        return Err(Box::new(VerificationError::InvalidPoint).into());
+        return Err(wrong_length(48, data.len()));
     }
 
-    G1Affine::deserialize_compressed(data)
-        .map_err(|_| Box::new(VerificationError::Deserialization).into())
+    checked(G1Affine::deserialize_compressed_unchecked(data).map_err(decompression_error)?)
 }
 
-fn g2_from_variable(data: &[u8]) -> Result<G2Affine> {
+pub(crate) fn g2_from_variable(data: &[u8]) -> Result<G2Affine> {
     if data.len() != 96 {
-        return Err(Box::new(VerificationError::InvalidPoint).into());
+        return Err(wrong_length(96, data.len()));
+    }
+
+    checked(G2Affine::deserialize_compressed_unchecked(data).map_err(decompression_error)?)
+}
+
+/// BN254 points encoding, and hashing to G1, as used by drand evmnet.
+/// Hashing follows RFC 9380 with `expand_message_xmd` on Keccak-256, and the Shallue-van de Woestijne map.
+#[cfg(feature = "bn254")]
+pub(crate) mod bn254 {
+    use alloc::{borrow::ToOwned, boxed::Box, vec::Vec};
+
+    use ark_bn254::{Fq, Fq2, G1Affine, G1Projective, G2Affine};
+    use ark_ec::CurveGroup;
+    use ark_ff::{BigInteger, Field, One, PrimeField, Zero};
+    use ark_serialize::CanonicalDeserialize;
+    use sha3::{Digest, Keccak256};
+
+    use super::{checked, wrong_length, BlsError};
+    use crate::{DrandError, Result};
+
+    /// Length of a base field element, big-endian encoded.
+    const FIELD_LEN: usize = 32;
+    /// Length of the uniform bytes a field element is reduced from, `ceil((ceil(log2(p)) + 128) / 8)`.
+    const HASH_TO_FIELD_LEN: usize = 48;
+    /// Keccak-256 output length.
+    const KECCAK_LEN: usize = 32;
+    /// Keccak-256 input block length.
+    const KECCAK_BLOCK_LEN: usize = 136;
+
+    fn initialisation_error() -> DrandError {
+        Box::new(BlsError::Initialisation {
+            curve: "BN254 G1".to_owned(),
+        })
+        .into()
     }
 
-    G2Affine::deserialize_compressed(data)
-        .map_err(|_| Box::new(VerificationError::Deserialization).into())
+    pub(crate) fn hash_to_g1(dst: &[u8], msg: &[u8]) -> Result<G1Affine> {
+        let uniform = expand_message_xmd(dst, msg, 2 * HASH_TO_FIELD_LEN)?;
+        let u0 = Fq::from_be_bytes_mod_order(&uniform[..HASH_TO_FIELD_LEN]);
+        let u1 = Fq::from_be_bytes_mod_order(&uniform[HASH_TO_FIELD_LEN..]);
+        // G1 cofactor is 1, there is no need to clear it
+        let point = G1Projective::from(map_to_curve(u0)?) + G1Projective::from(map_to_curve(u1)?);
+        Ok(point.into_affine())
+    }
+
+    /// expand_message_xmd from RFC 9380 Section 5.3.1, with Keccak-256.
+    fn expand_message_xmd(dst: &[u8], msg: &[u8], len: usize) -> Result<Vec<u8>> {
+        let ell = len.div_ceil(KECCAK_LEN);
+        if ell > 255 || len > u16::MAX as usize || dst.len() > 255 {
+            return Err(initialisation_error());
+        }
+        let dst_prime = [dst, &[dst.len() as u8][..]].concat();
+
+        let b_0 = Keccak256::new()
+            .chain_update([0u8; KECCAK_BLOCK_LEN])
+            .chain_update(msg)
+            .chain_update((len as u16).to_be_bytes())
+            .chain_update([0u8])
+            .chain_update(&dst_prime)
+            .finalize();
+        let mut b_i = Keccak256::new()
+            .chain_update(b_0)
+            .chain_update([1u8])
+            .chain_update(&dst_prime)
+            .finalize();
+        let mut uniform = b_i.to_vec();
+        for i in 2..=ell {
+            let xored: Vec<u8> = b_0.iter().zip(b_i.iter()).map(|(a, b)| a ^ b).collect();
+            b_i = Keccak256::new()
+                .chain_update(xored)
+                .chain_update([i as u8])
+                .chain_update(&dst_prime)
+                .finalize();
+            uniform.extend_from_slice(&b_i);
+        }
+        uniform.truncate(len);
+        Ok(uniform)
+    }
+
+    /// Shallue-van de Woestijne map from RFC 9380 Section 6.6.1, for `y^2 = x^3 + 3` with `Z = 1`.
+    fn map_to_curve(u: Fq) -> Result<G1Affine> {
+        let b = Fq::from(3u64);
+        let g = |x: Fq| x.square() * x + b;
+        let z = Fq::one();
+        let three_z2 = Fq::from(3u64) * z.square();
+
+        let c1 = g(z);
+        let c2 = -z / Fq::from(2u64);
+        let c3 = (-g(z) * three_z2).sqrt().ok_or_else(initialisation_error)?;
+        let c3 = if sgn0(&c3) { -c3 } else { c3 };
+        let c4 = -Fq::from(4u64) * g(z) / three_z2;
+
+        let tv1 = u.square() * c1;
+        let tv2 = Fq::one() + tv1;
+        let tv1 = Fq::one() - tv1;
+        let tv3 = (tv1 * tv2).inverse().unwrap_or_else(Fq::zero);
+        let tv4 = u * tv1 * tv3 * c3;
+        let x1 = c2 - tv4;
+        let x2 = c2 + tv4;
+        let x3 = c4 * (tv2.square() * tv3).square() + z;
+
+        let x = if g(x1).legendre().is_qr() {
+            x1
+        } else if g(x2).legendre().is_qr() {
+            x2
+        } else {
+            x3
+        };
+        let y = g(x).sqrt().ok_or_else(initialisation_error)?;
+        let y = if sgn0(&u) == sgn0(&y) { y } else { -y };
+        Ok(G1Affine::new_unchecked(x, y))
+    }
+
+    fn sgn0(x: &Fq) -> bool {
+        x.into_bigint().is_odd()
+    }
+
+    fn fq_from_be(data: &[u8]) -> Result<Fq> {
+        let mut data = data.to_vec();
+        data.reverse();
+        Fq::deserialize_uncompressed(data.as_slice())
+            .map_err(|_| Box::new(BlsError::Deserialization).into())
+    }
+
+    /// G1 point encoded as `x || y`.
+    pub(crate) fn g1_from_uncompressed(data: &[u8]) -> Result<G1Affine> {
+        if data.len() != 2 * FIELD_LEN {
+            return Err(wrong_length(2 * FIELD_LEN, data.len()));
+        }
+
+        let x = fq_from_be(&data[..FIELD_LEN])?;
+        let y = fq_from_be(&data[FIELD_LEN..])?;
+        checked(G1Affine::new_unchecked(x, y))
+    }
+
+    /// G2 point encoded as `x.c1 || x.c0 || y.c1 || y.c0`, as expected by EVM precompiles.
+    pub(crate) fn g2_from_uncompressed(data: &[u8]) -> Result<G2Affine> {
+        if data.len() != 4 * FIELD_LEN {
+            return Err(wrong_length(4 * FIELD_LEN, data.len()));
+        }
+
+        let fq = |i: usize| fq_from_be(&data[i * FIELD_LEN..(i + 1) * FIELD_LEN]);
+        let x = Fq2::new(fq(1)?, fq(0)?);
+        let y = Fq2::new(fq(3)?, fq(2)?);
+        checked(G2Affine::new_unchecked(x, y))
+    }
+
+    #[cfg(test)]
+    pub(crate) fn g1_to_uncompressed(point: &G1Affine) -> Vec<u8> {
+        [
+            point.x.into_bigint().to_bytes_be(),
+            point.y.into_bigint().to_bytes_be(),
+        ]
+        .concat()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn g2_to_uncompressed(point: &G2Affine) -> Vec<u8> {
+        [point.x.c1, point.x.c0, point.y.c1, point.y.c0]
+            .iter()
+            .flat_map(|c| c.into_bigint().to_bytes_be())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ark_bls12_381::Bls12_381;
+    use ark_serialize::CanonicalSerialize;
+
+    use super::*;
+
+    fn bls_error<T: fmt::Debug>(result: Result<T>) -> BlsError {
+        match result {
+            Err(DrandError::Signature(e)) => *e,
+            Err(e) => panic!("point should fail with a BLS error, got {e}"),
+            Ok(point) => panic!("point should not decode, got {point:?}"),
+        }
+    }
+
+    /// Compressed G1 encoding of a point with abscissa `x`, if there is one.
+    fn g1_with_x(x: u8) -> [u8; 48] {
+        let mut data = [0u8; 48];
+        data[0] = 0x80;
+        data[47] = x;
+        data
+    }
+
+    fn g1_generator() -> Vec<u8> {
+        let mut data = vec![];
+        G1Affine::generator()
+            .serialize_compressed(&mut data)
+            .unwrap();
+        data
+    }
+
+    #[test]
+    fn bls_point_wrong_length_fails() {
+        assert!(matches!(
+            bls_error(PublicKey::g1(&g1_generator()[..47])),
+            BlsError::WrongLength {
+                expected: 48,
+                got: 47
+            }
+        ));
+        assert!(matches!(
+            bls_error(PublicKey::g2(&g1_generator())),
+            BlsError::WrongLength {
+                expected: 96,
+                got: 48
+            }
+        ));
+        #[cfg(feature = "bn254")]
+        assert!(matches!(
+            bls_error(PublicKey::bn254_g2(&[0u8; 96])),
+            BlsError::WrongLength {
+                expected: 128,
+                got: 96
+            }
+        ));
+
+        // a truncated signature is a decoding error, not an invalid signature
+        let public_key = PublicKey::g1(&g1_generator()).unwrap();
+        assert!(matches!(
+            bls_error(verify(G2_DOMAIN, &[0u8; 95], b"message", &public_key)),
+            BlsError::WrongLength {
+                expected: 96,
+                got: 95
+            }
+        ));
+    }
+
+    #[test]
+    fn bls_point_not_on_curve_fails() {
+        // about half of the abscissas have no point on the curve
+        assert!((1..=u8::MAX).any(|x| matches!(
+            bls_error(PublicKey::g1(&g1_with_x(x))),
+            BlsError::NotOnCurve
+        )));
+        #[cfg(feature = "bn254")]
+        assert!(matches!(
+            bls_error(PublicKey::bn254_g2(&[0u8; 128])),
+            BlsError::NotOnCurve
+        ));
+    }
+
+    #[test]
+    fn bls_point_not_in_subgroup_fails() {
+        // (0, 2) is on the curve, but G1 cofactor keeps it out of the prime order subgroup
+        assert!(matches!(
+            bls_error(PublicKey::g1(&g1_with_x(0))),
+            BlsError::NotInSubgroup
+        ));
+    }
+
+    #[test]
+    fn bls_point_identity_fails() {
+        // compressed encodings of the identity set the compression and infinity flags, with a zero abscissa
+        let mut g1_identity = [0u8; 48];
+        g1_identity[0] = 0xc0;
+        let mut g2_identity = [0u8; 96];
+        g2_identity[0] = 0xc0;
+        let mut encoded = vec![];
+        G1Affine::zero().serialize_compressed(&mut encoded).unwrap();
+        assert_eq!(encoded, g1_identity);
+        encoded.clear();
+        G2Affine::zero().serialize_compressed(&mut encoded).unwrap();
+        assert_eq!(encoded, g2_identity);
+
+        assert!(matches!(
+            bls_error(PublicKey::g1(&g1_identity)),
+            BlsError::Identity
+        ));
+        assert!(matches!(
+            bls_error(PublicKey::g2(&g2_identity)),
+            BlsError::Identity
+        ));
+
+        // identity signatures are rejected before any pairing
+        let public_key = PublicKey::g1(&g1_generator()).unwrap();
+        assert!(matches!(
+            bls_error(verify(G2_DOMAIN, &g2_identity, b"message", &public_key)),
+            BlsError::Identity
+        ));
+        let verifier = PreparedVerifier::new(G2_DOMAIN, &public_key, &g1_generator());
+        assert!(matches!(
+            bls_error(verifier.verify(&g2_identity, b"message")),
+            BlsError::Identity
+        ));
+        assert!(matches!(
+            bls_error(verifier.verify_batch(&[g2_identity.to_vec()], &[b"message".to_vec()])),
+            BlsError::Identity
+        ));
+
+        // as are identity public keys provided as points
+        assert!(matches!(
+            bls_error(verify_g2_on_g1(
+                G2_DOMAIN,
+                &g2_identity,
+                b"message",
+                &G1Affine::zero()
+            )),
+            BlsError::Identity
+        ));
+        assert!(matches!(
+            bls_error(verify_g1_on_g2(
+                G1_DOMAIN,
+                &g1_identity,
+                b"message",
+                &G2Affine::zero()
+            )),
+            BlsError::Identity
+        ));
+
+        // an all-zero public key lacks the compression flag, and does not decode
+        assert!(matches!(
+            bls_error(PublicKey::g1(&[0u8; 48])),
+            BlsError::Deserialization
+        ));
+        assert!(matches!(
+            bls_error(PublicKey::g2(&[0u8; 96])),
+            BlsError::Deserialization
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn bls_hasher_cache_works() {
+        let cache = HasherCache::<G1Hasher>::new();
+        let init = |dst: &'static [u8]| move || Ok(G1Hasher::new(dst).unwrap());
+
+        // hashers are built once per domain separation tag, and hash as fresh ones do
+        let rfc = cache.get_or_try_init(G1_DOMAIN, init(G1_DOMAIN)).unwrap();
+        let legacy = cache.get_or_try_init(G2_DOMAIN, init(G2_DOMAIN)).unwrap();
+        assert!(!Arc::ptr_eq(&rfc, &legacy));
+        assert!(Arc::ptr_eq(
+            &rfc,
+            &cache
+                .get_or_try_init(G1_DOMAIN, || panic!("hasher should be cached"))
+                .unwrap()
+        ));
+        for (hasher, dst) in [(&rfc, G1_DOMAIN), (&legacy, G2_DOMAIN)] {
+            assert_eq!(
+                hasher.hash(b"message").unwrap(),
+                G1Hasher::new(dst).unwrap().hash(b"message").unwrap()
+            );
+        }
+        assert_ne!(
+            rfc.hash(b"message").unwrap(),
+            legacy.hash(b"message").unwrap()
+        );
+
+        // the oldest tag is evicted once the cache is full, and initialisation errors are not cached
+        let dsts: Vec<Vec<u8>> = (0..HASHER_CACHE_CAPACITY)
+            .map(|i| format!("BLS_SIG_TEST{i}").into_bytes())
+            .collect();
+        for dst in &dsts {
+            cache
+                .get_or_try_init(dst, || Ok(G1Hasher::new(dst).unwrap()))
+                .unwrap();
+        }
+        assert!(cache
+            .get_or_try_init(G1_DOMAIN, || Err(initialisation_error("G1")))
+            .is_err());
+        assert!(cache
+            .get_or_try_init(dsts.last().unwrap(), || panic!("hasher should be cached"))
+            .is_ok());
+    }
+
+    #[test]
+    fn bls_point_malformed_encoding_fails() {
+        // uncompressed flag is not a valid encoding for 48 bytes
+        let mut data = g1_generator();
+        data[0] &= 0x7f;
+        assert!(matches!(
+            bls_error(PublicKey::g1(&data)),
+            BlsError::Deserialization
+        ));
+    }
+
+    #[test]
+    fn bls_prepared_verifier_works() {
+        let secret_key = Fr::from(0xd4a2du64);
+        let messages: Vec<Vec<u8>> = (1u64..=4)
+            .map(|round| sha2::Sha256::digest(round.to_be_bytes()).to_vec())
+            .collect();
+
+        let mut g1_public_key = vec![];
+        (G1Affine::generator() * secret_key)
+            .into_affine()
+            .serialize_compressed(&mut g1_public_key)
+            .unwrap();
+        let g2_signatures: Vec<Vec<u8>> = messages
+            .iter()
+            .map(|message| {
+                let mut signature = vec![];
+                (g2_hasher(G2_DOMAIN).unwrap().hash(message).unwrap() * secret_key)
+                    .into_affine()
+                    .serialize_compressed(&mut signature)
+                    .unwrap();
+                signature
+            })
+            .collect();
+
+        let mut g2_public_key = vec![];
+        (G2Affine::generator() * secret_key)
+            .into_affine()
+            .serialize_compressed(&mut g2_public_key)
+            .unwrap();
+        let g1_signatures: Vec<Vec<u8>> = messages
+            .iter()
+            .map(|message| {
+                let mut signature = vec![];
+                (g1_hasher(G1_DOMAIN).unwrap().hash(message).unwrap() * secret_key)
+                    .into_affine()
+                    .serialize_compressed(&mut signature)
+                    .unwrap();
+                signature
+            })
+            .collect();
+
+        for (dst, public_key, encoded, signatures) in [
+            (
+                G2_DOMAIN,
+                PublicKey::g1(&g1_public_key).unwrap(),
+                &g1_public_key,
+                &g2_signatures,
+            ),
+            (
+                G1_DOMAIN,
+                PublicKey::g2(&g2_public_key).unwrap(),
+                &g2_public_key,
+                &g1_signatures,
+            ),
+        ] {
+            let verifier = PreparedVerifier::new(dst, &public_key, encoded);
+            for (i, signature) in signatures.iter().enumerate() {
+                for (j, message) in messages.iter().enumerate() {
+                    let expected = verify(dst, signature, message, &public_key).unwrap();
+                    assert_eq!(expected, i == j);
+                    assert_eq!(verifier.verify(signature, message).unwrap(), expected);
+                }
+            }
+            assert!(verifier.verify_batch(signatures, &messages).unwrap());
+
+            // swapping two signatures invalidates the batch, as it does each of them
+            let mut swapped = signatures.clone();
+            swapped.swap(0, 1);
+            assert!(!verifier.verify_batch(&swapped, &messages).unwrap());
+            assert!(!verifier.verify_batch(&signatures[1..], &messages).unwrap());
+
+            // decoding errors are the same
+            assert!(matches!(
+                bls_error(verifier.verify(&signatures[0][1..], &messages[0])),
+                BlsError::WrongLength { .. }
+            ));
+            assert!(matches!(
+                bls_error(verify(dst, &signatures[0][1..], &messages[0], &public_key)),
+                BlsError::WrongLength { .. }
+            ));
+        }
+    }
+
+    #[test]
+    fn bls_dst_for_scheme_works() {
+        let dst = |id: &str| dst_for_scheme(&id.parse().unwrap());
+        assert_eq!(
+            dst("pedersen-bls-chained"),
+            Some(&b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_"[..])
+        );
+        assert_eq!(
+            dst("pedersen-bls-unchained"),
+            Some(&b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_"[..])
+        );
+        assert_eq!(
+            dst("bls-unchained-on-g1"),
+            Some(&b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_"[..])
+        );
+        assert_eq!(
+            dst("bls-unchained-g1-rfc9380"),
+            Some(&b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_NUL_"[..])
+        );
+        assert_eq!(
+            dst("bls-bn254-unchained-on-g1"),
+            Some(&b"BLS_SIG_BN254G1_XMD:KECCAK-256_SVDW_RO_NUL_"[..])
+        );
+        assert_eq!(dst("bls-unchained-on-g3"), None);
+    }
+
+    #[test]
+    fn bls_pairing_failure_fails() {
+        assert!(matches!(
+            bls_error(pairing_is_identity::<Bls12_381>(None)),
+            BlsError::PairingFailure
+        ));
+        assert!(pairing_is_identity::<Bls12_381>(Some(PairingOutput::zero())).unwrap());
+    }
 }
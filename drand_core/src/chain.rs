@@ -1,50 +1,415 @@
-use serde::{Deserialize, Serialize};
+use alloc::{borrow::ToOwned, boxed::Box, string::String, sync::Arc, vec::Vec};
+use core::{
+    convert::Infallible,
+    fmt,
+    hash::{Hash, Hasher},
+    str::FromStr,
+    time::Duration,
+};
 
-use crate::{beacon::RandomnessBeacon, Result};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    beacon::{ApiBeacon, BeaconError, RandomnessBeacon, VerifyOptions},
+    bls_signatures::{dst_for_scheme, PreparedVerifier, PublicKey},
+    DrandError, Result,
+};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 /// Additional information about the chain.
+/// Relays omitting it, or its beacon ID, serve the default beacon, as drand does.
 pub struct ChainMetadata {
-    #[serde(rename(serialize = "beaconID", deserialize = "beaconID"))]
+    #[serde(
+        rename(serialize = "beaconID", deserialize = "beaconID"),
+        default = "default_beacon_id"
+    )]
     beacon_id: String,
+    #[serde(flatten)]
+    extra: Map<String, Value>,
 }
 
 impl ChainMetadata {
     pub fn new(beacon_id: String) -> Self {
-        Self { beacon_id }
+        Self {
+            beacon_id,
+            extra: Map::new(),
+        }
     }
 
     /// The ID of the beacon chain this `ChainInfo` corresponds to.
+    /// It is `default` when the chain info does not provide one, and is serialized as such.
     pub fn beacon_id(&self) -> String {
         self.beacon_id.clone()
     }
+
+    /// Metadata fields unknown to this crate, kept as served so that they are serialized back.
+    pub fn extra(&self) -> &Map<String, Value> {
+        &self.extra
+    }
+}
+
+impl Default for ChainMetadata {
+    fn default() -> Self {
+        Self::new(default_beacon_id())
+    }
+}
+
+/// Beacon ID of chains that do not provide one.
+fn default_beacon_id() -> String {
+    "default".to_owned()
 }
 
+/// Metadata are equal when their beacon IDs are. Unknown fields are ignored, as they do not identify the chain.
 impl PartialEq for ChainMetadata {
     fn eq(&self, other: &Self) -> bool {
         self.beacon_id == other.beacon_id
     }
 }
 
+impl Eq for ChainMetadata {}
+
+impl Hash for ChainMetadata {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.beacon_id.hash(state);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Cryptographic scheme of a chain, as identified by its `schemeID`.
+/// Identifiers not known to this crate are preserved as [`Scheme::Unknown`], and cannot be verified unless described in a [`SchemeRegistry`].
+pub enum Scheme {
+    /// Chained beacons, signatures on G2. Identified by `pedersen-bls-chained`.
+    PedersenBlsChained,
+    /// Unchained beacons, signatures on G2. Identified by `pedersen-bls-unchained`.
+    PedersenBlsUnchained,
+    /// Unchained beacons, signatures on G1 with the G2 domain separation tag. Identified by `bls-unchained-on-g1`.
+    BlsUnchainedOnG1,
+    /// Unchained beacons, signatures on G1 hashed as per RFC 9380. Identified by `bls-unchained-g1-rfc9380`.
+    BlsUnchainedG1Rfc9380,
+    /// Unchained beacons on BN254, signatures on G1 hashed as per RFC 9380. Identified by `bls-bn254-unchained-on-g1`.
+    BlsBn254UnchainedOnG1,
+    Unknown(String),
+}
+
+impl Scheme {
+    /// Identifier of the scheme, as served in `schemeID`.
+    pub fn id(&self) -> &str {
+        match self {
+            Self::PedersenBlsChained => "pedersen-bls-chained",
+            Self::PedersenBlsUnchained => "pedersen-bls-unchained",
+            Self::BlsUnchainedOnG1 => "bls-unchained-on-g1",
+            Self::BlsUnchainedG1Rfc9380 => "bls-unchained-g1-rfc9380",
+            Self::BlsBn254UnchainedOnG1 => "bls-bn254-unchained-on-g1",
+            Self::Unknown(id) => id,
+        }
+    }
+
+    /// Is the scheme relying on RFC 9380 Hashing to elliptic curves
+    pub fn is_rfc9380(&self) -> bool {
+        matches!(
+            self,
+            Self::BlsUnchainedG1Rfc9380 | Self::BlsBn254UnchainedOnG1
+        )
+    }
+
+    pub fn is_unchained(&self) -> bool {
+        matches!(
+            self,
+            Self::PedersenBlsUnchained
+                | Self::BlsUnchainedOnG1
+                | Self::BlsUnchainedG1Rfc9380
+                | Self::BlsBn254UnchainedOnG1
+        )
+    }
+
+    /// Are signatures on G1, with public keys on G2
+    pub fn is_g1(&self) -> bool {
+        matches!(
+            self,
+            Self::BlsUnchainedOnG1 | Self::BlsUnchainedG1Rfc9380 | Self::BlsBn254UnchainedOnG1
+        )
+    }
+
+    /// Group beacon signatures are on, or `None` for unknown schemes.
+    pub fn signature_group(&self) -> Option<SignatureGroup> {
+        match self {
+            Self::PedersenBlsChained | Self::PedersenBlsUnchained => Some(SignatureGroup::G2),
+            Self::BlsUnchainedOnG1 | Self::BlsUnchainedG1Rfc9380 => Some(SignatureGroup::G1),
+            Self::BlsBn254UnchainedOnG1 => Some(SignatureGroup::Bn254G1),
+            Self::Unknown(_) => None,
+        }
+    }
+
+    /// Length in bytes of chain public keys, on the other group than signatures, or `None` for unknown schemes.
+    pub fn public_key_len(&self) -> Option<usize> {
+        self.signature_group().map(|group| group.public_key_len())
+    }
+
+    /// Length in bytes of beacon signatures, or `None` for unknown schemes.
+    pub fn signature_len(&self) -> Option<usize> {
+        self.signature_group().map(|group| group.signature_len())
+    }
+}
+
+impl FromStr for Scheme {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        let scheme = match s {
+            "pedersen-bls-chained" => Self::PedersenBlsChained,
+            "pedersen-bls-unchained" => Self::PedersenBlsUnchained,
+            "bls-unchained-on-g1" => Self::BlsUnchainedOnG1,
+            "bls-unchained-g1-rfc9380" => Self::BlsUnchainedG1Rfc9380,
+            "bls-bn254-unchained-on-g1" => Self::BlsBn254UnchainedOnG1,
+            id => Self::Unknown(id.to_owned()),
+        };
+        Ok(scheme)
+    }
+}
+
+impl fmt::Display for Scheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.id())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Group beacon signatures are on. Public keys are on the other group of the pairing.
+pub enum SignatureGroup {
+    /// BLS12-381 G1, compressed.
+    G1,
+    /// BLS12-381 G2, compressed.
+    G2,
+    /// BN254 G1, uncompressed. Requires the `bn254` feature.
+    Bn254G1,
+}
+
+impl SignatureGroup {
+    /// Length in bytes of signatures on this group.
+    pub fn signature_len(&self) -> usize {
+        match self {
+            Self::G1 => 48,
+            Self::G2 => 96,
+            Self::Bn254G1 => 64,
+        }
+    }
+
+    /// Length in bytes of public keys verifying signatures on this group, which are on the other group of the pairing.
+    pub fn public_key_len(&self) -> usize {
+        match self {
+            Self::G1 => 96,
+            Self::G2 => 48,
+            Self::Bn254G1 => 128,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// How beacons of a scheme are signed and verified.
+/// Descriptors of schemes known to drand are provided by [`SchemeDescriptor::builtin`], others can be registered in a [`SchemeRegistry`].
+pub struct SchemeDescriptor {
+    id: String,
+    is_unchained: bool,
+    signature_group: SignatureGroup,
+    dst: Vec<u8>,
+    message_fn: fn(&ApiBeacon) -> Result<Vec<u8>>,
+    randomness_fn: fn(&[u8]) -> [u8; 32],
+}
+
+impl SchemeDescriptor {
+    /// Unchained scheme identified by `id`, with signatures on `signature_group` hashed to the curve with the domain separation tag `dst`.
+    /// Beacons sign [`ApiBeacon::message`], and their randomness is `sha256(signature)`.
+    pub fn new(id: &str, signature_group: SignatureGroup, dst: &[u8]) -> Self {
+        Self {
+            id: id.to_owned(),
+            is_unchained: true,
+            signature_group,
+            dst: dst.to_vec(),
+            message_fn: ApiBeacon::message,
+            randomness_fn: |signature| Sha256::digest(signature).into(),
+        }
+    }
+
+    /// Descriptor of a scheme known to drand, or `None` for [`Scheme::Unknown`].
+    pub fn builtin(scheme: &Scheme) -> Option<Self> {
+        let dst = dst_for_scheme(scheme)?;
+        let descriptor = match scheme {
+            Scheme::PedersenBlsChained => {
+                Self::new(scheme.id(), SignatureGroup::G2, dst).with_unchained(false)
+            }
+            Scheme::PedersenBlsUnchained => Self::new(scheme.id(), SignatureGroup::G2, dst),
+            Scheme::BlsUnchainedOnG1 | Scheme::BlsUnchainedG1Rfc9380 => {
+                Self::new(scheme.id(), SignatureGroup::G1, dst)
+            }
+            // evmnet digests rounds with Keccak-256, which is cheaper to compute on EVM chains
+            #[cfg(feature = "bn254")]
+            Scheme::BlsBn254UnchainedOnG1 => Self::new(scheme.id(), SignatureGroup::Bn254G1, dst)
+                .with_message_fn(|beacon| {
+                    Ok(sha3::Keccak256::digest(beacon.round().to_be_bytes()).to_vec())
+                }),
+            // cannot be prepared without the bn254 feature
+            #[cfg(not(feature = "bn254"))]
+            Scheme::BlsBn254UnchainedOnG1 => Self::new(scheme.id(), SignatureGroup::Bn254G1, dst),
+            Scheme::Unknown(_) => return None,
+        };
+        Some(descriptor)
+    }
+
+    /// Whether beacons are unchained. Chained beacons sign the previous signature alongside their round.
+    pub fn with_unchained(mut self, is_unchained: bool) -> Self {
+        self.is_unchained = is_unchained;
+        self
+    }
+
+    /// Message signed by beacons, given the beacon being verified.
+    pub fn with_message_fn(mut self, message_fn: fn(&ApiBeacon) -> Result<Vec<u8>>) -> Self {
+        self.message_fn = message_fn;
+        self
+    }
+
+    /// Randomness of beacons, given their signature.
+    pub fn with_randomness_fn(mut self, randomness_fn: fn(&[u8]) -> [u8; 32]) -> Self {
+        self.randomness_fn = randomness_fn;
+        self
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn is_unchained(&self) -> bool {
+        self.is_unchained
+    }
+
+    pub fn signature_group(&self) -> SignatureGroup {
+        self.signature_group
+    }
+
+    pub fn dst(&self) -> &[u8] {
+        &self.dst
+    }
+
+    pub fn message(&self, beacon: &ApiBeacon) -> Result<Vec<u8>> {
+        (self.message_fn)(beacon)
+    }
+
+    pub fn randomness(&self, signature: &[u8]) -> [u8; 32] {
+        (self.randomness_fn)(signature)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+/// Schemes known in addition to the ones of drand.
+/// Registered descriptors take precedence over built-in ones with the same identifier.
+pub struct SchemeRegistry {
+    schemes: Vec<SchemeDescriptor>,
+}
+
+impl SchemeRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_scheme(mut self, descriptor: SchemeDescriptor) -> Self {
+        self.register(descriptor);
+        self
+    }
+
+    /// Register `descriptor`, replacing any descriptor previously registered with the same identifier.
+    pub fn register(&mut self, descriptor: SchemeDescriptor) {
+        self.schemes.retain(|scheme| scheme.id != descriptor.id);
+        self.schemes.push(descriptor);
+    }
+
+    /// Descriptor registered for `scheme`, falling back to the built-in one.
+    pub fn get(&self, scheme: &Scheme) -> Option<SchemeDescriptor> {
+        self.schemes
+            .iter()
+            .find(|descriptor| descriptor.id == scheme.id())
+            .cloned()
+            .or_else(|| SchemeDescriptor::builtin(scheme))
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ChainInfo {
-    #[serde(with = "hex::serde")]
+    #[serde(with = "crate::hex_serde")]
     public_key: Vec<u8>,
+    #[serde(deserialize_with = "deserialize_period")]
     period: u64,
     genesis_time: u64,
-    #[serde(with = "hex::serde")]
+    #[serde(with = "crate::hex_serde")]
     hash: Vec<u8>,
     #[serde(
         rename(serialize = "groupHash", deserialize = "groupHash"),
-        with = "hex::serde"
+        with = "crate::hex_serde"
     )]
     group_hash: Vec<u8>,
     #[serde(rename(serialize = "schemeID", deserialize = "schemeID"))]
     scheme_id: String,
+    #[serde(default)]
     metadata: ChainMetadata,
+    #[serde(flatten)]
+    extra: Map<String, Value>,
+}
+
+/// Period of deserialized chain info, which is a divisor of round computations and cannot be zero.
+fn deserialize_period<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> core::result::Result<u64, D::Error> {
+    match u64::deserialize(deserializer)? {
+        0 => Err(serde::de::Error::custom(BeaconError::InvalidPeriod)),
+        period => Ok(period),
+    }
 }
 
 impl ChainInfo {
+    /// Chain info of a network, such as a private one, as served on its `/info` endpoint.
+    /// Fails with [`BeaconError::InvalidLength`] if `hash` or `group_hash` are not 32 bytes,
+    /// or if `public_key` is not on the group of public keys of a known scheme. Public keys of unknown schemes are checked when chain info is prepared.
+    /// Fails with [`BeaconError::InvalidPeriod`] if `period` is zero.
+    pub fn new(
+        public_key: Vec<u8>,
+        period: u64,
+        genesis_time: u64,
+        hash: Vec<u8>,
+        group_hash: Vec<u8>,
+        scheme_id: String,
+        metadata: ChainMetadata,
+    ) -> Result<Self> {
+        let check_len = |field: &'static str, expected: usize, got: usize| -> Result<()> {
+            if expected != got {
+                return Err(Box::new(BeaconError::InvalidLength {
+                    field,
+                    expected,
+                    got,
+                })
+                .into());
+            }
+            Ok(())
+        };
+        if let Some(len) = scheme_id.parse::<Scheme>().unwrap().public_key_len() {
+            check_len("public key", len, public_key.len())?;
+        }
+        check_len("hash", 32, hash.len())?;
+        check_len("group hash", 32, group_hash.len())?;
+        if period == 0 {
+            return Err(Box::new(BeaconError::InvalidPeriod).into());
+        }
+        Ok(Self {
+            public_key,
+            period,
+            genesis_time,
+            hash,
+            group_hash,
+            scheme_id,
+            metadata,
+            extra: Map::new(),
+        })
+    }
+
     /// Hex encoded BLS12-381 public key.
     pub fn public_key(&self) -> Vec<u8> {
         self.public_key.clone()
@@ -60,6 +425,22 @@ impl ChainInfo {
         self.genesis_time
     }
 
+    /// How often the network emits randomness.
+    /// Fails with [`BeaconError::TimeOutOfRange`] if the period does not fit a [`time::Duration`].
+    #[cfg(feature = "time")]
+    pub fn period_duration(&self) -> Result<time::Duration> {
+        i64::try_from(self.period)
+            .map(time::Duration::seconds)
+            .map_err(|_| Box::new(BeaconError::TimeOutOfRange).into())
+    }
+
+    /// Date of the round 1 of the network.
+    /// Fails with [`BeaconError::TimeOutOfRange`] if the genesis time cannot be represented.
+    #[cfg(feature = "time")]
+    pub fn genesis_datetime(&self) -> Result<time::OffsetDateTime> {
+        self.time_info().datetime_of_round(1)
+    }
+
     /// Hash identifying this specific chain of beacons.
     pub fn hash(&self) -> Vec<u8> {
         self.hash.clone()
@@ -71,25 +452,116 @@ impl ChainInfo {
     }
 
     /// The version/format of cryptography.
+    /// See [`Self::scheme`] for its parsed counterpart.
     pub fn scheme_id(&self) -> String {
         self.scheme_id.clone()
     }
 
+    /// The version/format of cryptography.
+    pub fn scheme(&self) -> Scheme {
+        match self.scheme_id.parse() {
+            Ok(scheme) => scheme,
+            Err(infallible) => match infallible {},
+        }
+    }
+
     /// Is the chain relying on RFC 9380 Hashing to elliptic curves
     pub fn is_rfc9380(&self) -> bool {
-        self.scheme_id.contains("rfc9380")
+        self.scheme().is_rfc9380()
     }
 
     pub fn is_unchained(&self) -> bool {
-        self.scheme_id.contains("unchained")
+        self.scheme().is_unchained()
     }
 
     /// Additional information about the chain.
     pub fn metadata(&self) -> ChainMetadata {
         self.metadata.clone()
     }
+
+    /// Fields unknown to this crate, such as `boundedStorage`, kept as served so that they are serialized back.
+    pub fn extra(&self) -> &Map<String, Value> {
+        &self.extra
+    }
+
+    /// Deserialize the public key once, to verify many beacons against this chain.
+    /// Only schemes known to drand can be prepared, see [`Self::prepare_with_registry`] for others.
+    pub fn prepare(&self) -> Result<PreparedChainInfo> {
+        self.prepare_with_registry(&SchemeRegistry::default())
+    }
+
+    /// Prepare chain info as [`Self::prepare`] does, looking the scheme up in `registry` first.
+    pub fn prepare_with_registry(&self, registry: &SchemeRegistry) -> Result<PreparedChainInfo> {
+        let Some(scheme) = registry.get(&self.scheme()) else {
+            return Err(Box::new(BeaconError::UnknownScheme {
+                scheme: self.scheme_id(),
+            })
+            .into());
+        };
+        // the group is set by the scheme, rather than sniffed from the public key length
+        let expected = scheme.signature_group().public_key_len();
+        if self.public_key.len() != expected {
+            return Err(Box::new(BeaconError::InvalidLength {
+                field: "public key",
+                expected,
+                got: self.public_key.len(),
+            })
+            .into());
+        }
+        let public_key = match scheme.signature_group() {
+            SignatureGroup::G1 => PublicKey::g2(&self.public_key)?,
+            SignatureGroup::G2 => PublicKey::g1(&self.public_key)?,
+            #[cfg(feature = "bn254")]
+            SignatureGroup::Bn254G1 => PublicKey::bn254_g2(&self.public_key)?,
+            #[cfg(not(feature = "bn254"))]
+            SignatureGroup::Bn254G1 => {
+                return Err(Box::new(BeaconError::UnsupportedScheme {
+                    scheme: self.scheme_id(),
+                })
+                .into())
+            }
+        };
+        Ok(PreparedChainInfo {
+            info: self.clone(),
+            verifier: PreparedVerifier::new(scheme.dst(), &public_key, &self.public_key),
+            scheme,
+        })
+    }
+
+    /// Whether the chain hash is the hash of the chain info, as derived by drand.
+    /// It is `sha256(period || genesis_time || public_key || group_hash || beacon_id)`, with the period as a big-endian `u32`, and the genesis time as a big-endian `i64`.
+    /// Beacon ID is omitted for the default beacon, such as mainnet. The scheme is not hashed.
+    /// Fails with [`BeaconError::Parsing`] if the period or genesis time do not fit these types.
+    pub fn verify_hash(&self) -> Result<bool> {
+        let parsing = |_| -> DrandError { Box::new(BeaconError::Parsing).into() };
+        let period = u32::try_from(self.period).map_err(parsing)?;
+        let genesis_time = i64::try_from(self.genesis_time).map_err(parsing)?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(period.to_be_bytes());
+        hasher.update(genesis_time.to_be_bytes());
+        hasher.update(&self.public_key);
+        hasher.update(&self.group_hash);
+        if !matches!(self.metadata.beacon_id.as_str(), "" | "default") {
+            hasher.update(self.metadata.beacon_id.as_bytes());
+        }
+        Ok(hasher.finalize().as_slice() == self.hash.as_slice())
+    }
+
+    /// Genesis and period of the chain.
+    pub fn time_info(&self) -> ChainTimeInfo {
+        ChainTimeInfo::new(self.genesis_time, self.period)
+    }
+
+    /// Latest round produced according to the system clock, 0 if the chain has not started yet.
+    /// See [`ChainTimeInfo::current_round`] to provide the current time instead.
+    #[cfg(feature = "std")]
+    pub fn current_round(&self) -> u64 {
+        self.time_info().current_round(unix_now())
+    }
 }
 
+/// Chain info are equal when all their known fields are. Unknown fields, see [`ChainInfo::extra`], are ignored, as they are not part of the chain hash.
 impl PartialEq for ChainInfo {
     fn eq(&self, other: &Self) -> bool {
         self.public_key == other.public_key
@@ -102,16 +574,65 @@ impl PartialEq for ChainInfo {
     }
 }
 
+impl Eq for ChainInfo {}
+
+/// Chain info is hashed by its chain hash, which identifies the chain. Equal chain info have the same chain hash.
+impl Hash for ChainInfo {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hash.hash(state);
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Chain info with its public key deserialized, as returned by [`ChainInfo::prepare`].
+/// Verifying beacons against it does not deserialize and check the public key, nor prepare it for pairings, each time.
+pub struct PreparedChainInfo {
+    info: ChainInfo,
+    verifier: PreparedVerifier,
+    scheme: SchemeDescriptor,
+}
+
+impl PreparedChainInfo {
+    pub fn info(&self) -> &ChainInfo {
+        &self.info
+    }
+
+    /// Descriptor beacons are verified with.
+    pub fn scheme(&self) -> &SchemeDescriptor {
+        &self.scheme
+    }
+
+    pub(crate) fn verifier(&self) -> &PreparedVerifier {
+        &self.verifier
+    }
+}
+
 #[derive(Debug, Clone)]
 /// Retrieval and validation options when interacting with a chain.
 /// This controls beacons validation, chain validation, and cache on retrieval.
 pub struct ChainOptions {
     is_beacon_verification: bool,
     is_cache: bool,
+    cache_ttl: Option<Duration>,
+    clock_skew: Duration,
+    is_monotonic: bool,
+    is_url_chain_hash: bool,
+    is_require_https: bool,
+    is_cache_bust_latest: bool,
+    is_remote_clock: bool,
+    is_verify_chain_hash: bool,
+    verify_options: VerifyOptions,
+    scheme_registry: SchemeRegistry,
     chain_verification: ChainVerification,
 }
 
 impl ChainOptions {
+    /// Options built with named setters. See [`ChainOptionsBuilder`].
+    pub fn builder() -> ChainOptionsBuilder {
+        ChainOptionsBuilder::default()
+    }
+
+    /// Prefer [`ChainOptions::builder`], which names each option.
     pub fn new(
         is_beacon_verification: bool,
         is_cache: bool,
@@ -120,6 +641,16 @@ impl ChainOptions {
         Self {
             is_beacon_verification,
             is_cache,
+            cache_ttl: None,
+            clock_skew: Duration::ZERO,
+            is_monotonic: false,
+            is_url_chain_hash: true,
+            is_require_https: false,
+            is_cache_bust_latest: false,
+            is_remote_clock: false,
+            is_verify_chain_hash: false,
+            verify_options: VerifyOptions::default(),
+            scheme_registry: SchemeRegistry::default(),
             chain_verification: chain_verification.unwrap_or_default(),
         }
     }
@@ -132,6 +663,115 @@ impl ChainOptions {
         self.is_cache
     }
 
+    /// Cached chain info is retrieved and verified again once `ttl` has elapsed.
+    /// Without it, chain info is cached for the lifetime of the client.
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = Some(ttl);
+        self
+    }
+
+    pub fn cache_ttl(&self) -> Option<Duration> {
+        self.cache_ttl
+    }
+
+    /// Clock skew tolerated between the local clock and the remote one when inferring the latest round.
+    /// If the inferred round is not found, the previous round is retrieved instead, once.
+    /// Defaults to zero, in which case the inferred round has to exist.
+    pub fn with_clock_skew(mut self, tolerance: Duration) -> Self {
+        self.clock_skew = tolerance;
+        self
+    }
+
+    pub fn clock_skew(&self) -> Duration {
+        self.clock_skew
+    }
+
+    /// Whether the latest beacon has to be at least as recent as any beacon previously retrieved by the client.
+    /// This protects against a remote rolling back, or replayed responses. Historical rounds can still be retrieved explicitly.
+    pub fn with_monotonic(mut self, is_monotonic: bool) -> Self {
+        self.is_monotonic = is_monotonic;
+        self
+    }
+
+    pub fn is_monotonic(&self) -> bool {
+        self.is_monotonic
+    }
+
+    /// Whether a chain hash ending the base URL, such as `https://api.drand.sh/<chain hash>/`, is used to verify the chain info served by the remote.
+    /// Enabled by default, it can be disabled for remotes with a different URL layout.
+    pub fn with_url_chain_hash(mut self, is_url_chain_hash: bool) -> Self {
+        self.is_url_chain_hash = is_url_chain_hash;
+        self
+    }
+
+    pub fn is_url_chain_hash(&self) -> bool {
+        self.is_url_chain_hash
+    }
+
+    /// Whether clients refuse base URLs not using HTTPS, as chain info retrieved in cleartext could be tampered with.
+    /// Loopback hosts, such as `localhost`, are allowed regardless. Disabled by default.
+    pub fn with_require_https(mut self, is_require_https: bool) -> Self {
+        self.is_require_https = is_require_https;
+        self
+    }
+
+    pub fn is_require_https(&self) -> bool {
+        self.is_require_https
+    }
+
+    /// Whether requests for the latest beacon carry a random query parameter, so caches in front of the remote do not serve a stale one.
+    /// Numbered rounds are immutable, and are never busted. Disabled by default.
+    pub fn with_cache_bust_latest(mut self, is_cache_bust_latest: bool) -> Self {
+        self.is_cache_bust_latest = is_cache_bust_latest;
+        self
+    }
+
+    pub fn is_cache_bust_latest(&self) -> bool {
+        self.is_cache_bust_latest
+    }
+
+    /// Whether rounds are inferred from the remote clock, as measured with the `Date` header of its responses, when it differs from the local clock by more than the tolerated clock skew.
+    /// Disabled by default, in which case the local clock is trusted.
+    pub fn with_remote_clock(mut self, is_remote_clock: bool) -> Self {
+        self.is_remote_clock = is_remote_clock;
+        self
+    }
+
+    pub fn is_remote_clock(&self) -> bool {
+        self.is_remote_clock
+    }
+
+    /// Whether chain info retrieved by clients has to hash to its chain hash, see [`ChainInfo::verify_hash`].
+    /// This catches a remote serving a tampered public key alongside a legitimate chain hash, without pinning the public key. Disabled by default.
+    pub fn with_verify_chain_hash(mut self, is_verify_chain_hash: bool) -> Self {
+        self.is_verify_chain_hash = is_verify_chain_hash;
+        self
+    }
+
+    pub fn is_verify_chain_hash(&self) -> bool {
+        self.is_verify_chain_hash
+    }
+
+    /// Checks performed on top of beacon signatures when beacon verification is enabled, such as the randomness being the hash of the signature.
+    pub fn with_verify_options(mut self, verify_options: VerifyOptions) -> Self {
+        self.verify_options = verify_options;
+        self
+    }
+
+    pub fn verify_options(&self) -> &VerifyOptions {
+        &self.verify_options
+    }
+
+    /// Schemes beacons can be verified with, in addition to the ones of drand.
+    pub fn with_scheme_registry(mut self, scheme_registry: SchemeRegistry) -> Self {
+        self.scheme_registry = scheme_registry;
+        self
+    }
+
+    pub fn scheme_registry(&self) -> &SchemeRegistry {
+        &self.scheme_registry
+    }
+
     pub fn verify(&self, info: &ChainInfo) -> bool {
         self.chain_verification.verify(info)
     }
@@ -143,16 +783,78 @@ impl Default for ChainOptions {
     }
 }
 
+/// Builder for [`ChainOptions`], starting from [`ChainOptions::default`].
+/// Options not set by the builder are set on the built options, such as [`ChainOptions::with_cache_ttl`].
+#[derive(Debug, Clone, Default)]
+pub struct ChainOptionsBuilder {
+    options: ChainOptions,
+}
+
+impl ChainOptionsBuilder {
+    /// Whether beacons are verified against chain info. Defaults to true.
+    pub fn verify_beacons(mut self, is_beacon_verification: bool) -> Self {
+        self.options.is_beacon_verification = is_beacon_verification;
+        self
+    }
+
+    /// Whether chain info is cached. Defaults to true.
+    pub fn cache(mut self, is_cache: bool) -> Self {
+        self.options.is_cache = is_cache;
+        self
+    }
+
+    /// Hash the chain info has to match.
+    pub fn pin_hash(mut self, hash: &[u8]) -> Self {
+        self.options.chain_verification.hash = Some(hash.to_vec());
+        self
+    }
+
+    /// Public key the chain info has to match.
+    pub fn pin_public_key(mut self, public_key: &[u8]) -> Self {
+        self.options.chain_verification.public_key = Some(public_key.to_vec());
+        self
+    }
+
+    /// Parameters the chain info has to match, replacing pinned hash and public key.
+    pub fn chain_verification(mut self, chain_verification: ChainVerification) -> Self {
+        self.options.chain_verification = chain_verification;
+        self
+    }
+
+    pub fn build(self) -> ChainOptions {
+        self.options
+    }
+}
+
 #[derive(Debug, Clone)]
 /// Parameters that can be used to validate a chain is the expected one.
 pub struct ChainVerification {
     hash: Option<Vec<u8>>,
     public_key: Option<Vec<u8>>,
+    beacon_id: Option<String>,
+    scheme: Option<Scheme>,
 }
 
 impl ChainVerification {
     pub fn new(hash: Option<Vec<u8>>, public_key: Option<Vec<u8>>) -> Self {
-        Self { hash, public_key }
+        Self {
+            hash,
+            public_key,
+            beacon_id: None,
+            scheme: None,
+        }
+    }
+
+    /// Beacon ID the chain is expected to have in its metadata, such as `quicknet` or `default`.
+    pub fn with_beacon_id(mut self, beacon_id: &str) -> Self {
+        self.beacon_id = Some(beacon_id.to_owned());
+        self
+    }
+
+    /// Scheme the chain is expected to use, such as an unchained one for timelock encryption.
+    pub fn with_scheme(mut self, scheme: Scheme) -> Self {
+        self.scheme = Some(scheme);
+        self
     }
 
     pub fn verify(&self, info: &ChainInfo) -> bool {
@@ -164,7 +866,15 @@ impl ChainVerification {
             Some(pk) => info.public_key == *pk,
             None => true,
         };
-        ok_hash && ok_public_key
+        let ok_beacon_id = match &self.beacon_id {
+            Some(id) => info.metadata.beacon_id == *id,
+            None => true,
+        };
+        let ok_scheme = match &self.scheme {
+            Some(scheme) => info.scheme() == *scheme,
+            None => true,
+        };
+        ok_hash && ok_public_key && ok_beacon_id && ok_scheme
     }
 }
 
@@ -181,29 +891,84 @@ impl From<ChainInfo> for ChainVerification {
 }
 
 /// Drand client, that can retrieve and validate information from a given chain.
-pub trait ChainClient {
+/// The trait is object safe, so that clients can be swapped behind a `Box<dyn ChainClient>`, and shared across threads.
+pub trait ChainClient: Send + Sync {
     /// Options that are used to validate chain result.
     fn options(&self) -> ChainOptions;
     /// Retrieve latest beacon.
     /// This is retrieved and validated based on the client options.
     fn latest(&self) -> Result<RandomnessBeacon>;
-    /// Retrieve specific round beacon.
+    /// Retrieve specific round beacon. Round 0 retrieves the latest beacon, as [`Self::latest`] does.
     /// This is retrieved and validated based on the client options.
     fn get(&self, round_number: u64) -> Result<RandomnessBeacon>;
     /// Chain info the client is associated to.
     fn chain_info(&self) -> Result<ChainInfo>;
 }
 
-#[cfg(feature = "time")]
+impl<C: ChainClient + ?Sized> ChainClient for Box<C> {
+    fn options(&self) -> ChainOptions {
+        (**self).options()
+    }
+
+    fn latest(&self) -> Result<RandomnessBeacon> {
+        (**self).latest()
+    }
+
+    fn get(&self, round_number: u64) -> Result<RandomnessBeacon> {
+        (**self).get(round_number)
+    }
+
+    fn chain_info(&self) -> Result<ChainInfo> {
+        (**self).chain_info()
+    }
+}
+
+impl<C: ChainClient + ?Sized> ChainClient for Arc<C> {
+    fn options(&self) -> ChainOptions {
+        (**self).options()
+    }
+
+    fn latest(&self) -> Result<RandomnessBeacon> {
+        (**self).latest()
+    }
+
+    fn get(&self, round_number: u64) -> Result<RandomnessBeacon> {
+        (**self).get(round_number)
+    }
+
+    fn chain_info(&self) -> Result<ChainInfo> {
+        (**self).chain_info()
+    }
+}
+
+/// Asynchronous counterpart of [`ChainClient`].
+/// Retrieval and validation behave the same, allowing downstream code to be generic over either.
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+pub trait AsyncChainClient {
+    /// Options that are used to validate chain result.
+    fn options(&self) -> ChainOptions;
+    /// Retrieve latest beacon.
+    /// This is retrieved and validated based on the client options.
+    async fn latest(&self) -> Result<RandomnessBeacon>;
+    /// Retrieve specific round beacon. Round 0 retrieves the latest beacon, as [`Self::latest`] does.
+    /// This is retrieved and validated based on the client options.
+    async fn get(&self, round_number: u64) -> Result<RandomnessBeacon>;
+    /// Chain info the client is associated to.
+    async fn chain_info(&self) -> Result<ChainInfo>;
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 /// Time information for a chain.
 /// Genesis and period, allowing to reconstruct time information of a given beacon.
+///
+/// Round 1 is produced at genesis, and round `n` at `genesis + (n - 1) * period`.
+/// A round covers the period from its production up to, but excluding, the production of the next round.
 pub struct ChainTimeInfo {
     genesis_time: u64,
     period: u64,
 }
 
-#[cfg(feature = "time")]
 impl ChainTimeInfo {
     pub fn new(genesis_time: u64, period: u64) -> Self {
         Self {
@@ -219,6 +984,79 @@ impl ChainTimeInfo {
     pub fn period(&self) -> u64 {
         self.period
     }
+
+    /// Latest round produced at `unix_time`, in seconds. Times before genesis return 0, as no round has been produced yet.
+    pub fn round_at(&self, unix_time: u64) -> u64 {
+        match unix_time.checked_sub(self.genesis_time) {
+            Some(elapsed) => elapsed / self.period + 1,
+            None => 0,
+        }
+    }
+
+    /// Unix time, in seconds, at which `round` is produced. Round 0 does not exist, and is given genesis time as round 1 is.
+    /// Times beyond `u64::MAX` saturate.
+    pub fn time_of_round(&self, round: u64) -> u64 {
+        self.genesis_time
+            .saturating_add(round.saturating_sub(1).saturating_mul(self.period))
+    }
+
+    /// Rounds produced from `start_unix` to `end_unix` included, in seconds, with the unix time each is produced at.
+    /// Windows ending before genesis, or starting after they end, are empty. Windows spanning genesis start at round 1.
+    pub fn rounds_between(
+        &self,
+        start_unix: u64,
+        end_unix: u64,
+    ) -> impl Iterator<Item = (u64, u64)> {
+        let info = Self::new(self.genesis_time, self.period);
+        // first round produced at, or after, the start of the window
+        let first = match start_unix.checked_sub(self.genesis_time) {
+            Some(elapsed) if elapsed % self.period == 0 => elapsed / self.period + 1,
+            Some(elapsed) => (elapsed / self.period).saturating_add(2),
+            None => 1,
+        };
+        let last = match start_unix <= end_unix {
+            true => self.round_at(end_unix),
+            false => 0,
+        };
+        (first..=last).map(move |round| (round, info.time_of_round(round)))
+    }
+
+    /// Date at which `round` is produced, see [`Self::time_of_round`].
+    /// Fails with [`BeaconError::TimeOutOfRange`] if it cannot be represented, such as for an absurd genesis time.
+    #[cfg(feature = "time")]
+    pub fn datetime_of_round(&self, round: u64) -> Result<time::OffsetDateTime> {
+        i64::try_from(self.time_of_round(round))
+            .ok()
+            .and_then(|unix_time| time::OffsetDateTime::from_unix_timestamp(unix_time).ok())
+            .ok_or_else(|| Box::new(BeaconError::TimeOutOfRange).into())
+    }
+
+    /// Latest round produced when the clock reads `now`, in unix seconds, 0 if the chain has not started yet.
+    /// The clock is provided by the caller, so that it can be used without `std`, or with another clock than the system one.
+    pub fn current_round(&self, now: u64) -> u64 {
+        self.round_at(now)
+    }
+}
+
+impl From<ChainInfo> for ChainTimeInfo {
+    fn from(value: ChainInfo) -> Self {
+        value.time_info()
+    }
+}
+
+/// Current unix time, in seconds.
+/// wasm32-unknown-unknown has no system clock, so the JavaScript one is used instead.
+#[cfg(all(feature = "std", not(all(feature = "wasm", target_arch = "wasm32"))))]
+pub(crate) fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+pub(crate) fn unix_now() -> u64 {
+    (js_sys::Date::now() / 1000.0) as u64
 }
 
 #[cfg(test)]
@@ -285,6 +1123,244 @@ pub mod tests {
         }"#).unwrap()
     }
 
+    #[test]
+    fn scheme_works() {
+        assert_eq!(chained_chain_info().scheme(), Scheme::PedersenBlsChained);
+        assert_eq!(
+            unchained_chain_info().scheme(),
+            Scheme::PedersenBlsUnchained
+        );
+        assert_eq!(
+            unchained_chain_on_g1_info().scheme(),
+            Scheme::BlsUnchainedOnG1
+        );
+        assert_eq!(
+            unchained_chain_on_g1_rfc_info().scheme(),
+            Scheme::BlsUnchainedG1Rfc9380
+        );
+
+        let scheme: Scheme = "bls-bls24-unchained-on-g1".parse().unwrap();
+        assert_eq!(
+            scheme,
+            Scheme::Unknown("bls-bls24-unchained-on-g1".to_string())
+        );
+        assert!(!scheme.is_g1());
+        assert!(!scheme.is_unchained());
+        assert_eq!(scheme.signature_len(), None);
+        assert_eq!(Scheme::PedersenBlsChained.signature_len(), Some(96));
+        assert_eq!(Scheme::BlsUnchainedG1Rfc9380.signature_len(), Some(48));
+        assert_eq!(Scheme::BlsBn254UnchainedOnG1.signature_len(), Some(64));
+
+        for info in [
+            chained_chain_info(),
+            unchained_chain_info(),
+            unchained_chain_on_g1_info(),
+            unchained_chain_on_g1_rfc_info(),
+        ] {
+            assert_eq!(info.scheme().to_string(), info.scheme_id());
+        }
+    }
+
+    #[test]
+    fn chain_time_info_works() {
+        let time_info = chained_chain_info().time_info();
+        let genesis_time = time_info.genesis_time();
+        let period = time_info.period();
+
+        // before genesis
+        assert_eq!(time_info.round_at(0), 0);
+        assert_eq!(time_info.round_at(genesis_time - 1), 0);
+        // at genesis
+        assert_eq!(time_info.round_at(genesis_time), 1);
+        assert_eq!(time_info.time_of_round(1), genesis_time);
+        assert_eq!(time_info.time_of_round(0), genesis_time);
+        // one second before a period boundary, and exactly on it
+        assert_eq!(time_info.round_at(genesis_time + period - 1), 1);
+        assert_eq!(time_info.round_at(genesis_time + period), 2);
+        assert_eq!(time_info.time_of_round(2), genesis_time + period);
+        assert_eq!(
+            time_info.round_at(genesis_time + 999_999 * period - 1),
+            999_999
+        );
+        assert_eq!(
+            time_info.round_at(genesis_time + 999_999 * period),
+            1_000_000
+        );
+        assert_eq!(
+            time_info.time_of_round(1_000_000),
+            genesis_time + 999_999 * period
+        );
+
+        // a round is produced at its own time
+        for round in [1, 2, 1000, 1_000_000] {
+            assert_eq!(time_info.round_at(time_info.time_of_round(round)), round);
+            assert_eq!(
+                time_info.round_at(time_info.time_of_round(round + 1) - 1),
+                round
+            );
+        }
+
+        // beacon time agrees on boundaries
+        #[cfg(feature = "time")]
+        for unix_time in [
+            genesis_time,
+            genesis_time + period - 1,
+            genesis_time + period,
+        ] {
+            let time =
+                crate::beacon::RandomnessBeaconTime::parse(&time_info, &format!("@{unix_time}"))
+                    .unwrap();
+            assert_eq!(time.round(), time_info.round_at(unix_time));
+            let time =
+                crate::beacon::RandomnessBeaconTime::from_round(&time_info, time.round()).unwrap();
+            assert_eq!(
+                time.absolute().unix_timestamp() as u64,
+                time_info.time_of_round(time_info.round_at(unix_time))
+            );
+        }
+    }
+
+    #[test]
+    fn chain_time_info_current_round_works() {
+        let time_info = ChainTimeInfo::new(1_000, 30);
+        // no round before genesis, round 1 at genesis
+        assert_eq!(time_info.current_round(0), 0);
+        assert_eq!(time_info.current_round(999), 0);
+        assert_eq!(time_info.current_round(1_000), 1);
+        // a round lasts until the next one is produced, exactly on the period edge
+        assert_eq!(time_info.current_round(1_029), 1);
+        assert_eq!(time_info.current_round(1_030), 2);
+        assert_eq!(time_info.current_round(1_000 + 30 * 99), 100);
+        assert_eq!(
+            time_info.current_round(u64::MAX),
+            (u64::MAX - 1_000) / 30 + 1
+        );
+
+        // drand mainnet round 1000000 was produced at 1625431020
+        let time_info = chained_chain_info().time_info();
+        assert_eq!(time_info.current_round(1625431020), 1000000);
+        assert_eq!(time_info.current_round(1625431049), 1000000);
+        assert_eq!(time_info.current_round(1625431050), 1000001);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn chain_info_current_round_works() {
+        let info = chained_chain_info();
+        let before = info.time_info().current_round(unix_now());
+        let current_round = info.current_round();
+        assert!(current_round > 1_000_000);
+        assert!(current_round >= before);
+        assert!(current_round <= info.time_info().current_round(unix_now()));
+
+        // the chain starts in the future, so no round has been produced yet
+        let mut future = serde_json::to_value(&info).unwrap();
+        future["genesis_time"] = (unix_now() + 3600).into();
+        let future: ChainInfo = serde_json::from_value(future).unwrap();
+        assert_eq!(future.current_round(), 0);
+    }
+
+    #[test]
+    fn chain_time_info_round_boundaries_works() {
+        let time_info = ChainTimeInfo::new(1_000, 30);
+        // at genesis, round 1 is produced
+        assert_eq!(time_info.round_at(999), 0);
+        assert_eq!(time_info.round_at(1_000), 1);
+        assert_eq!(time_info.time_of_round(1), 1_000);
+        // exactly on a period edge, the next round is produced
+        assert_eq!(time_info.round_at(1_029), 1);
+        assert_eq!(time_info.round_at(1_030), 2);
+        assert_eq!(time_info.time_of_round(2), 1_030);
+        assert_eq!(time_info.round_at(1_000 + 99 * 30), 100);
+        assert_eq!(time_info.round_at(1_000 + 99 * 30 - 1), 99);
+        // rounds and times agree on every edge
+        for round in 1..=100 {
+            assert_eq!(time_info.round_at(time_info.time_of_round(round)), round);
+        }
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn chain_info_time_accessors_works() {
+        let info = chained_chain_info();
+        assert_eq!(info.period_duration().unwrap(), time::Duration::seconds(30));
+        assert_eq!(
+            info.genesis_datetime().unwrap(),
+            time::OffsetDateTime::from_unix_timestamp(1595431050).unwrap()
+        );
+
+        // values beyond the representable range fail instead of panicking
+        let mut absurd = serde_json::to_value(&info).unwrap();
+        absurd["genesis_time"] = u64::MAX.into();
+        absurd["period"] = u64::MAX.into();
+        let absurd: ChainInfo = serde_json::from_value(absurd).unwrap();
+        for result in [
+            absurd.genesis_datetime().map(|_| ()),
+            absurd.period_duration().map(|_| ()),
+            absurd.time_info().datetime_of_round(2).map(|_| ()),
+        ] {
+            match result {
+                Err(DrandError::Beacon(e)) => assert!(matches!(*e, BeaconError::TimeOutOfRange)),
+                _ => panic!("time should be out of range"),
+            }
+        }
+        assert_eq!(absurd.time_info().time_of_round(2), u64::MAX);
+    }
+
+    #[test]
+    fn chain_time_info_rounds_between_works() {
+        let time_info = ChainTimeInfo::new(1_000, 30);
+        let rounds = |start, end| time_info.rounds_between(start, end).collect::<Vec<_>>();
+
+        // window before genesis
+        assert!(rounds(0, 999).is_empty());
+        // window spanning genesis
+        assert_eq!(rounds(0, 1_000), vec![(1, 1_000)]);
+        assert_eq!(rounds(900, 1_060), vec![(1, 1_000), (2, 1_030), (3, 1_060)]);
+        // window on round edges includes both ends
+        assert_eq!(rounds(1_030, 1_060), vec![(2, 1_030), (3, 1_060)]);
+        assert_eq!(rounds(1_030, 1_030), vec![(2, 1_030)]);
+        // window between round edges
+        assert!(rounds(1_031, 1_059).is_empty());
+        assert_eq!(rounds(1_001, 1_030), vec![(2, 1_030)]);
+        assert_eq!(rounds(1_029, 1_089), vec![(2, 1_030), (3, 1_060)]);
+        // degenerate window
+        assert!(rounds(1_060, 1_030).is_empty());
+        assert!(rounds(1_060, 999).is_empty());
+
+        // every round of a window is produced in it, and rounds are consecutive
+        for start in 990..1_100 {
+            for end in start..1_200 {
+                let rounds = rounds(start, end);
+                for (round, time) in &rounds {
+                    assert!((start..=end).contains(time));
+                    assert_eq!(time_info.round_at(*time), *round);
+                }
+                let expected = (start..=end)
+                    .filter(|time| *time >= 1_000 && (time - 1_000) % 30 == 0)
+                    .count();
+                assert_eq!(rounds.len(), expected);
+            }
+        }
+
+        // the end of time does not overflow
+        let last = ChainTimeInfo::new(0, 2).rounds_between(u64::MAX - 1, u64::MAX);
+        assert_eq!(last.count(), 1);
+    }
+
+    #[test]
+    fn round_at_works() {
+        let info = chained_chain_info();
+        let genesis_time = info.genesis_time();
+        assert_eq!(info.time_info().round_at(genesis_time - 1), 0);
+        assert_eq!(info.time_info().round_at(genesis_time), 1);
+        assert_eq!(
+            info.time_info().round_at(genesis_time + info.period() - 1),
+            1
+        );
+        assert_eq!(info.time_info().round_at(genesis_time + info.period()), 2);
+    }
+
     #[test]
     fn chain_verification_success_works() {
         // Full validation should pass
@@ -315,6 +1391,332 @@ pub mod tests {
         assert!(no_verification.verify(&chained_chain_info()));
     }
 
+    #[test]
+    fn chain_info_eq_hash_works() {
+        use std::collections::HashSet;
+
+        assert_eq!(chained_chain_info(), chained_chain_info());
+        assert_ne!(chained_chain_info(), unchained_chain_info());
+
+        let chains: HashSet<ChainInfo> = [
+            chained_chain_info(),
+            unchained_chain_info(),
+            chained_chain_info(),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(chains.len(), 2);
+        assert!(chains.contains(&unchained_chain_info()));
+        assert!(!chains.contains(&unchained_chain_on_g1_info()));
+    }
+
+    #[test]
+    fn chain_info_new_works() {
+        let info = unchained_chain_on_g1_info();
+        let new = |public_key: Vec<u8>, hash: Vec<u8>, scheme_id: &str| {
+            ChainInfo::new(
+                public_key,
+                info.period(),
+                info.genesis_time(),
+                hash,
+                info.group_hash(),
+                scheme_id.to_owned(),
+                info.metadata(),
+            )
+        };
+        assert_eq!(
+            new(info.public_key(), info.hash(), &info.scheme_id()).unwrap(),
+            info
+        );
+
+        let invalid_length = |result: Result<ChainInfo>| match result {
+            Err(DrandError::Beacon(e)) => match *e {
+                BeaconError::InvalidLength {
+                    field,
+                    expected,
+                    got,
+                } => (field, expected, got),
+                e => panic!("chain info should have an invalid length, got {e}"),
+            },
+            Err(e) => panic!("chain info should have an invalid length, got {e}"),
+            Ok(_) => panic!("chain info should have an invalid length"),
+        };
+        // G1 public key of a scheme with signatures on G1
+        assert_eq!(
+            invalid_length(new(
+                unchained_chain_info().public_key(),
+                info.hash(),
+                &info.scheme_id()
+            )),
+            ("public key", 96, 48)
+        );
+        assert_eq!(
+            invalid_length(new(
+                info.public_key(),
+                info.hash()[..31].to_vec(),
+                &info.scheme_id()
+            )),
+            ("hash", 32, 31)
+        );
+        // public keys of unknown schemes are checked once prepared
+        assert!(new(vec![0u8; 3], info.hash(), "bls-bls24-unchained-on-g1").is_ok());
+    }
+
+    #[test]
+    fn chain_info_zero_period_fails() {
+        let info = unchained_chain_info();
+        match ChainInfo::new(
+            info.public_key(),
+            0,
+            info.genesis_time(),
+            info.hash(),
+            info.group_hash(),
+            info.scheme_id(),
+            info.metadata(),
+        ) {
+            Err(DrandError::Beacon(e)) => assert!(matches!(*e, BeaconError::InvalidPeriod)),
+            Err(e) => panic!("chain info should have an invalid period, got {e}"),
+            Ok(_) => panic!("chain info should have an invalid period"),
+        }
+
+        // as served by a relay, rounds of this chain would divide by zero
+        let mut served = serde_json::to_value(&info).unwrap();
+        served["period"] = 0.into();
+        let err = serde_json::from_value::<ChainInfo>(served).unwrap_err();
+        assert!(err.to_string().contains("invalid chain period"));
+    }
+
+    #[test]
+    fn chain_info_verify_hash_works() {
+        // drand quicknet (curl -sS https://api.drand.sh/52db9ba70e0cc0f6eaf7803dd07447a1f5477735fd3f661792ba94600c84e971/info)
+        let quicknet: ChainInfo = serde_json::from_str(r#"{
+            "public_key": "83cf0f2896adee7eb8b5f01fcad3912212c437e0073e911fb90022d3e760183c8c4b450b6a0a6c3ac6a5776a2d1064510d1fec758c921cc22b0e17e63aaf4bcb5ed66304de9cf809bd274ca73bab4af5a6e9c76a4bc09e76eae8991ef5ece45a",
+            "period": 3,
+            "genesis_time": 1692803367,
+            "hash": "52db9ba70e0cc0f6eaf7803dd07447a1f5477735fd3f661792ba94600c84e971",
+            "groupHash": "f477d5c89f21a17c863a7f937c6a6d15859414d2be09cd448d4279af331c5d3e",
+            "schemeID": "bls-unchained-g1-rfc9380",
+            "metadata": {
+                "beaconID": "quicknet"
+            }
+        }"#).unwrap();
+        for info in [
+            chained_chain_info(),
+            unchained_chain_info(),
+            unchained_chain_on_g1_info(),
+            quicknet.clone(),
+        ] {
+            assert!(info.verify_hash().unwrap(), "{:?}", info.metadata());
+        }
+
+        // public key, period, genesis time, group hash, and beacon ID are bound to the hash
+        let tampered = |public_key: Vec<u8>,
+                        period: u64,
+                        genesis_time: u64,
+                        group_hash: Vec<u8>,
+                        beacon_id: &str| {
+            ChainInfo::new(
+                public_key,
+                period,
+                genesis_time,
+                quicknet.hash(),
+                group_hash,
+                quicknet.scheme_id(),
+                ChainMetadata::new(beacon_id.to_owned()),
+            )
+            .unwrap()
+        };
+        let mut public_key = quicknet.public_key();
+        public_key[95] ^= 1;
+        let mut group_hash = quicknet.group_hash();
+        group_hash[0] ^= 1;
+        for info in [
+            tampered(public_key, 3, 1692803367, quicknet.group_hash(), "quicknet"),
+            tampered(
+                quicknet.public_key(),
+                30,
+                1692803367,
+                quicknet.group_hash(),
+                "quicknet",
+            ),
+            tampered(
+                quicknet.public_key(),
+                3,
+                1692803368,
+                quicknet.group_hash(),
+                "quicknet",
+            ),
+            tampered(quicknet.public_key(), 3, 1692803367, group_hash, "quicknet"),
+            tampered(
+                quicknet.public_key(),
+                3,
+                1692803367,
+                quicknet.group_hash(),
+                "default",
+            ),
+        ] {
+            assert!(!info.verify_hash().unwrap());
+        }
+        // scheme is not hashed, but the public key of the test chain is not the one of fastnet
+        assert!(!unchained_chain_on_g1_rfc_info().verify_hash().unwrap());
+
+        let info = tampered(
+            quicknet.public_key(),
+            1 << 32,
+            1692803367,
+            quicknet.group_hash(),
+            "quicknet",
+        );
+        assert!(info.verify_hash().is_err());
+    }
+
+    #[test]
+    fn chain_options_builder_works() {
+        let info = chained_chain_info();
+        let assert_equivalent = |built: ChainOptions, legacy: ChainOptions| {
+            assert_eq!(
+                built.is_beacon_verification(),
+                legacy.is_beacon_verification()
+            );
+            assert_eq!(built.is_cache(), legacy.is_cache());
+            for other in [
+                chained_chain_info(),
+                unchained_chain_info(),
+                unchained_chain_on_g1_info(),
+            ] {
+                assert_eq!(built.verify(&other), legacy.verify(&other));
+            }
+        };
+
+        assert_equivalent(ChainOptions::builder().build(), ChainOptions::default());
+        assert_equivalent(
+            ChainOptions::builder()
+                .verify_beacons(false)
+                .cache(false)
+                .build(),
+            ChainOptions::new(false, false, None),
+        );
+        assert_equivalent(
+            ChainOptions::builder()
+                .pin_hash(&info.hash())
+                .pin_public_key(&info.public_key())
+                .build(),
+            ChainOptions::new(
+                true,
+                true,
+                Some(ChainVerification::new(
+                    Some(info.hash()),
+                    Some(info.public_key()),
+                )),
+            ),
+        );
+        assert_equivalent(
+            ChainOptions::builder()
+                .pin_public_key(&unchained_chain_info().public_key())
+                .build(),
+            ChainOptions::new(
+                true,
+                true,
+                Some(ChainVerification::new(
+                    None,
+                    Some(unchained_chain_info().public_key()),
+                )),
+            ),
+        );
+        assert_equivalent(
+            ChainOptions::builder()
+                .verify_beacons(false)
+                .chain_verification(info.clone().into())
+                .build(),
+            ChainOptions::new(false, true, Some(info.clone().into())),
+        );
+
+        // pinned parameters are checked
+        let options = ChainOptions::builder().pin_hash(&info.hash()).build();
+        assert!(options.verify(&info));
+        assert!(!options.verify(&unchained_chain_info()));
+    }
+
+    #[test]
+    fn chain_info_without_metadata_works() {
+        // drand mainnet, as served by relays predating metadata
+        let json = r#"{
+            "public_key": "868f005eb8e6e4ca0a47c8a77ceaa5309a47978a7c71bc5cce96366b5d7a569937c529eeda66c7293784a9402801af31",
+            "period": 30,
+            "genesis_time": 1595431050,
+            "hash": "8990e7a9aaed2ffed73dbd7092123d6f289930540d7651336225dc172e51b2ce",
+            "groupHash": "176f93498eac9ca337150b46d21dd58673ea4e3581185f869672e59fa4cb390a",
+            "schemeID": "pedersen-bls-chained"
+        }"#;
+        let info: ChainInfo = serde_json::from_str(json).unwrap();
+        assert_eq!(info.metadata().beacon_id(), "default");
+        assert_eq!(info, chained_chain_info());
+        assert!(info.verify_hash().unwrap());
+
+        // default metadata is serialized, as it is served by newer relays
+        let mut expected: Value = serde_json::from_str(json).unwrap();
+        expected["metadata"] = serde_json::json!({ "beaconID": "default" });
+        assert_eq!(serde_json::to_value(&info).unwrap(), expected);
+
+        // metadata without a beacon ID is the default one too
+        let mut partial = expected.clone();
+        partial["metadata"] = serde_json::json!({});
+        let info: ChainInfo = serde_json::from_value(partial).unwrap();
+        assert_eq!(info.metadata().beacon_id(), "default");
+        assert_eq!(serde_json::to_value(&info).unwrap(), expected);
+    }
+
+    #[test]
+    fn chain_info_unknown_fields_works() {
+        // drand quicknet info, with fields added by newer versions
+        let json = r#"{
+            "public_key": "83cf0f2896adee7eb8b5f01fcad3912212c437e0073e911fb90022d3e760183c8c4b450b6a0a6c3ac6a5776a2d1064510d1fec758c921cc22b0e17e63aaf4bcb5ed66304de9cf809bd274ca73bab4af5a6e9c76a4bc09e76eae8991ef5ece45a",
+            "period": 3,
+            "genesis_time": 1692803367,
+            "genesis_seed": "f477d5c89f21a17c863a7f937c6a6d15859414d2be09cd448d4279af331c5d3e",
+            "chain_hash": "52db9ba70e0cc0f6eaf7803dd07447a1f5477735fd3f661792ba94600c84e971",
+            "hash": "52db9ba70e0cc0f6eaf7803dd07447a1f5477735fd3f661792ba94600c84e971",
+            "groupHash": "f477d5c89f21a17c863a7f937c6a6d15859414d2be09cd448d4279af331c5d3e",
+            "schemeID": "bls-unchained-g1-rfc9380",
+            "boundedStorage": { "maxRounds": 1000 },
+            "metadata": {
+                "beaconID": "quicknet",
+                "network": "mainnet"
+            }
+        }"#;
+        let info: ChainInfo = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            info.extra().get("boundedStorage"),
+            Some(&serde_json::json!({ "maxRounds": 1000 }))
+        );
+        assert_eq!(info.extra().len(), 3);
+        assert_eq!(
+            info.metadata().extra().get("network"),
+            Some(&Value::from("mainnet"))
+        );
+        assert!(info.verify_hash().unwrap());
+
+        // nothing is lost when serialized back
+        let expected: Value = serde_json::from_str(json).unwrap();
+        assert_eq!(serde_json::to_value(&info).unwrap(), expected);
+        let reloaded: ChainInfo = serde_json::from_value(expected).unwrap();
+        assert_eq!(reloaded.extra(), info.extra());
+
+        // unknown fields are ignored by equality
+        let known = ChainInfo::new(
+            info.public_key(),
+            info.period(),
+            info.genesis_time(),
+            info.hash(),
+            info.group_hash(),
+            info.scheme_id(),
+            ChainMetadata::new(info.metadata().beacon_id()),
+        )
+        .unwrap();
+        assert!(known.extra().is_empty());
+        assert_eq!(known, info);
+    }
+
     #[test]
     fn chain_verification_failure_works() {
         // Full validation should fail when public key is invalid
@@ -339,4 +1741,39 @@ pub mod tests {
             ChainVerification::new(None, Some(unchained_chain_info().public_key()));
         assert!(!public_key_verification.verify(&chained_chain_info()));
     }
+
+    #[test]
+    fn chain_verification_beacon_id_and_scheme_works() {
+        let info = unchained_chain_info();
+
+        // Validate only the beacon ID
+        let beacon_id_verification =
+            ChainVerification::default().with_beacon_id("testnet-unchained-3s");
+        assert!(beacon_id_verification.verify(&info));
+        assert!(!beacon_id_verification.verify(&chained_chain_info()));
+        assert!(!ChainVerification::default()
+            .with_beacon_id("default")
+            .verify(&info));
+
+        // Validate only the scheme, refusing chained beacons
+        let scheme_verification =
+            ChainVerification::default().with_scheme(Scheme::PedersenBlsUnchained);
+        assert!(scheme_verification.verify(&info));
+        assert!(!scheme_verification.verify(&chained_chain_info()));
+        assert!(!scheme_verification.verify(&unchained_chain_on_g1_info()));
+
+        // Each dimension fails independently of the others
+        let full_verification = ChainVerification::from(info.clone())
+            .with_beacon_id("testnet-unchained-3s")
+            .with_scheme(Scheme::PedersenBlsUnchained);
+        assert!(full_verification.verify(&info));
+        assert!(!ChainVerification::from(info.clone())
+            .with_beacon_id("default")
+            .with_scheme(Scheme::PedersenBlsUnchained)
+            .verify(&info));
+        assert!(!ChainVerification::from(info.clone())
+            .with_beacon_id("testnet-unchained-3s")
+            .with_scheme(Scheme::PedersenBlsChained)
+            .verify(&info));
+    }
 }
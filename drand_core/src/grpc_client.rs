@@ -0,0 +1,419 @@
+use std::sync::Mutex;
+
+use thiserror::Error;
+use tonic::{
+    transport::{Channel, ClientTlsConfig, Endpoint},
+    Code, Request, Status, Streaming,
+};
+
+use crate::{
+    beacon::{ApiBeacon, BeaconError, RandomnessBeacon},
+    chain::{ChainClient, ChainInfo, ChainOptions},
+    http_client::{verify_beacon, verify_chain_hash, verify_chain_info, PreparedChainInfoCache},
+    DrandError, Result,
+};
+
+/// Client of the drand node public API, generated from `proto/drand/api.proto` with tonic-build 0.11.
+#[allow(clippy::all, dead_code)]
+mod proto;
+
+use proto::{
+    public_client::PublicClient, ChainInfoRequest, Metadata, PublicRandRequest, PublicRandResponse,
+};
+
+#[derive(Error, Debug)]
+pub enum GrpcError {
+    #[error("invalid endpoint \"{endpoint}\"")]
+    InvalidEndpoint { endpoint: String },
+    #[error("failed to start runtime: {message}")]
+    Runtime { message: String },
+    #[error(transparent)]
+    Transport(#[from] Box<tonic::transport::Error>),
+    #[error("remote responded with {}: {}", .0.code(), .0.message())]
+    Status(#[from] Box<Status>),
+}
+
+impl GrpcError {
+    /// Whether the request might succeed if attempted again later, such as when the node is unavailable.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Transport(_) => true,
+            Self::Status(status) => matches!(
+                status.code(),
+                Code::Unavailable
+                    | Code::DeadlineExceeded
+                    | Code::ResourceExhausted
+                    | Code::Aborted
+            ),
+            _ => false,
+        }
+    }
+}
+
+/// Client for drand nodes exposing the gRPC public API, rather than an HTTP relay.
+/// Beacons and chain info are verified as they are with [`HttpClient`](crate::HttpClient).
+/// Requests are performed on a runtime owned by the client, which is therefore blocking, and should not be used from within an async runtime.
+pub struct GrpcClient {
+    client: PublicClient<Channel>,
+    runtime: tokio::runtime::Runtime,
+    options: ChainOptions,
+    chain_hash: Option<Vec<u8>>,
+    cached_chain_info: Mutex<Option<ChainInfo>>,
+    prepared_chain_info: PreparedChainInfoCache,
+}
+
+impl GrpcClient {
+    /// Client for the default chain of the node at `endpoint`, such as `https://drand.example.com:443`.
+    /// No connection is made until the first request.
+    pub fn new(endpoint: &str, options: Option<ChainOptions>) -> Result<Self> {
+        Self::connect(endpoint, None, options)
+    }
+
+    /// Client for the chain identified by `chain_hash`, for nodes following multiple chains.
+    /// Chain info served by the node has to be for this very chain.
+    pub fn for_chain(
+        endpoint: &str,
+        chain_hash: &[u8],
+        options: Option<ChainOptions>,
+    ) -> Result<Self> {
+        Self::connect(endpoint, Some(chain_hash.to_vec()), options)
+    }
+
+    fn connect(
+        endpoint: &str,
+        chain_hash: Option<Vec<u8>>,
+        options: Option<ChainOptions>,
+    ) -> Result<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| {
+                Box::new(GrpcError::Runtime {
+                    message: e.to_string(),
+                })
+            })?;
+        let mut channel = Endpoint::from_shared(endpoint.to_owned()).map_err(|_| {
+            Box::new(GrpcError::InvalidEndpoint {
+                endpoint: endpoint.to_owned(),
+            })
+        })?;
+        if channel.uri().scheme_str() == Some("https") {
+            channel = channel
+                .tls_config(ClientTlsConfig::new())
+                .map_err(|e| Box::new(GrpcError::Transport(Box::new(e))))?;
+        }
+        // the channel runs on the client runtime, which makes progress whenever a request is performed
+        let channel = {
+            let _guard = runtime.enter();
+            channel.connect_lazy()
+        };
+
+        Ok(Self {
+            client: PublicClient::new(channel),
+            runtime,
+            options: options.unwrap_or_default(),
+            chain_hash,
+            cached_chain_info: Mutex::new(None),
+            prepared_chain_info: PreparedChainInfoCache::default(),
+        })
+    }
+
+    pub fn options(&self) -> ChainOptions {
+        self.options.clone()
+    }
+
+    /// Metadata identifying the chain requests are for, if any.
+    fn metadata(&self) -> Option<Metadata> {
+        self.chain_hash.as_ref().map(|chain_hash| Metadata {
+            chain_hash: chain_hash.clone(),
+            ..Default::default()
+        })
+    }
+
+    pub fn chain_info(&self) -> Result<ChainInfo> {
+        if self.options.is_cache() {
+            if let Some(info) = self.cached_chain_info.lock().unwrap().clone() {
+                return Ok(info);
+            }
+        }
+
+        let request = Request::new(ChainInfoRequest {
+            metadata: self.metadata(),
+        });
+        let packet = self
+            .runtime
+            .block_on(self.client.clone().chain_info(request))
+            .map_err(status_failed)?
+            .into_inner();
+        let info = ChainInfo::try_from(packet)?;
+        if let Some(expected) = &self.chain_hash {
+            verify_chain_hash(expected, &info)?;
+        }
+        let info = verify_chain_info(&self.options, info)?;
+
+        if self.options.is_cache() {
+            *self.cached_chain_info.lock().unwrap() = Some(info.clone());
+        }
+        Ok(info)
+    }
+
+    pub fn latest(&self) -> Result<RandomnessBeacon> {
+        let info = self.chain_info()?;
+        let beacon = self.public_rand(0)?;
+        verify_beacon(
+            &self.options,
+            &self.prepared_chain_info,
+            info,
+            beacon,
+            "latest",
+        )
+    }
+
+    /// Retrieve the beacon for `round`. Round 0 retrieves the latest beacon.
+    pub fn get(&self, round: u64) -> Result<RandomnessBeacon> {
+        if round == 0 {
+            return self.latest();
+        }
+        let info = self.chain_info()?;
+        let beacon = self.public_rand(round)?;
+        verify_beacon(
+            &self.options,
+            &self.prepared_chain_info,
+            info,
+            beacon,
+            &round.to_string(),
+        )
+    }
+
+    /// Stream beacons as they are produced by the chain, over a single request.
+    /// Each beacon is validated based on the client options. The stream ends if the node closes it.
+    pub fn watch(&self) -> Result<BeaconStream<'_>> {
+        let info = self.chain_info()?;
+        let request = Request::new(PublicRandRequest {
+            round: 0,
+            metadata: self.metadata(),
+        });
+        let stream = self
+            .runtime
+            .block_on(self.client.clone().public_rand_stream(request))
+            .map_err(status_failed)?
+            .into_inner();
+        Ok(BeaconStream {
+            client: self,
+            info,
+            stream,
+        })
+    }
+
+    fn public_rand(&self, round: u64) -> Result<ApiBeacon> {
+        let request = Request::new(PublicRandRequest {
+            round,
+            metadata: self.metadata(),
+        });
+        let response = self
+            .runtime
+            .block_on(self.client.clone().public_rand(request))
+            .map_err(status_failed)?
+            .into_inner();
+        ApiBeacon::try_from(response)
+    }
+}
+
+impl ChainClient for GrpcClient {
+    fn options(&self) -> ChainOptions {
+        self.options()
+    }
+
+    fn latest(&self) -> Result<RandomnessBeacon> {
+        self.latest()
+    }
+
+    fn get(&self, round_number: u64) -> Result<RandomnessBeacon> {
+        self.get(round_number)
+    }
+
+    fn chain_info(&self) -> Result<ChainInfo> {
+        self.chain_info()
+    }
+}
+
+/// Iterator over beacons streamed by a node, as returned by [`GrpcClient::watch`].
+pub struct BeaconStream<'a> {
+    client: &'a GrpcClient,
+    info: ChainInfo,
+    stream: Streaming<PublicRandResponse>,
+}
+
+impl<'a> Iterator for BeaconStream<'a> {
+    type Item = Result<RandomnessBeacon>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let response = match self.client.runtime.block_on(self.stream.message()) {
+            Ok(response) => response?,
+            Err(status) => return Some(Err(status_failed(status))),
+        };
+        let round = response.round.to_string();
+        let beacon = match ApiBeacon::try_from(response) {
+            Ok(beacon) => beacon,
+            Err(err) => return Some(Err(err)),
+        };
+        Some(verify_beacon(
+            &self.client.options,
+            &self.client.prepared_chain_info,
+            self.info.clone(),
+            beacon,
+            &round,
+        ))
+    }
+}
+
+/// Missing rounds are reported as [`BeaconError::NotFound`], as they are by the HTTP client.
+fn status_failed(status: Status) -> DrandError {
+    match status.code() {
+        Code::NotFound => Box::new(BeaconError::NotFound).into(),
+        _ => Box::new(GrpcError::Status(Box::new(status))).into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use tonic::transport::Server;
+
+    use crate::{
+        beacon::tests::{chained_beacon, signed_chain},
+        chain::tests::{chained_chain_info, unchained_chain_info},
+        HttpClientError,
+    };
+
+    use super::{
+        proto::{
+            public_server::{Public, PublicServer},
+            ChainInfoPacket,
+        },
+        *,
+    };
+
+    /// Node serving `info`, and `beacons` indexed by round.
+    struct MockNode {
+        info: ChainInfo,
+        beacons: BTreeMap<u64, ApiBeacon>,
+    }
+
+    impl MockNode {
+        fn new(info: ChainInfo, beacons: Vec<ApiBeacon>) -> Self {
+            Self {
+                info,
+                beacons: beacons
+                    .into_iter()
+                    .map(|beacon| (beacon.round(), beacon))
+                    .collect(),
+            }
+        }
+    }
+
+    #[tonic::async_trait]
+    impl Public for MockNode {
+        async fn public_rand(
+            &self,
+            request: Request<PublicRandRequest>,
+        ) -> std::result::Result<tonic::Response<PublicRandResponse>, Status> {
+            let beacon = match request.into_inner().round {
+                0 => self.beacons.values().last(),
+                round => self.beacons.get(&round),
+            };
+            match beacon {
+                Some(beacon) => Ok(tonic::Response::new(PublicRandResponse::from(beacon))),
+                None => Err(Status::not_found("round not found")),
+            }
+        }
+
+        type PublicRandStreamStream =
+            tokio_stream::Iter<std::vec::IntoIter<std::result::Result<PublicRandResponse, Status>>>;
+
+        async fn public_rand_stream(
+            &self,
+            _request: Request<PublicRandRequest>,
+        ) -> std::result::Result<tonic::Response<Self::PublicRandStreamStream>, Status> {
+            let responses: Vec<_> = self
+                .beacons
+                .values()
+                .map(PublicRandResponse::from)
+                .map(Ok)
+                .collect();
+            Ok(tonic::Response::new(tokio_stream::iter(responses)))
+        }
+
+        async fn chain_info(
+            &self,
+            _request: Request<ChainInfoRequest>,
+        ) -> std::result::Result<tonic::Response<ChainInfoPacket>, Status> {
+            let packet = ChainInfoPacket::try_from(&self.info)
+                .map_err(|e| Status::internal(e.to_string()))?;
+            Ok(tonic::Response::new(packet))
+        }
+    }
+
+    /// Serve `node` in process, returning its endpoint.
+    fn serve(node: MockNode) -> String {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.set_nonblocking(true).unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            runtime.block_on(async {
+                let listener = tokio::net::TcpListener::from_std(listener).unwrap();
+                Server::builder()
+                    .add_service(PublicServer::new(node))
+                    .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener))
+                    .await
+                    .unwrap()
+            })
+        });
+        endpoint
+    }
+
+    #[test]
+    fn grpc_client_works() {
+        let endpoint = serve(MockNode::new(chained_chain_info(), vec![chained_beacon()]));
+
+        let client = GrpcClient::new(&endpoint, None).unwrap();
+        assert_eq!(client.chain_info().unwrap(), chained_chain_info());
+        let round = chained_beacon().round();
+        assert_eq!(client.get(round).unwrap().round(), round);
+        assert_eq!(client.latest().unwrap().round(), round);
+        match client.get(round + 1) {
+            Err(DrandError::Beacon(e)) => assert!(matches!(*e, BeaconError::NotFound)),
+            _ => panic!("missing round should not be found"),
+        }
+
+        // chain info served over gRPC is verified as well
+        let client =
+            GrpcClient::for_chain(&endpoint, &unchained_chain_info().hash(), None).unwrap();
+        match client.chain_info() {
+            Err(DrandError::HTTPClient(e)) => {
+                assert!(matches!(*e, HttpClientError::ChainHashMismatch { .. }))
+            }
+            _ => panic!("chain info for another chain should be rejected"),
+        }
+    }
+
+    #[test]
+    fn grpc_client_watch_works() {
+        let (info, beacons) = signed_chain(3);
+        let beacons = beacons.iter().map(RandomnessBeacon::beacon).collect();
+        let endpoint = serve(MockNode::new(info, beacons));
+
+        let client = GrpcClient::new(&endpoint, None).unwrap();
+        let rounds: Vec<u64> = client
+            .watch()
+            .unwrap()
+            .map(|beacon| beacon.unwrap().round())
+            .collect();
+        assert_eq!(rounds, vec![1, 2, 3]);
+    }
+}
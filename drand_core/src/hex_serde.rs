@@ -0,0 +1,108 @@
+//! Hex encoding of byte fields, as `hex::serde` does, with lenient decoding.
+//! Values can be prefixed with `0x`, and use any case, as emitted by Ethereum tooling. They are serialized lowercase, without prefix.
+
+use alloc::string::String;
+use core::fmt;
+
+use serde::{de::Error, Deserialize, Deserializer};
+
+pub(crate) use hex::serde::serialize;
+
+pub(crate) fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: hex::FromHex,
+    <T as hex::FromHex>::Error: fmt::Display,
+{
+    let value = String::deserialize(deserializer)?;
+    let digits = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+        .unwrap_or(&value);
+    T::from_hex(digits).map_err(Error::custom)
+}
+
+/// Deserialize a value which is present, for optional fields defaulting to `None` when absent.
+pub(crate) fn deserialize_some<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: hex::FromHex,
+    <T as hex::FromHex>::Error: fmt::Display,
+{
+    deserialize(deserializer).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Serialize;
+
+    use super::*;
+    use crate::{
+        beacon::{tests::chained_beacon, ApiBeacon},
+        chain::{tests::chained_chain_info, ChainInfo},
+    };
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Bytes(#[serde(with = "crate::hex_serde")] Vec<u8>);
+
+    fn parse(value: &str) -> Option<Vec<u8>> {
+        serde_json::from_value::<Bytes>(value.into())
+            .ok()
+            .map(|bytes| bytes.0)
+    }
+
+    #[test]
+    fn hex_serde_works() {
+        assert_eq!(parse("0a1bff"), Some(vec![0x0a, 0x1b, 0xff]));
+        assert_eq!(parse("0x0a1bff"), Some(vec![0x0a, 0x1b, 0xff]));
+        assert_eq!(parse("0X0A1BFF"), Some(vec![0x0a, 0x1b, 0xff]));
+        assert_eq!(parse("0x0A1bFf"), Some(vec![0x0a, 0x1b, 0xff]));
+        assert_eq!(parse(""), Some(vec![]));
+        assert_eq!(parse("0x"), Some(vec![]));
+
+        // odd length, and prefixes that are not exactly `0x`, fail
+        assert_eq!(parse("0a1"), None);
+        assert_eq!(parse("0x0a1"), None);
+        assert_eq!(parse("0x0x0a"), None);
+        assert_eq!(parse("x0a"), None);
+        assert_eq!(parse("0xzz"), None);
+
+        // serialization stays lowercase, without prefix
+        assert_eq!(
+            serde_json::to_string(&Bytes(vec![0x0a, 0x1b, 0xff])).unwrap(),
+            r#""0a1bff""#
+        );
+    }
+
+    #[test]
+    fn hex_serde_beacon_and_chain_info_works() {
+        let prefixed = |value: serde_json::Value| -> serde_json::Value {
+            let mut value = value;
+            for field in value.as_object_mut().unwrap().values_mut() {
+                let hex = field.as_str().filter(|hex| hex.len() >= 64);
+                if let Some(prefixed) = hex.map(|hex| format!("0x{}", hex.to_uppercase())) {
+                    *field = prefixed.into();
+                }
+            }
+            value
+        };
+
+        let beacon = serde_json::to_value(chained_beacon()).unwrap();
+        let parsed: ApiBeacon = serde_json::from_value(prefixed(beacon.clone())).unwrap();
+        assert_eq!(parsed, chained_beacon());
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), beacon);
+
+        let info = serde_json::to_value(chained_chain_info()).unwrap();
+        let parsed: ChainInfo = serde_json::from_value(prefixed(info.clone())).unwrap();
+        assert_eq!(parsed, chained_chain_info());
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), info);
+
+        // odd length signatures and hashes fail to parse
+        let mut beacon = beacon;
+        beacon["signature"] = format!("0x{}", &beacon["signature"].as_str().unwrap()[1..]).into();
+        assert!(serde_json::from_value::<ApiBeacon>(beacon).is_err());
+        let mut info = info;
+        info["hash"] = "0x0".into();
+        assert!(serde_json::from_value::<ChainInfo>(info).is_err());
+    }
+}
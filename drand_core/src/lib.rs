@@ -17,26 +17,294 @@
 //! // Get the latest beacon. By default, it verifies its signature against the chain info.
 //! let beacon = client.latest().unwrap();
 //!
-//! // Print the beacon
-//! println!("{:?}", beacon);
+//! // Print the beacon round, randomness, and signature
+//! println!("{beacon}");
 //! ```
+//!
+//! Without the default `std` feature, the crate is `no_std` and requires `alloc`. Beacon and chain info parsing and verification remain available.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+#[cfg(all(
+    feature = "http",
+    not(any(
+        feature = "tls-rustls-webpki",
+        feature = "tls-rustls-native",
+        feature = "tls-native",
+        target_arch = "wasm32"
+    ))
+))]
+compile_error!(
+    "drand_core HTTP clients require a TLS backend, enable one of the `tls-rustls-webpki`, `tls-rustls-native`, or `tls-native` features"
+);
 
 pub mod beacon;
-mod bls_signatures;
+pub use beacon::{verify_beacon_json, VerifiedBeacon, VerifyOptions};
+pub mod bls_signatures;
+pub use bls_signatures::BlsError;
 pub mod chain;
 pub use chain::ChainOptions;
+#[cfg(feature = "grpc")]
+mod grpc_client;
+#[cfg(feature = "grpc")]
+pub use grpc_client::{BeaconStream, GrpcClient, GrpcError};
+mod hex_serde;
+#[cfg(feature = "http")]
 mod http_client;
-pub use http_client::HttpClient;
-use thiserror::Error;
+#[cfg(feature = "async")]
+pub use http_client::AsyncHttpClient;
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+pub use http_client::FetchTransport;
+#[cfg(feature = "http")]
+pub use http_client::{
+    HttpClient, HttpClientBuilder, HttpClientError, RangeOptions, RetryPolicy, DEFAULT_USER_AGENT,
+};
+#[cfg(feature = "wasm")]
+pub use http_client::{HttpTransport, TransportClient};
+#[cfg(any(test, feature = "test-util"))]
+mod mock_client;
+#[cfg(any(test, feature = "test-util"))]
+pub use mock_client::MockChainClient;
+#[cfg(feature = "http")]
+mod observer;
+#[cfg(feature = "proto")]
+pub mod proto;
+#[cfg(feature = "http")]
+pub use observer::Observer;
+#[cfg(feature = "http")]
+mod quorum_client;
+#[cfg(feature = "http")]
+pub use quorum_client::{QuorumClient, QuorumError};
+#[cfg(feature = "http")]
+mod rate_limiter;
+#[cfg(feature = "http")]
+pub use rate_limiter::RateLimiter;
+#[cfg(feature = "tlock")]
+pub mod tlock;
+#[cfg(feature = "wasm-bindings")]
+pub mod wasm_bindings;
+#[cfg(feature = "http")]
+mod watcher;
+#[cfg(feature = "http")]
+pub use watcher::Watcher;
 
-#[derive(Error, Debug)]
+use alloc::boxed::Box;
+use core::fmt;
+
+/// Errors are displayed as the error they wrap. `std::error::Error` is implemented with the `std` feature.
+#[derive(Debug)]
 pub enum DrandError {
-    #[error(transparent)]
-    Beacon(#[from] Box<beacon::BeaconError>),
-    #[error(transparent)]
-    HTTPClient(#[from] Box<http_client::HttpClientError>),
-    #[error(transparent)]
-    Signature(#[from] Box<bls_signatures::VerificationError>),
+    Beacon(Box<beacon::BeaconError>),
+    #[cfg(feature = "grpc")]
+    Grpc(Box<grpc_client::GrpcError>),
+    #[cfg(feature = "http")]
+    HTTPClient(Box<http_client::HttpClientError>),
+    #[cfg(feature = "http")]
+    Quorum(Box<quorum_client::QuorumError>),
+    Signature(Box<bls_signatures::BlsError>),
+    #[cfg(feature = "tlock")]
+    Tlock(Box<tlock::TlockError>),
+}
+
+impl fmt::Display for DrandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Beacon(e) => e.fmt(f),
+            #[cfg(feature = "grpc")]
+            Self::Grpc(e) => e.fmt(f),
+            #[cfg(feature = "http")]
+            Self::HTTPClient(e) => e.fmt(f),
+            #[cfg(feature = "http")]
+            Self::Quorum(e) => e.fmt(f),
+            Self::Signature(e) => e.fmt(f),
+            #[cfg(feature = "tlock")]
+            Self::Tlock(e) => e.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DrandError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Beacon(e) => e.source(),
+            #[cfg(feature = "grpc")]
+            Self::Grpc(e) => e.source(),
+            #[cfg(feature = "http")]
+            Self::HTTPClient(e) => e.source(),
+            #[cfg(feature = "http")]
+            Self::Quorum(e) => e.source(),
+            Self::Signature(e) => e.source(),
+            #[cfg(feature = "tlock")]
+            Self::Tlock(e) => e.source(),
+        }
+    }
+}
+
+impl From<Box<beacon::BeaconError>> for DrandError {
+    fn from(e: Box<beacon::BeaconError>) -> Self {
+        Self::Beacon(e)
+    }
+}
+
+#[cfg(feature = "grpc")]
+impl From<Box<grpc_client::GrpcError>> for DrandError {
+    fn from(e: Box<grpc_client::GrpcError>) -> Self {
+        Self::Grpc(e)
+    }
+}
+
+#[cfg(feature = "http")]
+impl From<Box<http_client::HttpClientError>> for DrandError {
+    fn from(e: Box<http_client::HttpClientError>) -> Self {
+        Self::HTTPClient(e)
+    }
+}
+
+#[cfg(feature = "http")]
+impl From<Box<quorum_client::QuorumError>> for DrandError {
+    fn from(e: Box<quorum_client::QuorumError>) -> Self {
+        Self::Quorum(e)
+    }
+}
+
+impl From<Box<bls_signatures::BlsError>> for DrandError {
+    fn from(e: Box<bls_signatures::BlsError>) -> Self {
+        Self::Signature(e)
+    }
+}
+
+#[cfg(feature = "tlock")]
+impl From<Box<tlock::TlockError>> for DrandError {
+    fn from(e: Box<tlock::TlockError>) -> Self {
+        Self::Tlock(e)
+    }
+}
+
+impl DrandError {
+    /// Whether the operation might succeed if attempted again later, such as after a network failure.
+    /// See [`HttpClientError::is_retryable`].
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            #[cfg(feature = "http")]
+            Self::HTTPClient(e) => e.is_retryable(),
+            #[cfg(feature = "grpc")]
+            Self::Grpc(e) => e.is_retryable(),
+            _ => false,
+        }
+    }
+
+    /// Whether the beacon does not exist, such as a round which has not been produced yet.
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            Self::Beacon(e) => matches!(**e, beacon::BeaconError::NotFound),
+            #[cfg(feature = "http")]
+            Self::HTTPClient(e) => match e.as_ref() {
+                http_client::HttpClientError::RangeFailed { source, .. } => source.is_not_found(),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Whether what the remote served failed verification, such as an invalid signature or an unexpected chain.
+    pub fn is_validation_failure(&self) -> bool {
+        use beacon::BeaconError;
+
+        match self {
+            Self::Beacon(e) => matches!(
+                **e,
+                BeaconError::Validation { .. }
+                    | BeaconError::InvalidBeacon { .. }
+                    | BeaconError::ChainLinkage { .. }
+                    | BeaconError::RoundMismatch
+                    | BeaconError::Rollback { .. }
+            ),
+            #[cfg(feature = "http")]
+            Self::HTTPClient(e) => match e.as_ref() {
+                http_client::HttpClientError::InvalidChainInfo
+                | http_client::HttpClientError::ChainHashMismatch { .. } => true,
+                http_client::HttpClientError::RangeFailed { source, .. } => {
+                    source.is_validation_failure()
+                }
+                _ => false,
+            },
+            Self::Signature(e) => matches!(
+                **e,
+                BlsError::NotOnCurve
+                    | BlsError::NotInSubgroup
+                    | BlsError::Identity
+                    | BlsError::PairingFailure
+            ),
+            // other variants only exist with these features
+            #[cfg(any(feature = "grpc", feature = "http", feature = "tlock"))]
+            _ => false,
+        }
+    }
+
+    /// Whether the remote could not be reached. See [`HttpClientError::is_network`].
+    pub fn is_network(&self) -> bool {
+        match self {
+            #[cfg(feature = "http")]
+            Self::HTTPClient(e) => e.is_network(),
+            #[cfg(feature = "grpc")]
+            Self::Grpc(e) => matches!(**e, grpc_client::GrpcError::Transport(_)),
+            _ => false,
+        }
+    }
+
+    /// Whether the remote did not respond in time. See [`HttpClientError::is_timeout`].
+    pub fn is_timeout(&self) -> bool {
+        match self {
+            #[cfg(feature = "http")]
+            Self::HTTPClient(e) => e.is_timeout(),
+            _ => false,
+        }
+    }
+
+    /// Whether an input or a response could not be parsed, such as malformed JSON or a point of the wrong length.
+    pub fn is_parse(&self) -> bool {
+        use beacon::BeaconError;
+
+        match self {
+            Self::Beacon(e) => match **e {
+                BeaconError::Parsing
+                | BeaconError::MalformedJson { .. }
+                | BeaconError::InvalidLength { .. }
+                | BeaconError::InvalidPeriod => true,
+                #[cfg(feature = "time")]
+                BeaconError::DurationParse => true,
+                _ => false,
+            },
+            #[cfg(feature = "http")]
+            Self::HTTPClient(e) => match e.as_ref() {
+                http_client::HttpClientError::ParseURL(_)
+                | http_client::HttpClientError::NoProtocol { .. }
+                | http_client::HttpClientError::InvalidChainHash { .. } => true,
+                http_client::HttpClientError::RangeFailed { source, .. } => source.is_parse(),
+                _ => false,
+            },
+            Self::Signature(e) => matches!(
+                **e,
+                BlsError::WrongLength { .. } | BlsError::Deserialization
+            ),
+            // other variants only exist with these features
+            #[cfg(any(feature = "grpc", feature = "http", feature = "tlock"))]
+            _ => false,
+        }
+    }
+
+    /// HTTP status the remote responded with, for errors originating from one. See [`HttpClientError::status_code`].
+    /// Missing beacons are reported as [`beacon::BeaconError::NotFound`] instead, see [`Self::is_not_found`].
+    pub fn status_code(&self) -> Option<u16> {
+        match self {
+            #[cfg(feature = "http")]
+            Self::HTTPClient(e) => e.status_code(),
+            _ => None,
+        }
+    }
 }
 
-type Result<T> = std::result::Result<T, DrandError>;
+type Result<T> = core::result::Result<T, DrandError>;
@@ -0,0 +1,168 @@
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::{
+    beacon::{ApiBeacon, BeaconError, RandomnessBeacon},
+    chain::{ChainClient, ChainInfo, ChainOptions},
+    Result,
+};
+
+/// Client serving canned beacons of a chain, to test code relying on a [`ChainClient`] without a remote.
+/// Beacons are returned as provided, and are not verified, whatever the options.
+/// Available with the `test-util` feature.
+#[derive(Debug, Clone)]
+pub struct MockChainClient {
+    info: ChainInfo,
+    options: ChainOptions,
+    beacons: Vec<RandomnessBeacon>,
+}
+
+impl MockChainClient {
+    /// Client for the chain described by `info`, serving `beacons`. The time of each beacon is the one its round is produced at.
+    pub fn new(info: ChainInfo, beacons: Vec<ApiBeacon>) -> Self {
+        let beacons = beacons
+            .into_iter()
+            .map(|beacon| RandomnessBeacon::from_api(beacon, &info))
+            .collect();
+        Self {
+            info,
+            options: ChainOptions::default(),
+            beacons,
+        }
+    }
+
+    /// Options returned by [`ChainClient::options`]. Defaults to [`ChainOptions::default`].
+    pub fn with_options(mut self, options: ChainOptions) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+impl ChainClient for MockChainClient {
+    fn options(&self) -> ChainOptions {
+        self.options.clone()
+    }
+
+    /// Beacon with the highest round, or [`BeaconError::NotFound`] if there is none.
+    fn latest(&self) -> Result<RandomnessBeacon> {
+        self.beacons
+            .iter()
+            .max_by_key(|beacon| beacon.round())
+            .cloned()
+            .ok_or_else(|| Box::new(BeaconError::NotFound).into())
+    }
+
+    /// Beacon for `round_number`, or [`BeaconError::NotFound`] if it has not been provided.
+    fn get(&self, round_number: u64) -> Result<RandomnessBeacon> {
+        if round_number == 0 {
+            return self.latest();
+        }
+        self.beacons
+            .iter()
+            .find(|beacon| beacon.round() == round_number)
+            .cloned()
+            .ok_or_else(|| Box::new(BeaconError::NotFound).into())
+    }
+
+    fn chain_info(&self) -> Result<ChainInfo> {
+        Ok(self.info.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::sync::Arc;
+
+    use super::*;
+    use crate::{
+        beacon::tests::{chained_beacon, chained_beacon_1},
+        chain::tests::chained_chain_info,
+        DrandError,
+    };
+
+    fn assert_not_found(result: Result<RandomnessBeacon>) {
+        match result {
+            Err(DrandError::Beacon(e)) => assert!(matches!(*e, BeaconError::NotFound)),
+            _ => panic!("beacon should not be found"),
+        }
+    }
+
+    #[test]
+    fn mock_client_works() {
+        let client = MockChainClient::new(
+            chained_chain_info(),
+            vec![chained_beacon_1(), chained_beacon()],
+        )
+        .with_options(ChainOptions::builder().verify_beacons(false).build());
+
+        assert!(!client.options().is_beacon_verification());
+        assert_eq!(client.chain_info().unwrap(), chained_chain_info());
+        assert_eq!(client.latest().unwrap().beacon(), chained_beacon());
+        assert_eq!(client.get(0).unwrap().beacon(), chained_beacon());
+        let beacon = client.get(1).unwrap();
+        assert_eq!(beacon.beacon(), chained_beacon_1());
+        assert_eq!(beacon.time(), chained_chain_info().genesis_time());
+        assert_not_found(client.get(2));
+        assert_not_found(MockChainClient::new(chained_chain_info(), vec![]).latest());
+    }
+
+    #[test]
+    fn dyn_chain_client_works() {
+        fn latest_round(client: &dyn ChainClient) -> u64 {
+            client.latest().unwrap().round()
+        }
+
+        let mock = MockChainClient::new(chained_chain_info(), vec![chained_beacon()]);
+        let clients: Vec<Box<dyn ChainClient>> = vec![Box::new(mock.clone())];
+        for client in &clients {
+            assert_eq!(client.latest().unwrap().beacon(), chained_beacon());
+            assert_eq!(
+                client.get(chained_beacon().round()).unwrap().beacon(),
+                chained_beacon()
+            );
+            assert_eq!(client.chain_info().unwrap(), chained_chain_info());
+            assert_eq!(latest_round(client.as_ref()), chained_beacon().round());
+        }
+
+        // boxed and shared clients are clients themselves, and can be moved across threads
+        fn assert_client<C: ChainClient + 'static>(client: C) -> C {
+            client
+        }
+        let boxed = assert_client(clients.into_iter().next().unwrap());
+        let shared: Arc<dyn ChainClient> = assert_client(Arc::new(mock));
+        let handle = std::thread::spawn(move || shared.latest().unwrap().round());
+        assert_eq!(handle.join().unwrap(), boxed.latest().unwrap().round());
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn dyn_http_client_works() {
+        let mut server = mockito::Server::new();
+        let _info_mock = server
+            .mock("GET", "/info")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&chained_chain_info()).unwrap())
+            .create();
+        let round = chained_beacon().round();
+        let _get_mock = server
+            .mock("GET", format!("/public/{round}").as_str())
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&chained_beacon()).unwrap())
+            .create();
+
+        let clients: Vec<Box<dyn ChainClient>> = vec![
+            Box::new(crate::HttpClient::new(server.url().as_str(), None).unwrap()),
+            Box::new(MockChainClient::new(
+                chained_chain_info(),
+                vec![chained_beacon()],
+            )),
+        ];
+        for client in clients {
+            assert_eq!(client.get(round).unwrap().beacon(), chained_beacon());
+            assert_eq!(client.chain_info().unwrap(), chained_chain_info());
+        }
+    }
+}
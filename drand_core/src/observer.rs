@@ -0,0 +1,20 @@
+use std::time::Duration;
+
+/// Hooks into [`HttpClient`](crate::HttpClient) activity, such as to export metrics.
+/// Every method does nothing by default, so implementors only override the events they are interested in.
+/// Callbacks are invoked synchronously on the thread making the request, and should return quickly.
+pub trait Observer: Send + Sync {
+    /// Called after each request attempt to the remote, including retries.
+    /// `endpoint` is the path template, such as `/info`, `/public/{round}`, or `/chains`.
+    /// `status` is the HTTP status code, or `None` if no response was received.
+    fn on_request(&self, _endpoint: &str, _duration: Duration, _status: Option<u16>) {}
+
+    /// Called after the beacon for `round` has been verified, `ok` being the outcome.
+    /// Not called when beacon verification is disabled.
+    fn on_verification(&self, _round: u64, _ok: bool) {}
+}
+
+/// Default observer, ignoring every event.
+pub(crate) struct NoopObserver;
+
+impl Observer for NoopObserver {}
@@ -0,0 +1,169 @@
+//! Protobuf messages of the drand node public API, as exchanged over gRPC.
+//!
+//! Beacons and chain info convert to and from their wire representation. Messages are encoded and decoded with [`prost::Message`].
+
+use alloc::boxed::Box;
+
+use crate::{
+    beacon::{ApiBeacon, BeaconError},
+    chain::{ChainInfo, ChainMetadata},
+    DrandError,
+};
+
+/// Messages generated from `proto/drand/api.proto` with prost-build 0.12.
+#[allow(clippy::all, dead_code)]
+mod drand;
+
+pub use drand::{
+    ChainInfoPacket, ChainInfoRequest, Metadata, NodeVersion, PublicRandRequest, PublicRandResponse,
+};
+
+fn parsing<E>(_: E) -> DrandError {
+    Box::new(BeaconError::Parsing).into()
+}
+
+/// Randomness is derived from the signature, the one in the response is ignored. Unchained beacons have no previous signature.
+impl TryFrom<PublicRandResponse> for ApiBeacon {
+    type Error = DrandError;
+
+    fn try_from(response: PublicRandResponse) -> Result<Self, Self::Error> {
+        let previous_signature =
+            Some(response.previous_signature).filter(|signature| !signature.is_empty());
+        ApiBeacon::from_signatures(response.round, response.signature, previous_signature)
+    }
+}
+
+/// Beacons do not know the chain they belong to, metadata is left empty.
+impl From<&ApiBeacon> for PublicRandResponse {
+    fn from(beacon: &ApiBeacon) -> Self {
+        Self {
+            round: beacon.round(),
+            signature: beacon.signature(),
+            previous_signature: beacon.previous_signature().unwrap_or_default(),
+            randomness: beacon.randomness(),
+            metadata: None,
+        }
+    }
+}
+
+impl TryFrom<ChainInfoPacket> for ChainInfo {
+    type Error = DrandError;
+
+    fn try_from(packet: ChainInfoPacket) -> Result<Self, Self::Error> {
+        let genesis_time = u64::try_from(packet.genesis_time).map_err(parsing)?;
+        let beacon_id = packet
+            .metadata
+            .map(|metadata| metadata.beacon_id)
+            .unwrap_or_default();
+        ChainInfo::new(
+            packet.public_key,
+            u64::from(packet.period),
+            genesis_time,
+            packet.hash,
+            packet.group_hash,
+            packet.scheme_id,
+            ChainMetadata::new(beacon_id),
+        )
+    }
+}
+
+/// Fails if the period or genesis time do not fit the wire types.
+impl TryFrom<&ChainInfo> for ChainInfoPacket {
+    type Error = DrandError;
+
+    fn try_from(info: &ChainInfo) -> Result<Self, Self::Error> {
+        Ok(Self {
+            public_key: info.public_key(),
+            period: u32::try_from(info.period()).map_err(parsing)?,
+            genesis_time: i64::try_from(info.genesis_time()).map_err(parsing)?,
+            hash: info.hash(),
+            group_hash: info.group_hash(),
+            scheme_id: info.scheme_id(),
+            metadata: Some(Metadata {
+                beacon_id: info.metadata().beacon_id(),
+                chain_hash: info.hash(),
+                ..Default::default()
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use prost::Message;
+
+    use crate::{
+        beacon::tests::{chained_beacon, unchained_beacon},
+        chain::tests::{chained_chain_info, unchained_chain_info},
+    };
+
+    use super::*;
+
+    /// PublicRandResponse for drand mainnet round 1000000, with randomness and metadata as set by drand nodes
+    fn chained_response() -> Vec<u8> {
+        hex::decode("08c0843d126087e355169c4410a8ad6d3e7f5094b2122932c1062f603e6628aba2e4cb54f46c3bf1083c3537cd3b99e8296784f46fb40e090961cf9634f02c7dc2a96b69fc3c03735bc419962780a71245b72f81882cf6bb9c961bcf32da5624993bb747c9e51a6086bbc40c9d9347568967add4ddf6e351aff604352a7e1eec9b20dea4ca531ed6c7d38de9956ffc3bb5a7fabe28b3a36b069c8113bd9824135c3bff9b03359476f6b03beec179d4aeff456f4d34bbf702b9af78c3bb44e1892ace8e581bf4afa92220a26ba4d229c666f52a06f1a9be1278dcc7a80dbc1dd2004a1ae7b63cb79fd37e2a2b120764656661756c741a208990e7a9aaed2ffed73dbd7092123d6f289930540d7651336225dc172e51b2ce").unwrap()
+    }
+
+    /// PublicRandResponse for drand testnet unchained round 1000000. The previous signature field is absent.
+    fn unchained_response() -> Vec<u8> {
+        hex::decode("08c0843d126086b265e10e060805d20dca88f70f6b5e62d5956e7790d32029dfb73fbcd1996bc7aebdea7aeaf74dac0ca2b3ce8f7a6a0399f224a05fe740c0bac9da638212082b0ed21b1a8c5e44a33123f28955ef0713e93e21f6af0cda4073d9a73387434d22206671747f7d838f18159c474579ea19e8d863e8c25e5271fd7f18ca2ac85181cf2a381214746573746e65742d756e636861696e65642d33731a207672797f548f3f4748ac4bf3352fc6c6b6468c9ad40ad456a397545c6e2df5bf").unwrap()
+    }
+
+    /// ChainInfoPacket for drand mainnet
+    fn chained_chain_info_packet() -> Vec<u8> {
+        hex::decode("0a30868f005eb8e6e4ca0a47c8a77ceaa5309a47978a7c71bc5cce96366b5d7a569937c529eeda66c7293784a9402801af31101e188ab1e1f80522208990e7a9aaed2ffed73dbd7092123d6f289930540d7651336225dc172e51b2ce2a20176f93498eac9ca337150b46d21dd58673ea4e3581185f869672e59fa4cb390a3214706564657273656e2d626c732d636861696e65643a2b120764656661756c741a208990e7a9aaed2ffed73dbd7092123d6f289930540d7651336225dc172e51b2ce").unwrap()
+    }
+
+    #[test]
+    fn proto_beacon_works() {
+        for (encoded, expected, info) in [
+            (chained_response(), chained_beacon(), chained_chain_info()),
+            (
+                unchained_response(),
+                unchained_beacon(),
+                unchained_chain_info(),
+            ),
+        ] {
+            let response = PublicRandResponse::decode(encoded.as_slice()).unwrap();
+            let metadata = response.metadata.clone();
+            assert_eq!(metadata.as_ref().unwrap().chain_hash, info.hash());
+
+            let beacon = ApiBeacon::try_from(response).unwrap();
+            assert_eq!(beacon, expected);
+            assert!(beacon.verify(info).unwrap());
+
+            // encoding the beacon back gives the bytes served by the node, metadata aside
+            let mut response = PublicRandResponse::from(&beacon);
+            assert!(response.metadata.is_none());
+            response.metadata = metadata;
+            assert_eq!(response.encode_to_vec(), encoded);
+        }
+    }
+
+    #[test]
+    fn proto_beacon_invalid_signature_fails() {
+        let mut response = PublicRandResponse::decode(chained_response().as_slice()).unwrap();
+        response.signature.truncate(95);
+        match ApiBeacon::try_from(response) {
+            Err(DrandError::Beacon(e)) => assert!(matches!(*e, BeaconError::Parsing)),
+            _ => panic!("signature of invalid length should not parse"),
+        }
+    }
+
+    #[test]
+    fn proto_chain_info_works() {
+        let encoded = chained_chain_info_packet();
+        let packet = ChainInfoPacket::decode(encoded.as_slice()).unwrap();
+        let info = ChainInfo::try_from(packet).unwrap();
+        assert_eq!(info, chained_chain_info());
+        assert_eq!(info.metadata().beacon_id(), "default");
+
+        let packet = ChainInfoPacket::try_from(&info).unwrap();
+        assert_eq!(packet.encode_to_vec(), encoded);
+
+        // genesis time before the epoch cannot be represented
+        let mut packet = ChainInfoPacket::decode(encoded.as_slice()).unwrap();
+        packet.genesis_time = -1;
+        assert!(ChainInfo::try_from(packet).is_err());
+    }
+}
@@ -0,0 +1,85 @@
+// This file is @generated by prost-build.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NodeVersion {
+    #[prost(uint32, tag = "1")]
+    pub major: u32,
+    #[prost(uint32, tag = "2")]
+    pub minor: u32,
+    #[prost(uint32, tag = "3")]
+    pub patch: u32,
+    #[prost(string, tag = "4")]
+    pub prerelease: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Metadata {
+    #[prost(message, optional, tag = "1")]
+    pub node_version: ::core::option::Option<NodeVersion>,
+    #[prost(string, tag = "2")]
+    pub beacon_id: ::prost::alloc::string::String,
+    #[prost(bytes = "vec", tag = "3")]
+    pub chain_hash: ::prost::alloc::vec::Vec<u8>,
+}
+/// PublicRandRequest requests a public random value that has been generated in a
+/// unbiasable way and verifiable.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PublicRandRequest {
+    /// round uniquely identifies a beacon. If round == 0 (or unspecified), then
+    /// the response will contain the last.
+    #[prost(uint64, tag = "1")]
+    pub round: u64,
+    #[prost(message, optional, tag = "2")]
+    pub metadata: ::core::option::Option<Metadata>,
+}
+/// PublicRandResponse holds a signature which is the random value. It can be
+/// verified thanks to the distributed public key of the nodes that have ran the
+/// DKG protocol and is unbiasable. The randomness can be verified using the BLS
+/// verification routine with the message "round || previous_rand".
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PublicRandResponse {
+    #[prost(uint64, tag = "1")]
+    pub round: u64,
+    #[prost(bytes = "vec", tag = "2")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub previous_signature: ::prost::alloc::vec::Vec<u8>,
+    /// randomness is simply there to demonstrate - it is the hash of the
+    /// signature. It should be computed locally.
+    #[prost(bytes = "vec", tag = "4")]
+    pub randomness: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag = "5")]
+    pub metadata: ::core::option::Option<Metadata>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChainInfoRequest {
+    #[prost(message, optional, tag = "1")]
+    pub metadata: ::core::option::Option<Metadata>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChainInfoPacket {
+    /// marshalled public key
+    #[prost(bytes = "vec", tag = "1")]
+    pub public_key: ::prost::alloc::vec::Vec<u8>,
+    /// period in seconds
+    #[prost(uint32, tag = "2")]
+    pub period: u32,
+    /// genesis time of the chain
+    #[prost(int64, tag = "3")]
+    pub genesis_time: i64,
+    /// hash is included for ease of use - not needed to verify
+    #[prost(bytes = "vec", tag = "4")]
+    pub hash: ::prost::alloc::vec::Vec<u8>,
+    /// hash of the genesis group
+    #[prost(bytes = "vec", tag = "5")]
+    pub group_hash: ::prost::alloc::vec::Vec<u8>,
+    /// indicates a version of signing scheme used to generate randomness
+    #[prost(string, tag = "6")]
+    pub scheme_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "7")]
+    pub metadata: ::core::option::Option<Metadata>,
+}
@@ -0,0 +1,462 @@
+use std::thread;
+use thiserror::Error;
+
+use crate::{
+    beacon::{BeaconError, RandomnessBeacon},
+    chain::{ChainClient, ChainInfo, ChainOptions},
+    http_client::verify_chain_info,
+    HttpClient, Result,
+};
+
+#[derive(Error, Debug)]
+pub enum QuorumError {
+    #[error(
+        "threshold must be between 1 and the number of endpoints ({endpoints}), got {threshold}"
+    )]
+    InvalidThreshold { threshold: usize, endpoints: usize },
+    #[error("endpoints serve different chains: {}", .endpoints.join(", "))]
+    ChainMismatch { endpoints: Vec<String> },
+    #[error(
+        "quorum not reached, {agreeing} out of {threshold} required endpoints agree. disagreeing: [{}], unavailable: [{}]",
+        .disagreeing.join(", "),
+        .unavailable.join(", ")
+    )]
+    NoQuorum {
+        agreeing: usize,
+        threshold: usize,
+        disagreeing: Vec<String>,
+        unavailable: Vec<String>,
+    },
+}
+
+/// Client cross-checking beacons retrieved from multiple relays.
+/// A beacon is only accepted when at least `threshold` relays return the same signature for it.
+/// The agreed upon beacon is then verified once against the chain info, based on the quorum `options`.
+pub struct QuorumClient {
+    clients: Vec<HttpClient>,
+    threshold: usize,
+    options: ChainOptions,
+}
+
+impl QuorumClient {
+    pub fn new(
+        clients: Vec<HttpClient>,
+        threshold: usize,
+        options: Option<ChainOptions>,
+    ) -> Result<Self> {
+        if threshold == 0 || threshold > clients.len() {
+            return Err(Box::new(QuorumError::InvalidThreshold {
+                threshold,
+                endpoints: clients.len(),
+            })
+            .into());
+        }
+        Ok(Self {
+            clients,
+            threshold,
+            options: options.unwrap_or_default(),
+        })
+    }
+
+    /// Minimum number of relays that have to agree on a response.
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub fn options(&self) -> ChainOptions {
+        self.options.clone()
+    }
+
+    /// Chain info served by the relays.
+    /// At least `threshold` relays have to respond, and all responding relays have to serve the same chain.
+    pub fn chain_info(&self) -> Result<ChainInfo> {
+        let mut infos = vec![];
+        let mut unavailable = vec![];
+        for (client, info) in self
+            .clients
+            .iter()
+            .zip(self.fetch_all(|_, c| c.chain_info()))
+        {
+            match info {
+                Ok(info) => infos.push((client.base_url(), info)),
+                Err(_) => unavailable.push(client.base_url()),
+            }
+        }
+
+        if infos.windows(2).any(|pair| pair[0].1 != pair[1].1) {
+            return Err(Box::new(QuorumError::ChainMismatch {
+                endpoints: infos.into_iter().map(|(endpoint, _)| endpoint).collect(),
+            })
+            .into());
+        }
+        if infos.len() < self.threshold {
+            return Err(Box::new(QuorumError::NoQuorum {
+                agreeing: infos.len(),
+                threshold: self.threshold,
+                disagreeing: vec![],
+                unavailable,
+            })
+            .into());
+        }
+        let (_, info) = infos.swap_remove(0);
+        verify_chain_info(&self.options, info)
+    }
+
+    /// Latest beacon the relays agree upon.
+    /// Relays might be at different heads, in which case the minimum round served is used.
+    pub fn latest(&self) -> Result<RandomnessBeacon> {
+        let info = self.chain_info()?;
+        let latests = self.fetch_all(|_, c| c.latest());
+        let round = latests
+            .iter()
+            .filter_map(|beacon| beacon.as_ref().ok())
+            .map(RandomnessBeacon::round)
+            .min();
+        let Some(round) = round else {
+            return self.elect(info, latests);
+        };
+
+        // relays ahead of the minimum round are asked for it, others are not queried again
+        let beacons = self.fetch_all(|i, c| match &latests[i] {
+            Ok(beacon) if beacon.round() == round => Ok(beacon.clone()),
+            _ => c.get(round),
+        });
+        self.elect(info, beacons)
+    }
+
+    /// Round 0 is the latest round, for which relays first agree on a round.
+    pub fn get(&self, round_number: u64) -> Result<RandomnessBeacon> {
+        if round_number == 0 {
+            return self.latest();
+        }
+        let info = self.chain_info()?;
+        let beacons = self.fetch_all(|_, c| c.get(round_number));
+        self.elect(info, beacons)
+    }
+
+    /// Query all relays concurrently, returning their responses in the order relays were provided.
+    fn fetch_all<T: Send>(
+        &self,
+        fetch: impl Fn(usize, &HttpClient) -> Result<T> + Sync,
+    ) -> Vec<Result<T>> {
+        thread::scope(|s| {
+            let handles: Vec<_> = self
+                .clients
+                .iter()
+                .enumerate()
+                .map(|(i, client)| {
+                    let fetch = &fetch;
+                    s.spawn(move || fetch(i, client))
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("relay fetch should not panic"))
+                .collect()
+        })
+    }
+
+    /// Select the beacon returned by the most relays, provided they reach the threshold, and verify it.
+    fn elect(
+        &self,
+        info: ChainInfo,
+        beacons: Vec<Result<RandomnessBeacon>>,
+    ) -> Result<RandomnessBeacon> {
+        // beacons grouped by round and signature, alongside the relays returning them
+        let mut groups: Vec<(RandomnessBeacon, Vec<String>)> = vec![];
+        let mut unavailable = vec![];
+        for (client, beacon) in self.clients.iter().zip(beacons) {
+            let beacon = match beacon {
+                Ok(beacon) => beacon,
+                Err(_) => {
+                    unavailable.push(client.base_url());
+                    continue;
+                }
+            };
+            match groups
+                .iter_mut()
+                .find(|(b, _)| b.round() == beacon.round() && b.signature() == beacon.signature())
+            {
+                Some((_, endpoints)) => endpoints.push(client.base_url()),
+                None => groups.push((beacon, vec![client.base_url()])),
+            }
+        }
+
+        groups.sort_by_key(|(_, endpoints)| std::cmp::Reverse(endpoints.len()));
+        let agreeing = groups.first().map_or(0, |(_, endpoints)| endpoints.len());
+        if agreeing < self.threshold {
+            return Err(Box::new(QuorumError::NoQuorum {
+                agreeing,
+                threshold: self.threshold,
+                disagreeing: groups
+                    .into_iter()
+                    .skip(1)
+                    .flat_map(|(_, endpoints)| endpoints)
+                    .collect(),
+                unavailable,
+            })
+            .into());
+        }
+
+        let (beacon, _) = groups.swap_remove(0);
+        if self.options.is_beacon_verification() {
+            let info = info.prepare_with_registry(self.options.scheme_registry())?;
+            let reason = beacon.verify_with_options(&info, self.options.verify_options())?;
+            if !reason.is_ok() {
+                return Err(Box::new(BeaconError::Validation { reason }).into());
+            }
+        }
+        Ok(beacon)
+    }
+}
+
+impl ChainClient for QuorumClient {
+    fn options(&self) -> ChainOptions {
+        self.options()
+    }
+
+    fn latest(&self) -> Result<RandomnessBeacon> {
+        self.latest()
+    }
+
+    fn get(&self, round_number: u64) -> Result<RandomnessBeacon> {
+        self.get(round_number)
+    }
+
+    fn chain_info(&self) -> Result<ChainInfo> {
+        self.chain_info()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        beacon::{tests::chained_beacon, ApiBeacon, VerificationOutcome},
+        chain::tests::{chained_chain_info, unchained_chain_info},
+        DrandError, RetryPolicy,
+    };
+
+    use super::*;
+
+    /// Beacon for the same round as [`chained_beacon`], signed by someone else
+    fn forged_beacon() -> ApiBeacon {
+        serde_json::from_str(r#"{
+            "round": 1000000,
+            "randomness": "101297f1ca7dc44ef6088d94ad5fb7ba03455dc33d53ddb412bbc4564ed986ec",
+            "signature": "8d61d9100567de44682506aea1a7a6fa6e5491cd27a0a0ed349ef6910ac5ac20ff7bc3e09d7c046566c9f7f3c6f3b10104990e7cb424998203d8f7de586fb7fa5f60045417a432684f85093b06ca91c769f0e7ca19268375e659c2a2352b4655",
+            "previous_signature": "86bbc40c9d9347568967add4ddf6e351aff604352a7e1eec9b20dea4ca531ed6c7d38de9956ffc3bb5a7fabe28b3a36b069c8113bd9824135c3bff9b03359476f6b03beec179d4aeff456f4d34bbf702b9af78c3bb44e1892ace8e581bf4afa9"
+        }"#).unwrap()
+    }
+
+    /// Relay serving `info`, and `beacon` for its round. No beacon means the relay fails to serve beacons.
+    fn relay(info: &ChainInfo, beacon: Option<&ApiBeacon>) -> mockito::ServerGuard {
+        let mut server = mockito::Server::new();
+        server
+            .mock("GET", "/info")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(info).unwrap())
+            .create();
+        let mock = server
+            .mock("GET", mockito::Matcher::Regex("^/public/".to_owned()))
+            .match_query(mockito::Matcher::Any);
+        match beacon {
+            Some(beacon) => mock
+                .with_status(200)
+                .with_header("content-type", "application/json")
+                .with_body(serde_json::to_string(beacon).unwrap()),
+            None => mock.with_status(500),
+        }
+        .create();
+        server
+    }
+
+    /// Quorum over `relays`, which clients do not verify beacons themselves
+    fn quorum(relays: &[mockito::ServerGuard], threshold: usize) -> Result<QuorumClient> {
+        let clients = relays
+            .iter()
+            .map(|relay| {
+                HttpClient::builder(relay.url().as_str())
+                    .options(ChainOptions::new(false, true, None))
+                    .retry(RetryPolicy::none())
+                    .build()
+                    .unwrap()
+            })
+            .collect();
+        QuorumClient::new(clients, threshold, None)
+    }
+
+    fn quorum_error(err: DrandError) -> QuorumError {
+        match err {
+            DrandError::Quorum(e) => *e,
+            err => panic!("error should come from the quorum, got {}", err),
+        }
+    }
+
+    #[test]
+    fn quorum_threshold_works() {
+        let relays = [relay(&chained_chain_info(), Some(&chained_beacon()))];
+        for threshold in [0, 2] {
+            match quorum(&relays, threshold) {
+                Ok(_client) => panic!("threshold {threshold} should be invalid"),
+                Err(err) => assert!(matches!(
+                    quorum_error(err),
+                    QuorumError::InvalidThreshold { endpoints: 1, .. }
+                )),
+            }
+        }
+    }
+
+    #[test]
+    fn quorum_agreement_works() {
+        let info = chained_chain_info();
+        let relays = [
+            relay(&info, Some(&chained_beacon())),
+            relay(&info, Some(&chained_beacon())),
+            relay(&info, Some(&chained_beacon())),
+        ];
+        let client = quorum(&relays, 3).unwrap();
+
+        assert_eq!(client.chain_info().unwrap(), info);
+        let beacon = client.get(chained_beacon().round()).unwrap();
+        assert_eq!(beacon.beacon(), chained_beacon());
+    }
+
+    #[test]
+    fn quorum_disagreement_works() {
+        let info = chained_chain_info();
+        let relays = [
+            relay(&info, Some(&chained_beacon())),
+            relay(&info, Some(&chained_beacon())),
+            relay(&info, Some(&forged_beacon())),
+        ];
+        let round = chained_beacon().round();
+
+        // the majority is enough to reach a threshold of 2
+        let client = quorum(&relays, 2).unwrap();
+        assert_eq!(client.get(round).unwrap().beacon(), chained_beacon());
+
+        // but not a threshold of 3, and the disagreeing relay is named
+        let client = quorum(&relays, 3).unwrap();
+        match quorum_error(client.get(round).unwrap_err()) {
+            QuorumError::NoQuorum {
+                agreeing,
+                disagreeing,
+                unavailable,
+                ..
+            } => {
+                assert_eq!(agreeing, 2);
+                assert_eq!(disagreeing, vec![format!("{}/", relays[2].url())]);
+                assert!(unavailable.is_empty());
+            }
+            err => panic!("quorum should not be reached, got {}", err),
+        }
+
+        // beacons agreed upon are still verified
+        let relays = [
+            relay(&info, Some(&forged_beacon())),
+            relay(&info, Some(&forged_beacon())),
+        ];
+        let client = quorum(&relays, 2).unwrap();
+        match client.get(round) {
+            Ok(_beacon) => panic!("forged beacon should fail verification"),
+            Err(DrandError::Beacon(e)) => assert!(matches!(
+                *e,
+                BeaconError::Validation {
+                    reason: VerificationOutcome::SignatureInvalid
+                }
+            )),
+            Err(err) => panic!("forged beacon should fail verification, got {}", err),
+        }
+    }
+
+    #[test]
+    fn quorum_partial_outage_works() {
+        let info = chained_chain_info();
+        let relays = [
+            relay(&info, Some(&chained_beacon())),
+            relay(&info, Some(&chained_beacon())),
+            relay(&info, None),
+        ];
+        let round = chained_beacon().round();
+
+        let client = quorum(&relays, 2).unwrap();
+        assert_eq!(client.get(round).unwrap().beacon(), chained_beacon());
+
+        let client = quorum(&relays, 3).unwrap();
+        match quorum_error(client.get(round).unwrap_err()) {
+            QuorumError::NoQuorum {
+                agreeing,
+                disagreeing,
+                unavailable,
+                ..
+            } => {
+                assert_eq!(agreeing, 2);
+                assert!(disagreeing.is_empty());
+                assert_eq!(unavailable, vec![format!("{}/", relays[2].url())]);
+            }
+            err => panic!("quorum should not be reached, got {}", err),
+        }
+    }
+
+    #[test]
+    fn quorum_chain_mismatch_works() {
+        let relays = [
+            relay(&chained_chain_info(), Some(&chained_beacon())),
+            relay(&chained_chain_info(), Some(&chained_beacon())),
+            relay(&unchained_chain_info(), Some(&chained_beacon())),
+        ];
+        let client = quorum(&relays, 2).unwrap();
+        match quorum_error(client.get(chained_beacon().round()).unwrap_err()) {
+            QuorumError::ChainMismatch { endpoints } => assert_eq!(endpoints.len(), 3),
+            err => panic!("chains should mismatch, got {}", err),
+        }
+    }
+
+    #[test]
+    fn quorum_latest_works() {
+        let info = chained_chain_info();
+        let round = chained_beacon().round();
+        let relays = [
+            relay(&info, Some(&chained_beacon())),
+            relay(&info, Some(&chained_beacon())),
+            relay(&info, Some(&chained_beacon())),
+        ];
+
+        // the last relay is one round ahead, and is asked for the minimum common round instead
+        let mut ahead = serde_json::to_value(chained_beacon()).unwrap();
+        ahead["round"] = (round + 1).into();
+        let mut ahead_relay = mockito::Server::new();
+        let _info_mock = ahead_relay
+            .mock("GET", "/info")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&info).unwrap())
+            .create();
+        let latest_mock = ahead_relay
+            .mock("GET", "/public/latest")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(ahead.to_string())
+            .expect(1)
+            .create();
+        let get_mock = ahead_relay
+            .mock("GET", format!("/public/{round}").as_str())
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&chained_beacon()).unwrap())
+            .expect(1)
+            .create();
+
+        let mut relays = relays.into_iter().collect::<Vec<_>>();
+        relays.push(ahead_relay);
+        let client = quorum(&relays, 4).unwrap();
+        assert_eq!(client.latest().unwrap().beacon(), chained_beacon());
+        latest_mock.assert();
+        get_mock.assert();
+    }
+}
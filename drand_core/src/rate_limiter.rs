@@ -0,0 +1,101 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// Limits the rate of outgoing requests with a token bucket.
+/// Up to `burst` requests are allowed at once, after which requests are spaced to respect `requests_per_second`.
+/// Clones share the same bucket, allowing the limit to span multiple clients and threads.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    requests_per_second: f64,
+    burst: f64,
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// Available tokens. Negative when requests are waiting for tokens to be refilled.
+    tokens: f64,
+    refilled_at: Option<Instant>,
+}
+
+impl RateLimiter {
+    /// Panics if `requests_per_second` is not a positive number.
+    pub fn new(requests_per_second: f64, burst: u32) -> Self {
+        assert!(
+            requests_per_second.is_finite() && requests_per_second > 0.0,
+            "requests per second should be positive"
+        );
+        let burst = f64::from(burst.max(1));
+        Self {
+            requests_per_second,
+            burst,
+            bucket: Arc::new(Mutex::new(Bucket {
+                tokens: burst,
+                refilled_at: None,
+            })),
+        }
+    }
+
+    pub fn requests_per_second(&self) -> f64 {
+        self.requests_per_second
+    }
+
+    /// Wait until a request is allowed.
+    pub(crate) fn acquire(&self) {
+        std::thread::sleep(self.reserve(Instant::now()));
+    }
+
+    /// Reserve a token for a request made at `now`, returning how long the request has to wait for it.
+    fn reserve(&self, now: Instant) -> Duration {
+        let mut bucket = self.bucket.lock().unwrap();
+        let elapsed = bucket
+            .refilled_at
+            .map_or(Duration::ZERO, |at| now.saturating_duration_since(at));
+        bucket.tokens =
+            (bucket.tokens + elapsed.as_secs_f64() * self.requests_per_second).min(self.burst);
+        bucket.refilled_at = Some(bucket.refilled_at.map_or(now, |at| at.max(now)));
+
+        bucket.tokens -= 1.0;
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / self.requests_per_second)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_limiter_works() {
+        let limiter = RateLimiter::new(10.0, 2);
+        let start = Instant::now();
+
+        // burst is allowed right away
+        assert_eq!(limiter.reserve(start), Duration::ZERO);
+        assert_eq!(limiter.reserve(start), Duration::ZERO);
+        // then requests are spaced by 100ms
+        assert_eq!(limiter.reserve(start), Duration::from_millis(100));
+        assert_eq!(limiter.reserve(start), Duration::from_millis(200));
+
+        // tokens are refilled over time, up to the burst
+        let later = start + Duration::from_secs(10);
+        assert_eq!(limiter.reserve(later), Duration::ZERO);
+        assert_eq!(limiter.reserve(later), Duration::ZERO);
+        assert_eq!(limiter.reserve(later), Duration::from_millis(100));
+
+        // clones share the same bucket
+        let shared = limiter.clone();
+        assert_eq!(shared.reserve(later), Duration::from_millis(200));
+    }
+
+    #[test]
+    #[should_panic]
+    fn rate_limiter_rejects_invalid_rate() {
+        RateLimiter::new(0.0, 1);
+    }
+}
@@ -0,0 +1,432 @@
+//! Timelock encryption to a round of a drand chain, as specified by [tlock](https://eprint.iacr.org/2023/189).
+//!
+//! Messages are encrypted with Boneh-Franklin identity based encryption, made CCA secure with the Fujisaki-Okamoto transform.
+//! The identity is the message signed by the chain at that round, so that the beacon signature is the decryption key.
+//! Ciphertexts are `U || V || W`, following the hashing and encoding of tlock (Go) and tlock-js.
+//!
+//! Messages are at most 32 bytes long. tlock encrypts the symmetric key of an age file, see `tlock_age` for file encryption.
+//! Only unchained schemes on BLS12-381 are supported, as the signature of chained rounds depends on the previous one.
+//! Available with the `tlock` feature.
+
+use alloc::{boxed::Box, vec, vec::Vec};
+use core::fmt;
+
+use ark_bls12_381::{Bls12_381, Fr, G1Affine, G2Affine};
+use ark_ec::{
+    pairing::{Pairing, PairingOutput},
+    AffineRepr, CurveGroup,
+};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use rand::{CryptoRng, RngCore};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    beacon::{unchained_message, BeaconError, RandomnessBeacon},
+    bls_signatures::{self, dst_for_scheme},
+    chain::{ChainInfo, Scheme},
+    Result,
+};
+
+/// Longest message that can be encrypted, the length of SHA-256 outputs masking it.
+pub const MAX_MESSAGE_LEN: usize = 32;
+
+const H2_TAG: &[u8] = b"IBE-H2";
+const H3_TAG: &[u8] = b"IBE-H3";
+const H4_TAG: &[u8] = b"IBE-H4";
+
+/// Errors raised while encrypting to, or decrypting from, a round.
+#[derive(Debug)]
+pub enum TlockError {
+    /// Message is longer than [`MAX_MESSAGE_LEN`].
+    MessageTooLong { len: usize },
+    /// Ciphertext is too short, or `V` and `W` have different lengths.
+    InvalidCiphertext,
+    /// Beacon is not the one of the round the ciphertext is encrypted to, or the ciphertext has been tampered with.
+    DecryptionFailed,
+}
+
+impl fmt::Display for TlockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MessageTooLong { len } => write!(
+                f,
+                "message is {len} bytes long, at most {MAX_MESSAGE_LEN} bytes can be encrypted"
+            ),
+            Self::InvalidCiphertext => write!(f, "invalid ciphertext"),
+            Self::DecryptionFailed => write!(f, "decryption failed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TlockError {}
+
+/// Encrypt `message` to `round` of the chain described by `info`, with randomness from the operating system.
+/// It can be decrypted once the beacon for `round` is produced, see [`decrypt`].
+#[cfg(feature = "std")]
+pub fn encrypt(info: &ChainInfo, round: u64, message: &[u8]) -> Result<Vec<u8>> {
+    encrypt_with_rng(&mut rand::rngs::OsRng, info, round, message)
+}
+
+/// Encrypt `message` to `round` of the chain described by `info`, as [`encrypt`] does, with randomness from `rng`.
+pub fn encrypt_with_rng<R: RngCore + CryptoRng>(
+    rng: &mut R,
+    info: &ChainInfo,
+    round: u64,
+    message: &[u8],
+) -> Result<Vec<u8>> {
+    if message.len() > MAX_MESSAGE_LEN {
+        return Err(Box::new(TlockError::MessageTooLong { len: message.len() }).into());
+    }
+    let dst = unchained_dst(info)?;
+    let identity = unchained_message(round);
+
+    let mut sigma = vec![0u8; message.len()];
+    rng.fill_bytes(&mut sigma);
+    let r = h3(&sigma, message);
+
+    // U = r * P and r * e(Q_id, public key) = e(Q_id, U), on the group of the public key
+    let (u, r_gid) = if public_key_on_g1(&info.scheme()) {
+        let public_key = bls_signatures::g1_from_variable(&info.public_key())?;
+        let q_id = bls_signatures::hash_to_g2(dst, &identity)?;
+        let u = (G1Affine::generator() * r).into_affine();
+        (
+            serialize(&u),
+            h2(&Bls12_381::pairing(public_key * r, q_id), message.len()),
+        )
+    } else {
+        let public_key = bls_signatures::g2_from_variable(&info.public_key())?;
+        let q_id = bls_signatures::hash_to_g1(dst, &identity)?;
+        let u = (G2Affine::generator() * r).into_affine();
+        (
+            serialize(&u),
+            h2(&Bls12_381::pairing(q_id, public_key * r), message.len()),
+        )
+    };
+
+    let v = xor(&sigma, &r_gid);
+    let w = xor(message, &h4(&sigma, message.len()));
+    Ok([u, v, w].concat())
+}
+
+/// Decrypt `ciphertext` with `beacon`, the beacon of the round it is encrypted to.
+/// The beacon does not have to be verified, as decryption fails with [`TlockError::DecryptionFailed`] if its signature is not the expected one.
+pub fn decrypt(info: &ChainInfo, beacon: &RandomnessBeacon, ciphertext: &[u8]) -> Result<Vec<u8>> {
+    // ciphertexts are only produced for unchained schemes
+    unchained_dst(info)?;
+
+    let on_g1 = public_key_on_g1(&info.scheme());
+    let u_len = if on_g1 { 48 } else { 96 };
+    if ciphertext.len() < u_len || (ciphertext.len() - u_len) % 2 != 0 {
+        return Err(Box::new(TlockError::InvalidCiphertext).into());
+    }
+    let (u, vw) = ciphertext.split_at(u_len);
+    let (v, w) = vw.split_at(vw.len() / 2);
+    if w.len() > MAX_MESSAGE_LEN {
+        return Err(Box::new(TlockError::InvalidCiphertext).into());
+    }
+
+    // r * e(Q_id, public key) = e(U, signature), as the signature is the private key of the identity
+    let signature = beacon.signature();
+    let r_gid = if on_g1 {
+        let u = bls_signatures::g1_from_variable(u)?;
+        let signature = bls_signatures::g2_from_variable(&signature)?;
+        Bls12_381::pairing(u, signature)
+    } else {
+        let u = bls_signatures::g2_from_variable(u)?;
+        let signature = bls_signatures::g1_from_variable(&signature)?;
+        Bls12_381::pairing(signature, u)
+    };
+
+    let sigma = xor(v, &h2(&r_gid, v.len()));
+    let message = xor(w, &h4(&sigma, w.len()));
+
+    // U has to be the one derived from sigma and the message, which rejects other beacons and tampered ciphertexts
+    let r = h3(&sigma, &message);
+    let expected = match on_g1 {
+        true => serialize(&(G1Affine::generator() * r).into_affine()),
+        false => serialize(&(G2Affine::generator() * r).into_affine()),
+    };
+    if expected != u {
+        return Err(Box::new(TlockError::DecryptionFailed).into());
+    }
+    Ok(message)
+}
+
+/// Domain separation tag the chain hashes round messages with, for unchained schemes on BLS12-381.
+fn unchained_dst(info: &ChainInfo) -> Result<&'static [u8]> {
+    let scheme = info.scheme();
+    match scheme {
+        Scheme::PedersenBlsUnchained | Scheme::BlsUnchainedOnG1 | Scheme::BlsUnchainedG1Rfc9380 => {
+            dst_for_scheme(&scheme).ok_or_else(|| unsupported(info))
+        }
+        _ => Err(unsupported(info)),
+    }
+}
+
+fn unsupported(info: &ChainInfo) -> crate::DrandError {
+    Box::new(BeaconError::UnsupportedScheme {
+        scheme: info.scheme_id(),
+    })
+    .into()
+}
+
+/// Whether the public key is on G1, and signatures on G2.
+fn public_key_on_g1(scheme: &Scheme) -> bool {
+    matches!(scheme, Scheme::PedersenBlsUnchained)
+}
+
+fn serialize<T: CanonicalSerialize>(point: &T) -> Vec<u8> {
+    let mut data = vec![];
+    point
+        .serialize_compressed(&mut data)
+        .expect("serializing to a vector does not fail");
+    data
+}
+
+/// `sha256("IBE-H2" || gt)`, truncated to `len` bytes.
+/// `gt` is encoded as tlock (Go) does, with coefficients from the highest degree down, each big-endian.
+/// This is arkworks encoding, which is the other way around, reversed.
+fn h2(gt: &PairingOutput<Bls12_381>, len: usize) -> Vec<u8> {
+    let mut encoded = serialize(gt);
+    encoded.reverse();
+    Sha256::new()
+        .chain_update(H2_TAG)
+        .chain_update(encoded)
+        .finalize()[..len]
+        .to_vec()
+}
+
+/// Scalar derived from `sha256("IBE-H3" || sigma || message)`.
+/// It is hashed again with a little-endian counter, until the result, with its top bit cleared, is a big-endian scalar.
+fn h3(sigma: &[u8], message: &[u8]) -> Fr {
+    let seed = Sha256::new()
+        .chain_update(H3_TAG)
+        .chain_update(sigma)
+        .chain_update(message)
+        .finalize();
+    (1..=u16::MAX)
+        .find_map(|i| {
+            let mut hashed = Sha256::new()
+                .chain_update(i.to_le_bytes())
+                .chain_update(seed)
+                .finalize();
+            hashed[0] >>= 1;
+            hashed.reverse();
+            Fr::deserialize_compressed(hashed.as_slice()).ok()
+        })
+        .expect("a scalar is found within a few iterations")
+}
+
+/// `sha256("IBE-H4" || sigma)`, truncated to `len` bytes.
+fn h4(sigma: &[u8], len: usize) -> Vec<u8> {
+    Sha256::new()
+        .chain_update(H4_TAG)
+        .chain_update(sigma)
+        .finalize()[..len]
+        .to_vec()
+}
+
+fn xor(a: &[u8], b: &[u8]) -> Vec<u8> {
+    a.iter().zip(b).map(|(a, b)| a ^ b).collect()
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+
+    use super::*;
+    use crate::{
+        beacon::{
+            tests::{
+                chained_beacon, unchained_beacon, unchained_beacon_on_g1,
+                unchained_beacon_on_g1_rfc,
+            },
+            ApiBeacon,
+        },
+        chain::tests::{
+            chained_chain_info, unchained_chain_info, unchained_chain_on_g1_info,
+            unchained_chain_on_g1_rfc_info,
+        },
+        DrandError,
+    };
+
+    const MESSAGE: &[u8] = b"sixteen byte key";
+
+    /// Published chains with unchained schemes, with their public key length, and a beacon they produced.
+    fn chains() -> [(ChainInfo, usize, RandomnessBeacon); 3] {
+        let beacon = |beacon: ApiBeacon, info: &ChainInfo| RandomnessBeacon::from_api(beacon, info);
+        let testnet = unchained_chain_info();
+        let legacy = unchained_chain_on_g1_info();
+        let quicknet = unchained_chain_on_g1_rfc_info();
+        [
+            (testnet.clone(), 48, beacon(unchained_beacon(), &testnet)),
+            (
+                legacy.clone(),
+                96,
+                beacon(unchained_beacon_on_g1(), &legacy),
+            ),
+            (
+                quicknet.clone(),
+                96,
+                beacon(unchained_beacon_on_g1_rfc(), &quicknet),
+            ),
+        ]
+    }
+
+    fn tlock_error<T: fmt::Debug>(result: Result<T>) -> TlockError {
+        match result {
+            Err(DrandError::Tlock(e)) => *e,
+            Err(e) => panic!("should fail with a tlock error, got {e}"),
+            Ok(value) => panic!("should fail, got {value:?}"),
+        }
+    }
+
+    #[test]
+    fn tlock_works() {
+        let mut rng = ChaCha20Rng::seed_from_u64(1);
+        for (info, u_len, beacon) in chains() {
+            // the beacon signature published by the chain decrypts what is encrypted to its round
+            let ciphertext = encrypt_with_rng(&mut rng, &info, beacon.round(), MESSAGE).unwrap();
+            assert_eq!(ciphertext.len(), u_len + 2 * MESSAGE.len());
+            assert_eq!(decrypt(&info, &beacon, &ciphertext).unwrap(), MESSAGE);
+
+            // ciphertexts are randomized
+            let other = encrypt(&info, beacon.round(), MESSAGE).unwrap();
+            assert_ne!(other, ciphertext);
+            assert_eq!(decrypt(&info, &beacon, &other).unwrap(), MESSAGE);
+
+            for message in [&[][..], &b"a"[..], &[0xff; MAX_MESSAGE_LEN][..]] {
+                let ciphertext = encrypt(&info, beacon.round(), message).unwrap();
+                assert_eq!(decrypt(&info, &beacon, &ciphertext).unwrap(), message);
+            }
+        }
+
+        // encryption only depends on the randomness provided
+        let (info, _, beacon) = chains().into_iter().last().unwrap();
+        let encrypt_seeded = |seed| {
+            encrypt_with_rng(
+                &mut ChaCha20Rng::seed_from_u64(seed),
+                &info,
+                beacon.round(),
+                MESSAGE,
+            )
+            .unwrap()
+        };
+        assert_eq!(encrypt_seeded(7), encrypt_seeded(7));
+        assert_ne!(encrypt_seeded(7), encrypt_seeded(8));
+    }
+
+    #[test]
+    fn tlock_interop_works() {
+        // drand fastnet (curl -sS https://drand.cloudflare.com/dbd506d6ef76e5f386f41c651dcb808c5bcbd75471cc4eafa3f4df7ad4e4c493/public/1000)
+        // ciphertext of 16 zero bytes to round 1000, from the tlock crate documentation
+        let legacy = unchained_chain_on_g1_info();
+        let legacy_beacon: ApiBeacon = serde_json::from_str(r#"{
+            "round": 1000,
+            "signature": "b09eacd45767c4d58306b98901ad0d6086e2663766f3a4ec71d00cf26f0f49eaf248abc7151c60cf419c4e8b37e80412"
+        }"#).unwrap();
+        let legacy_beacon = RandomnessBeacon::from_api(legacy_beacon, &legacy);
+        assert!(legacy_beacon.verify(legacy.clone()).unwrap());
+        let ciphertext = hex::decode("9787b5ed1c3e36e84ce19064e975be835b81c0788d5aa2a49ab7edc98b2917f1d61ac21f196bdc693ed556194fb33da104ffafa3c036dbcfb55eb953aaf2d446871aad7a1266f531caac1d654247a2d8ee93b975a7a19f0286f44d3c646d76338f334f4450bddbb2db52daae55d9e20ec26503ea7855b165f713b4ea96e60376").unwrap();
+        assert_eq!(
+            decrypt(&legacy, &legacy_beacon, &ciphertext).unwrap(),
+            [0; 16]
+        );
+
+        // ciphertext to round 3 of a chain hashing to G1 with RFC 9380, produced by the tlock crate (tlock::encrypt)
+        let quicknet = unchained_chain_on_g1_rfc_info();
+        let quicknet_beacon = RandomnessBeacon::from_api(unchained_beacon_on_g1_rfc(), &quicknet);
+        let ciphertext = hex::decode("82cb0f1cbe422332d42c6793be0e87d1b65c446f4002527bdb2553c65d5b0c688484e07b6032e30a4874a96ec5cc468c1821296136acfeebd7c8646793fb314eeb7d875dedf82a755c59c290cbbb73dfca079b725c526e5f15a702434c7a9606b42d5dc4f23cff5baaa54be26a01553bd299a0e68411dff063ce02f2dd3813ea").unwrap();
+        assert_eq!(
+            decrypt(&quicknet, &quicknet_beacon, &ciphertext).unwrap(),
+            b"tlock interop 16"
+        );
+    }
+
+    #[test]
+    fn tlock_other_round_fails() {
+        for (info, _, beacon) in chains() {
+            for round in [beacon.round() - 1, beacon.round() + 1] {
+                let ciphertext = encrypt(&info, round, MESSAGE).unwrap();
+                assert!(matches!(
+                    tlock_error(decrypt(&info, &beacon, &ciphertext)),
+                    TlockError::DecryptionFailed
+                ));
+            }
+        }
+
+        // G1 chains hash rounds with a different domain separation tag, so ciphertexts are bound to their chain
+        let [_, (legacy, _, legacy_beacon), (quicknet, _, quicknet_beacon)] = chains();
+        let ciphertext = encrypt(&legacy, quicknet_beacon.round(), MESSAGE).unwrap();
+        assert!(matches!(
+            tlock_error(decrypt(&quicknet, &quicknet_beacon, &ciphertext)),
+            TlockError::DecryptionFailed
+        ));
+        let ciphertext = encrypt(&quicknet, legacy_beacon.round(), MESSAGE).unwrap();
+        assert!(matches!(
+            tlock_error(decrypt(&legacy, &legacy_beacon, &ciphertext)),
+            TlockError::DecryptionFailed
+        ));
+    }
+
+    #[test]
+    fn tlock_tampered_ciphertext_fails() {
+        for (info, u_len, beacon) in chains() {
+            let ciphertext = encrypt(&info, beacon.round(), MESSAGE).unwrap();
+
+            // flipping a bit of V or W changes sigma or the message, and U no longer matches them
+            for i in [u_len, u_len + MESSAGE.len(), ciphertext.len() - 1] {
+                let mut tampered = ciphertext.clone();
+                tampered[i] ^= 1;
+                assert!(matches!(
+                    tlock_error(decrypt(&info, &beacon, &tampered)),
+                    TlockError::DecryptionFailed
+                ));
+            }
+
+            // U is not a valid point anymore
+            let mut tampered = ciphertext.clone();
+            tampered[u_len - 1] ^= 1;
+            assert!(decrypt(&info, &beacon, &tampered).is_err());
+
+            // V and W of different lengths, or missing
+            for len in [ciphertext.len() - 1, u_len - 1] {
+                assert!(matches!(
+                    tlock_error(decrypt(&info, &beacon, &ciphertext[..len])),
+                    TlockError::InvalidCiphertext
+                ));
+            }
+            let mut padded = ciphertext.clone();
+            padded.extend_from_slice(&[0; 2 * MAX_MESSAGE_LEN]);
+            assert!(matches!(
+                tlock_error(decrypt(&info, &beacon, &padded)),
+                TlockError::InvalidCiphertext
+            ));
+        }
+    }
+
+    #[test]
+    fn tlock_unsupported_fails() {
+        let (info, _, beacon) = chains().into_iter().last().unwrap();
+        assert!(matches!(
+            tlock_error(encrypt(&info, beacon.round(), &[0; MAX_MESSAGE_LEN + 1])),
+            TlockError::MessageTooLong { len: 33 }
+        ));
+
+        // chained rounds cannot be encrypted to, as their signature depends on the previous one
+        let chained = chained_chain_info();
+        match encrypt(&chained, 1, MESSAGE) {
+            Err(DrandError::Beacon(e)) => {
+                assert!(matches!(*e, BeaconError::UnsupportedScheme { .. }))
+            }
+            _ => panic!("chained scheme should not be supported"),
+        }
+
+        // signatures on G2 are not decryption keys of chains with signatures on G1
+        let chained_beacon = RandomnessBeacon::from_api(chained_beacon(), &chained);
+        let ciphertext = encrypt(&info, beacon.round(), MESSAGE).unwrap();
+        assert!(decrypt(&info, &chained_beacon, &ciphertext).is_err());
+    }
+}
@@ -0,0 +1,74 @@
+//! JavaScript bindings to verify beacons offline, such as in a browser.
+//!
+//! Beacons and chain info are provided as JSON, in the format served by drand HTTP relays. No request is performed.
+//! Failures are thrown as strings.
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{
+    beacon::{ApiBeacon, BeaconError},
+    DrandError,
+};
+
+/// Verify `beacon_json` signature and randomness against `chain_info_json`.
+/// Returns `false` if the beacon is invalid, and throws if either input cannot be parsed.
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen(js_name = verifyBeacon))]
+pub fn verify_beacon(chain_info_json: &str, beacon_json: &str) -> Result<bool, String> {
+    match crate::verify_beacon_json(chain_info_json, beacon_json) {
+        Ok(_verified) => Ok(true),
+        Err(DrandError::Beacon(e)) if matches!(*e, BeaconError::Validation { .. }) => Ok(false),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Randomness of `beacon_json`. It is not verified, use `verifyBeacon` beforehand.
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn randomness(beacon_json: &str) -> Result<Vec<u8>, String> {
+    Ok(parse_beacon(beacon_json)?.randomness())
+}
+
+fn parse_beacon(beacon_json: &str) -> Result<ApiBeacon, String> {
+    serde_json::from_str(beacon_json).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        beacon::tests::{chained_beacon, invalid_beacon, unchained_beacon_on_g1},
+        chain::tests::{chained_chain_info, unchained_chain_on_g1_info},
+    };
+
+    use super::*;
+
+    fn json<T: serde::Serialize>(value: T) -> String {
+        serde_json::to_string(&value).unwrap()
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn verify_beacon_works() {
+        let mainnet = json(chained_chain_info());
+        assert_eq!(verify_beacon(&mainnet, &json(chained_beacon())), Ok(true));
+        assert_eq!(verify_beacon(&mainnet, &json(invalid_beacon())), Ok(false));
+        assert_eq!(
+            verify_beacon(
+                &json(unchained_chain_on_g1_info()),
+                &json(unchained_beacon_on_g1())
+            ),
+            Ok(true)
+        );
+        assert!(verify_beacon(&mainnet, "{}").is_err());
+        assert!(verify_beacon("not json", &json(chained_beacon())).is_err());
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test::wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn randomness_works() {
+        assert_eq!(
+            randomness(&json(chained_beacon())),
+            Ok(chained_beacon().randomness())
+        );
+        assert!(randomness("{}").is_err());
+    }
+}
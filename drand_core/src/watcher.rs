@@ -0,0 +1,192 @@
+use std::{
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    beacon::{BeaconError, RandomnessBeacon},
+    chain::{ChainClient, ChainInfo},
+    DrandError, Result,
+};
+
+/// Number of attempts at retrieving a round that is due, but not yet published by the remote.
+const NOT_FOUND_ATTEMPTS: u32 = 5;
+/// Delay between attempts at retrieving a round not yet published.
+const NOT_FOUND_DELAY: Duration = Duration::from_millis(500);
+
+/// Iterator over beacons, as they are produced by the chain.
+/// Each call waits for the next round to be due, then retrieves and validates it based on the client options.
+/// Rounds are yielded in order, without skipping any. If the consumer falls behind, due rounds are retrieved immediately.
+/// On error, the same round is attempted again on the next call.
+pub struct Watcher<'a, C: ChainClient + ?Sized> {
+    client: &'a C,
+    info: Option<ChainInfo>,
+    next_round: Option<u64>,
+}
+
+impl<'a, C: ChainClient + ?Sized> Watcher<'a, C> {
+    /// Watch beacons starting with the next round to be produced.
+    pub fn new(client: &'a C) -> Self {
+        Self {
+            client,
+            info: None,
+            next_round: None,
+        }
+    }
+
+    /// Watch beacons starting at `round`, which might already have been produced.
+    pub fn starting_at(client: &'a C, round: u64) -> Self {
+        Self {
+            client,
+            info: None,
+            next_round: Some(round),
+        }
+    }
+
+    fn chain_info(&mut self) -> Result<ChainInfo> {
+        match &self.info {
+            Some(info) => Ok(info.clone()),
+            None => {
+                let info = self.client.chain_info()?;
+                self.info = Some(info.clone());
+                Ok(info)
+            }
+        }
+    }
+
+    fn next_beacon(&mut self) -> Result<RandomnessBeacon> {
+        let info = self.chain_info()?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let round = *self
+            .next_round
+            .get_or_insert_with(|| info.time_info().round_at(now.as_secs()) + 1);
+
+        // round `round` is produced once round `round - 1` period is over
+        let round_time = Duration::from_secs(info.time_info().time_of_round(round));
+        thread::sleep(round_time.saturating_sub(now));
+
+        let mut attempt = 1;
+        let beacon = loop {
+            match self.client.get(round) {
+                Err(DrandError::Beacon(e))
+                    if matches!(*e, BeaconError::NotFound) && attempt < NOT_FOUND_ATTEMPTS =>
+                {
+                    thread::sleep(NOT_FOUND_DELAY);
+                    attempt += 1;
+                }
+                beacon => break beacon?,
+            }
+        };
+        self.next_round = Some(round + 1);
+        Ok(beacon)
+    }
+}
+
+impl<'a, C: ChainClient + ?Sized> Iterator for Watcher<'a, C> {
+    type Item = Result<RandomnessBeacon>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.next_beacon())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        beacon::tests::chained_beacon, chain::tests::chained_chain_info, ChainOptions, HttpClient,
+    };
+
+    use super::*;
+
+    /// Remote for a chain with a 1 second period, which has produced `rounds` rounds so far.
+    fn advancing_server(rounds: u64) -> mockito::ServerGuard {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        let mut info = serde_json::to_value(chained_chain_info()).unwrap();
+        info["period"] = 1.into();
+        info["genesis_time"] = (now.as_secs() + 1 - rounds).into();
+
+        let mut server = mockito::Server::new();
+        server
+            .mock("GET", "/info")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(info.to_string())
+            .create();
+        server
+    }
+
+    /// Serve beacons for any requested round.
+    /// Mocks created beforehand for specific rounds take precedence, until they have been hit as expected.
+    fn serve_beacons(server: &mut mockito::ServerGuard) {
+        server
+            .mock(
+                "GET",
+                mockito::Matcher::Regex("^/public/[0-9]+$".to_owned()),
+            )
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body_from_request(|request| {
+                let round: u64 = request
+                    .path()
+                    .trim_start_matches("/public/")
+                    .parse()
+                    .unwrap();
+                let mut beacon = serde_json::to_value(chained_beacon()).unwrap();
+                beacon["round"] = round.into();
+                beacon.to_string().into_bytes()
+            })
+            .create();
+    }
+
+    fn client(server: &mockito::ServerGuard) -> HttpClient {
+        HttpClient::new(
+            server.url().as_str(),
+            Some(ChainOptions::new(false, true, None)),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn watcher_works() {
+        let mut server = advancing_server(3);
+        // the upcoming round is not published on time
+        let late_mock = server
+            .mock(
+                "GET",
+                mockito::Matcher::Regex("^/public/[0-9]+$".to_owned()),
+            )
+            .match_query(mockito::Matcher::Any)
+            .with_status(404)
+            .expect(1)
+            .create();
+        serve_beacons(&mut server);
+        let client = client(&server);
+
+        let rounds: Vec<u64> = client
+            .watch()
+            .take(2)
+            .map(|beacon| beacon.unwrap().round())
+            .collect();
+        assert!(rounds[0] >= 4);
+        assert_eq!(rounds[1], rounds[0] + 1);
+        late_mock.assert();
+    }
+
+    #[test]
+    fn watcher_does_not_skip_rounds() {
+        let mut server = advancing_server(5);
+        serve_beacons(&mut server);
+        let client = client(&server);
+
+        // rounds already produced are retrieved right away
+        let rounds: Vec<u64> = Watcher::starting_at(&client, 2)
+            .take(3)
+            .map(|beacon| beacon.unwrap().round())
+            .collect();
+        assert_eq!(rounds, vec![2, 3, 4]);
+    }
+}